@@ -0,0 +1,124 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! `tastytrade-cli doctor`: end-to-end checks of configuration, login and streaming.
+
+use tastytrade::TastyTrade;
+use tastytrade::utils::config::TastyTradeConfig;
+
+struct Check<'a> {
+    name: &'a str,
+    result: Result<String, String>,
+    hint: &'static str,
+}
+
+impl Check<'_> {
+    fn print(&self) {
+        match &self.result {
+            Ok(detail) => println!("[PASS] {:<10} {}", self.name, detail),
+            Err(err) => {
+                println!("[FAIL] {:<10} {}", self.name, err);
+                println!("       hint: {}", self.hint);
+            }
+        }
+    }
+}
+
+fn hint_for_step(step: &str) -> &'static str {
+    match step {
+        "token" => {
+            "the account may lack market data access; check API access in your tastytrade settings"
+        }
+        "dns" => "the DXLink host could not be resolved; check DNS or proxy settings",
+        "websocket" => {
+            "outbound websocket traffic on port 443 may be blocked by a firewall or proxy"
+        }
+        _ => "DXLink rejected the channel setup; retry later or contact support with this report",
+    }
+}
+
+/// Runs every check in order and returns `true` when all of them passed.
+///
+/// Later checks depend on earlier ones, so the run stops at the first failure.
+pub async fn run(config: &TastyTradeConfig) -> bool {
+    let problems = config.validate();
+    let check = Check {
+        name: "config",
+        result: if problems.is_empty() {
+            Ok(format!(
+                "{} environment, user {}",
+                if config.use_demo {
+                    "demo"
+                } else {
+                    "production"
+                },
+                config.username
+            ))
+        } else {
+            Err(problems
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join("; "))
+        },
+        hint: "copy .env.example to .env and fill in the missing values",
+    };
+    check.print();
+    if check.result.is_err() {
+        return false;
+    }
+
+    let tasty = match TastyTrade::login(config).await {
+        Ok(tasty) => {
+            Check {
+                name: "login",
+                result: Ok(format!("session opened against {}", config.base_url)),
+                hint: "",
+            }
+            .print();
+            tasty
+        }
+        Err(e) => {
+            Check {
+                name: "login",
+                result: Err(e.to_string()),
+                hint: "verify the credentials and that TASTYTRADE_USE_DEMO matches the account's environment",
+            }
+            .print();
+            return false;
+        }
+    };
+
+    let check = Check {
+        name: "accounts",
+        result: match tasty.accounts().await {
+            Ok(accounts) if accounts.is_empty() => Err("no accounts returned".to_string()),
+            Ok(accounts) => Ok(format!("{} account(s) found", accounts.len())),
+            Err(e) => Err(e.to_string()),
+        },
+        hint: "make sure the user owns at least one account and has API access enabled",
+    };
+    check.print();
+    if check.result.is_err() {
+        return false;
+    }
+
+    let report = tasty.diagnose_streaming().await;
+    for step in &report.steps {
+        Check {
+            name: &step.name,
+            result: if step.passed {
+                Ok(format!("{} ({} ms)", step.detail, step.elapsed.as_millis()))
+            } else {
+                Err(step.detail.clone())
+            },
+            hint: hint_for_step(&step.name),
+        }
+        .print();
+    }
+
+    report.passed()
+}
@@ -4,8 +4,11 @@
    Date: 5/3/25
 ******************************************************************************/
 
+mod doctor;
+mod strategy;
+
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use crossterm::{
     event::{self, EventStream, KeyCode, KeyEventKind},
     execute,
@@ -19,38 +22,55 @@ use tui::{
     backend::{Backend, CrosstermBackend},
     layout::{Constraint, Layout},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Row, Table, TableState},
+    widgets::{Block, Borders, Cell, Row, Sparkline, Table, TableState},
 };
 
 use rust_decimal::{
     Decimal,
-    prelude::{FromPrimitive, Zero},
+    prelude::{FromPrimitive, ToPrimitive, Zero},
 };
 use serde::Serialize;
+use tastytrade::accounts::Account;
 use tastytrade::api::quote_streaming::DxFeedSymbol;
+use tastytrade::prelude::{InstrumentType, NetLiqHistoryPoint, TimeBack};
 use tastytrade::streaming::account_streaming::{AccountEvent, AccountMessage};
+use tastytrade::streaming::order_tracker::OrderTracker;
 use tastytrade::utils::config::TastyTradeConfig;
 use tastytrade::{
-    QuantityDirection, Symbol, TastyTrade,
+    FullPosition, LiveOrderRecord, QuantityDirection, Symbol, TastyTrade,
     dxfeed::{self, Event, EventData},
 };
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// tastytrade username or email
+    /// tastytrade username or email (overrides TASTYTRADE_USERNAME)
     #[arg(short, long)]
-    login: String,
+    login: Option<String>,
 
-    /// tastytrade password
+    /// tastytrade password (overrides TASTYTRADE_PASSWORD)
     #[arg(short, long)]
-    password: String,
+    password: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check configuration, login, accounts and streaming connectivity step by step
+    Doctor,
+    /// Build an option strategy from live greeks, then preview or place it
+    Strategy {
+        #[command(subcommand)]
+        command: strategy::StrategyCommand,
+    },
 }
 
 #[derive(DebugPretty, DisplaySimple, Serialize)]
 struct SimpleGreeks {
-    theta: f64,
-    delta: f64,
+    theta: Option<f64>,
+    delta: Option<f64>,
 }
 
 #[derive(DebugPretty, DisplaySimple, Serialize)]
@@ -62,6 +82,7 @@ struct PriceRecord {
     multiplier: Decimal,
     direction: QuantityDirection,
     greeks: SimpleGreeks,
+    position: FullPosition,
 }
 
 #[derive(Default)]
@@ -70,23 +91,44 @@ struct UnderlyingGroup {
     pub records: BTreeMap<DxFeedSymbol, PriceRecord>,
 }
 
+/// Price change applied by the re-price keys.
+const REPRICE_STEP: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+
+#[derive(PartialEq, Eq)]
+enum Pane {
+    Positions,
+    Orders,
+}
+
 struct App {
     state: TableState,
     groups: BTreeMap<Symbol, UnderlyingGroup>,
     num_lines: usize,
     balances: BTreeMap<String, Decimal>,
+    orders: OrderTracker,
+    order_state: TableState,
+    focus: Pane,
+    status: String,
+    net_liq_history: BTreeMap<String, Decimal>,
 }
 
 impl App {
     fn new(
         records: BTreeMap<Symbol, UnderlyingGroup>,
         balances: BTreeMap<String, Decimal>,
+        orders: OrderTracker,
+        net_liq_history: BTreeMap<String, Decimal>,
     ) -> Self {
         let mut this = Self {
             state: TableState::default(),
             groups: records,
             num_lines: 0,
             balances,
+            orders,
+            order_state: TableState::default(),
+            focus: Pane::Positions,
+            status: String::new(),
+            net_liq_history,
         };
 
         this.update_num_lines();
@@ -118,6 +160,42 @@ impl App {
         self.update_num_lines();
     }
 
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            Pane::Positions => Pane::Orders,
+            Pane::Orders => Pane::Positions,
+        };
+    }
+
+    pub fn next_order(&mut self) {
+        let len = self.orders.len();
+        if len == 0 {
+            return;
+        }
+        let i = self.order_state.selected().map_or(0, |i| (i + 1) % len);
+        self.order_state.select(Some(i));
+    }
+
+    pub fn previous_order(&mut self) {
+        let len = self.orders.len();
+        if len == 0 {
+            return;
+        }
+        let i = self
+            .order_state
+            .selected()
+            .map_or(0, |i| if i == 0 { len - 1 } else { i - 1 });
+        self.order_state.select(Some(i));
+    }
+
+    pub fn selected_order(&self) -> Option<LiveOrderRecord> {
+        let i = self.order_state.selected()?;
+        self.orders
+            .working_orders()
+            .nth(i)
+            .map(|tracked| tracked.record.clone())
+    }
+
     pub fn next(&mut self) {
         let i = match self.state.selected() {
             Some(i) => {
@@ -162,35 +240,67 @@ impl App {
 async fn main() -> Result<()> {
     let args = Args::parse();
 
+    let mut config = TastyTradeConfig::from_env();
+    if let Some(login) = args.login {
+        config.username = login;
+    }
+    if let Some(password) = args.password {
+        config.password = password;
+    }
+
+    if let Some(Command::Doctor) = args.command {
+        if doctor::run(&config).await {
+            println!("\nAll checks passed.");
+            return Ok(());
+        }
+        std::process::exit(1);
+    }
+
     println!("Logging in...");
-    let config = TastyTradeConfig::from_env();
     let tasty = TastyTrade::login(&config)
         .await
         .context("Logging into tastytrade")?;
 
+    if let Some(Command::Strategy { command }) = args.command {
+        return strategy::run(&tasty, command).await;
+    }
+
     println!("Downloading account info...");
 
     let account_streamer = tasty.create_account_streamer().await?;
-    let mut positions = Vec::new();
+    let mut quote_streamer = tasty.create_quote_streamer().await?;
+    let accounts = tasty.accounts().await?;
+    let mut live_greeks = Vec::new();
     let mut balances = BTreeMap::new();
-    for account in tasty.accounts().await.unwrap() {
-        account_streamer.subscribe_to_account(&account).await;
-        positions.extend(account.positions().await.unwrap());
+    let mut live_orders = Vec::new();
+    let mut net_liq_history = BTreeMap::new();
+    for account in &accounts {
+        account_streamer.subscribe_to_account(account).await;
+        live_greeks.push(account.live_greeks_positions(&mut quote_streamer).await?);
         balances.insert(account.number().0, account.balance().await?.cash_balance);
+        live_orders.extend(account.live_orders().await?);
+        add_net_liq_history(
+            &mut net_liq_history,
+            account.net_liq_history(TimeBack::Month).await?,
+        );
     }
 
-    println!("Downloading symbols...");
-    let sym_futures = positions
-        .iter()
-        .map(|pos| tasty.get_streamer_symbol(&pos.instrument_type, &pos.symbol));
-
-    let stream_syms = futures::future::join_all(sym_futures).await;
-    let stream_syms: Result<Vec<_>, _> = stream_syms.into_iter().collect();
-    let stream_syms = stream_syms?;
-
     println!("Setting up records...");
     let mut records: BTreeMap<Symbol, UnderlyingGroup> = BTreeMap::new();
-    for (pos, stream_sym) in positions.iter().zip(stream_syms.iter()) {
+    let mut stream_syms = Vec::new();
+    for entry in live_greeks.iter().flat_map(|live| live.snapshot()) {
+        let pos = &entry.position;
+        // Only options come with their streamer symbol; quotes need it for the rest too
+        let streamer_symbol = match pos.instrument_type {
+            InstrumentType::EquityOption | InstrumentType::FutureOption => {
+                entry.streamer_symbol.clone()
+            }
+            _ => {
+                tasty
+                    .get_streamer_symbol(&pos.instrument_type, &pos.symbol)
+                    .await?
+            }
+        };
         let record = PriceRecord {
             symbol: pos.symbol.clone(),
             open: pos.average_open_price.round_dp(2),
@@ -199,20 +309,23 @@ async fn main() -> Result<()> {
             multiplier: pos.multiplier,
             direction: pos.quantity_direction,
             greeks: SimpleGreeks {
-                theta: 0.0,
-                delta: 0.0,
+                theta: None,
+                delta: None,
             },
+            position: pos.clone(),
         };
         records
             .entry(pos.underlying_symbol.clone())
             .or_default()
             .records
-            .insert(stream_sym.clone(), record);
+            .insert(streamer_symbol.clone(), record);
+        stream_syms.push(streamer_symbol);
     }
+    let mut greeks_updates =
+        futures::stream::select_all(live_greeks.into_iter().map(|live| live.into_stream()));
 
     print!("Setting up quote streaming...");
-    let mut quote_streamer = tasty.create_quote_streamer().await?;
-    let mut quote_sub = quote_streamer.create_sub(dxfeed::DXF_ET_QUOTE | dxfeed::DXF_ET_GREEKS);
+    let mut quote_sub = quote_streamer.create_sub(dxfeed::DXF_ET_QUOTE);
     quote_sub.add_symbols(&stream_syms);
 
     enable_raw_mode()?;
@@ -221,31 +334,37 @@ async fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new(records, balances);
+    let mut app = App::new(
+        records,
+        balances,
+        OrderTracker::from_live_orders(live_orders),
+        net_liq_history,
+    );
     let mut keyboard_event_stream = EventStream::new();
 
     loop {
         tokio::select! {
             ev = quote_sub.get_event() => {
-                if let Ok(Event { sym, data }) = ev {
-                    if let Some(record) = app.get_record(DxFeedSymbol(sym)) {
-                        match data {
-                            EventData::Quote(quote) => {
-                                record.current = Decimal::from_f64((quote.bid_price + quote.ask_price) / 2.0).unwrap_or_default();
-                            }
-                            EventData::Greeks(greeks) => {
-                                record.greeks = SimpleGreeks {
-                                    theta: greeks.theta,
-                                    delta: greeks.delta,
-                                }
-                            }
-                            _ => {}
-                        }
+                if let Ok(Event { sym, data: EventData::Quote(quote), .. }) = ev {
+                    // A quote missing a side (NaN) keeps the last price
+                    if let Some(record) = app.get_record(DxFeedSymbol(sym))
+                        && let Some(mid) = quote.mid().and_then(Decimal::from_f64)
+                    {
+                        record.current = mid;
                     }
                 }
             }
+            Some(update) = greeks_updates.next() => {
+                if let (Some(record), Some(greeks)) = (app.get_record(update.streamer_symbol), update.greeks) {
+                    record.greeks = SimpleGreeks {
+                        theta: greeks.theta().or(record.greeks.theta),
+                        delta: greeks.delta().or(record.greeks.delta),
+                    };
+                }
+            }
             ev = account_streamer.get_event() => {
                 if let Ok(AccountEvent::AccountMessage(msg)) = ev {
+                    app.orders.apply(&msg);
                     if let AccountMessage::AccountBalance(bal) = *msg {
                         app.balances.insert(bal.account_number.0, bal.cash_balance);
                     }
@@ -258,9 +377,17 @@ async fn main() -> Result<()> {
                             if key.kind == KeyEventKind::Press {
                                 match key.code {
                                     KeyCode::Char('q') => break,
+                                    KeyCode::Tab => app.toggle_focus(),
+                                    KeyCode::Down if app.focus == Pane::Orders => app.next_order(),
+                                    KeyCode::Up if app.focus == Pane::Orders => app.previous_order(),
                                     KeyCode::Down => app.next(),
                                     KeyCode::Up => app.previous(),
                                     KeyCode::Char(' ') => app.toggle_group(),
+                                    KeyCode::Char(c @ ('c' | '+' | '-')) if app.focus == Pane::Orders => {
+                                        if let Some(order) = app.selected_order() {
+                                            app.status = order_action(&accounts, &order, c).await;
+                                        }
+                                    }
                                     _ => {}
                                 }
                             }
@@ -283,12 +410,149 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Cancels (`c`) or re-prices (`+`/`-`) a working order and describes the outcome.
+async fn order_action(accounts: &[Account<'_>], order: &LiveOrderRecord, key: char) -> String {
+    let Some(account) = accounts
+        .iter()
+        .find(|account| account.number() == order.account_number)
+    else {
+        return format!("order {}: unknown account", order.id.0);
+    };
+    let result = match key {
+        'c' => account.cancel_order(order.id.clone()).await,
+        '+' => {
+            account
+                .reprice_order(order, order.price + REPRICE_STEP)
+                .await
+        }
+        _ => {
+            account
+                .reprice_order(order, order.price - REPRICE_STEP)
+                .await
+        }
+    };
+    match result {
+        Ok(resp) => format!("order {}: {}", order.id.0, resp.status),
+        Err(e) => format!("order {}: {}", order.id.0, e),
+    }
+}
+
+/// Sums the closing net liq of every account per bar.
+fn add_net_liq_history(total: &mut BTreeMap<String, Decimal>, points: Vec<NetLiqHistoryPoint>) {
+    for point in points {
+        *total.entry(point.time).or_default() += point.close;
+    }
+}
+
+/// Net liq history scaled for a sparkline: cents above the period's low.
+fn sparkline_data(history: &BTreeMap<String, Decimal>) -> Vec<u64> {
+    let Some(low) = history.values().min().copied() else {
+        return Vec::new();
+    };
+    history
+        .values()
+        .map(|value| {
+            ((value - low) * Decimal::ONE_HUNDRED)
+                .to_u64()
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn net_liq_sparkline(app: &App, data: &[u64]) -> Sparkline<'_> {
+    let title = match (
+        app.net_liq_history.values().next(),
+        app.net_liq_history.values().last(),
+    ) {
+        (Some(first), Some(last)) => format!(
+            "NET LIQ 1M  {} -> {}  ({:+})",
+            first.round_dp(2),
+            last.round_dp(2),
+            (last - first).round_dp(2)
+        ),
+        _ => "NET LIQ 1M  no history".to_string(),
+    };
+    Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .data(data)
+        .style(Style::default().fg(Color::Green))
+}
+
+fn format_age(age: chrono::Duration) -> String {
+    let secs = age.num_seconds().max(0);
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    } else {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
+}
+
+fn orders_table(app: &App) -> Table<'static> {
+    let now = chrono::Utc::now();
+    let header = Row::new(
+        ["ID", "SYMBOL", "TYPE", "PRICE", "SIZE", "STATUS", "AGE"]
+            .iter()
+            .map(|h| Cell::from(*h).style(Style::default().fg(Color::Red))),
+    )
+    .style(Style::default().bg(Color::Blue))
+    .height(1);
+    let rows: Vec<Row> = app
+        .orders
+        .working_orders()
+        .map(|tracked| {
+            let order = &tracked.record;
+            Row::new(vec![
+                order.id.0.to_string(),
+                order.underlying_symbol.0.clone(),
+                format!("{:?}", order.order_type),
+                format!("{} {:?}", order.price, order.price_effect),
+                order.size.to_string(),
+                order.status.to_string(),
+                format_age(tracked.age(now)),
+            ])
+        })
+        .collect();
+    let title = format!(
+        "WORKING ORDERS  [Tab] switch pane  [c] cancel  [+/-] re-price  {}",
+        app.status
+    );
+    let highlight = if app.focus == Pane::Orders {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::default()
+    };
+    Table::new(rows)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(highlight)
+        .highlight_symbol(">> ")
+        .widths(&[
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(16),
+            Constraint::Length(6),
+            Constraint::Length(18),
+            Constraint::Length(8),
+        ])
+}
+
 fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     let rects = Layout::default()
-        .constraints([Constraint::Percentage(100)].as_ref())
+        .constraints(
+            [
+                Constraint::Length(6),
+                Constraint::Percentage(65),
+                Constraint::Percentage(30),
+            ]
+            .as_ref(),
+        )
         .margin(2)
         .split(f.size());
 
+    let history = sparkline_data(&app.net_liq_history);
+    f.render_widget(net_liq_sparkline(app, &history), rects[0]);
+
     let selected_style = Style::default().add_modifier(Modifier::REVERSED);
     let normal_style = Style::default().bg(Color::Blue);
     let header_cells = [
@@ -297,7 +561,8 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
         "CURRENT",
         "AMOUNT",
         "TRADE PRICE",
-        "PROFIT",
+        "UNREALIZED",
+        "REALIZED",
         "THETA",
         "DELTA",
         "NET LIQ",
@@ -326,6 +591,7 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
         .flat_map(|(underlying_symbol, records)| {
             let mut rows = vec![vec![]];
             let mut profit_sum = Decimal::zero();
+            let mut realized_sum = Decimal::zero();
             let mut net_liq_sum = Decimal::zero();
             for rec in records.records.values() {
                 let to_net = |value: Decimal| -> Decimal {
@@ -339,8 +605,10 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
                         })
                     .round_dp(2)
                 };
-                let profit = to_net(rec.current - rec.open);
+                let profit = rec.position.unrealized_pnl(rec.current).round_dp(2);
                 profit_sum += profit;
+                let realized = rec.position.realized_day_pnl().round_dp(2);
+                realized_sum += realized;
 
                 let net_liq = to_net(rec.current);
                 net_liq_sum += net_liq;
@@ -348,8 +616,14 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
                 if !records.open {
                     continue;
                 }
-                let theta = to_net(Decimal::from_f64(rec.greeks.theta).unwrap());
-                let delta = to_net(Decimal::from_f64(rec.greeks.delta).unwrap());
+                let greek = |value: Option<f64>| {
+                    value
+                        .and_then(Decimal::from_f64)
+                        .map(|value| to_net(value).to_string())
+                        .unwrap_or_default()
+                };
+                let theta = greek(rec.greeks.theta);
+                let delta = greek(rec.greeks.delta);
 
                 let name = if rec.symbol == *underlying_symbol {
                     "SHARES".to_owned()
@@ -373,8 +647,9 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
                     .to_string(),
                     rec.open.to_string(),
                     profit.to_string(),
-                    theta.to_string(),
-                    delta.to_string(),
+                    realized.to_string(),
+                    theta,
+                    delta,
                     net_liq.to_string(),
                 ];
                 rows.push(cells)
@@ -391,6 +666,7 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
                 "".to_owned(),
                 "".to_owned(),
                 profit_sum.round_dp(2).to_string(),
+                realized_sum.round_dp(2).to_string(),
                 "".to_owned(),
                 "".to_owned(),
                 net_liq_sum.round_dp(2).to_string(),
@@ -427,7 +703,11 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
             Constraint::Length(12),
             Constraint::Length(12),
             Constraint::Length(12),
+            Constraint::Length(12),
         ]);
 
-    f.render_stateful_widget(t, rects[0], &mut app.state);
+    f.render_stateful_widget(t, rects[1], &mut app.state);
+
+    let orders = orders_table(app);
+    f.render_stateful_widget(orders, rects[2], &mut app.order_state);
 }
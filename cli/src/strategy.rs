@@ -0,0 +1,247 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! `tastytrade-cli strategy`: builds option strategies from live greeks and previews or
+//! places them.
+//!
+//! `strategy ic --underlying SPY --dte 30 --delta .16 --width 5 --dry-run` picks the
+//! expiration closest to 30 days, sells the put and the call whose deltas are closest
+//! to ±0.16, buys the wings 5 points further out and sends the four legs as one order
+//! for the mid-price credit. With `--dry-run` the order is only previewed.
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::NaiveDate;
+use clap::{Args, Subcommand};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use std::collections::HashMap;
+use std::time::Duration;
+use tastytrade::TastyTrade;
+use tastytrade::api::quote_streaming::DxFeedSymbol;
+use tastytrade::dxfeed::{self, EventData};
+use tastytrade::prelude::{Expiration, StrategyBuilder, Strike};
+
+/// How long to wait for greeks and quotes of the expiration's options.
+const MARKET_DATA_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Subcommand, Debug)]
+pub enum StrategyCommand {
+    /// Short iron condor: short put and call at a target delta, long wings around them
+    Ic(IronCondorArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct IronCondorArgs {
+    /// Underlying symbol, e.g. SPY
+    #[arg(long)]
+    underlying: String,
+    /// Target days to expiration; the closest expiration is used
+    #[arg(long, default_value_t = 30)]
+    dte: u64,
+    /// Absolute delta of the short strikes
+    #[arg(long, default_value_t = 0.16)]
+    delta: f64,
+    /// Distance between each short strike and its long wing
+    #[arg(long, default_value_t = Decimal::from(5))]
+    width: Decimal,
+    /// Number of condors
+    #[arg(long, default_value_t = 1)]
+    quantity: u32,
+    /// Account number; the first account when omitted
+    #[arg(long)]
+    account: Option<String>,
+    /// Preview buying power and fees without placing the order
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Latest delta and mid price of one option.
+#[derive(Debug, Default, Clone, Copy)]
+struct OptionData {
+    delta: Option<f64>,
+    mid: Option<f64>,
+}
+
+/// The four strikes of an iron condor.
+struct Condor<'a> {
+    long_put: &'a Strike,
+    short_put: &'a Strike,
+    short_call: &'a Strike,
+    long_call: &'a Strike,
+}
+
+pub async fn run(tasty: &TastyTrade, command: StrategyCommand) -> Result<()> {
+    match command {
+        StrategyCommand::Ic(args) => iron_condor(tasty, args).await,
+    }
+}
+
+async fn iron_condor(tasty: &TastyTrade, args: IronCondorArgs) -> Result<()> {
+    let accounts = tasty.accounts().await?;
+    let account = match &args.account {
+        Some(number) => accounts.iter().find(|a| &a.number().0 == number),
+        None => accounts.first(),
+    }
+    .ok_or_else(|| anyhow!("account not found"))?;
+
+    let chain = tasty
+        .nested_option_chain_for(args.underlying.as_str())
+        .await
+        .with_context(|| format!("Fetching the option chain of {}", args.underlying))?;
+    let expiration = chain
+        .expirations
+        .iter()
+        .min_by_key(|exp| exp.days_to_expiration.abs_diff(args.dte))
+        .ok_or_else(|| anyhow!("{} has no expirations", args.underlying))?;
+    println!(
+        "Expiration {} ({} DTE), {} strikes",
+        expiration.expiration_date,
+        expiration.days_to_expiration,
+        expiration.strikes.len()
+    );
+
+    let data = market_data(tasty, expiration).await?;
+    let condor = select_strikes(expiration, &data, args.delta, args.width)?;
+    let mid = |symbol: &DxFeedSymbol| {
+        data.get(symbol)
+            .and_then(|d| d.mid)
+            .ok_or_else(|| anyhow!("no quote for {}", symbol.0))
+    };
+    let credit = mid(&condor.short_put.put_streamer_symbol)?
+        + mid(&condor.short_call.call_streamer_symbol)?
+        - mid(&condor.long_put.put_streamer_symbol)?
+        - mid(&condor.long_call.call_streamer_symbol)?;
+    let credit = Decimal::from_f64(credit)
+        .ok_or_else(|| anyhow!("invalid credit {credit}"))?
+        .round_dp(2);
+    if credit <= Decimal::ZERO {
+        bail!("the condor does not collect a credit at mid ({credit})");
+    }
+    println!(
+        "Long put {} / short put {} / short call {} / long call {} for {} credit",
+        condor.long_put.strike_price,
+        condor.short_put.strike_price,
+        condor.short_call.strike_price,
+        condor.long_call.strike_price,
+        credit
+    );
+
+    let expiration_date = NaiveDate::parse_from_str(&expiration.expiration_date, "%Y-%m-%d")
+        .with_context(|| format!("Parsing expiration {}", expiration.expiration_date))?;
+    let order = StrategyBuilder::new(&chain, args.quantity).iron_condor(
+        expiration_date,
+        [
+            condor.long_put.strike_price,
+            condor.short_put.strike_price,
+            condor.short_call.strike_price,
+            condor.long_call.strike_price,
+        ],
+        credit,
+    )?;
+    if args.dry_run {
+        let preview = account.dry_run(&order).await?.data;
+        let effect = &preview.buying_power_effect;
+        println!(
+            "Buying power change {} {}, fees {}",
+            effect.change_in_buying_power,
+            effect.change_in_buying_power_effect,
+            preview.fee_calculation.total_fees
+        );
+        for warning in &preview.warnings {
+            println!("warning {}: {}", warning.code, warning.message);
+        }
+    } else {
+        let placed = account.place_order(&order).await?.data;
+        println!(
+            "Placed order {} ({})",
+            placed.order.id.0, placed.order.status
+        );
+    }
+    Ok(())
+}
+
+/// Streams greeks and quotes of every option of `expiration` until all have both, or
+/// [`MARKET_DATA_TIMEOUT`] elapses.
+async fn market_data(
+    tasty: &TastyTrade,
+    expiration: &Expiration,
+) -> Result<HashMap<DxFeedSymbol, OptionData>> {
+    let symbols: Vec<DxFeedSymbol> = expiration
+        .strikes
+        .iter()
+        .flat_map(|s| {
+            [
+                s.put_streamer_symbol.clone(),
+                s.call_streamer_symbol.clone(),
+            ]
+        })
+        .collect();
+    let mut streamer = tasty.create_quote_streamer().await?;
+    let mut sub = streamer.create_sub(dxfeed::DXF_ET_GREEKS | dxfeed::DXF_ET_QUOTE);
+    sub.add_symbols(&symbols);
+
+    println!("Waiting for greeks of {} options...", symbols.len());
+    let mut data: HashMap<DxFeedSymbol, OptionData> = HashMap::new();
+    let complete = |data: &HashMap<DxFeedSymbol, OptionData>| {
+        data.len() == symbols.len() && data.values().all(|d| d.delta.is_some() && d.mid.is_some())
+    };
+    let collect = async {
+        while let Ok(event) = sub.get_event().await {
+            let entry = data.entry(DxFeedSymbol(event.sym)).or_default();
+            // A missing (NaN) value keeps the last one received
+            match event.data {
+                EventData::Greeks(greeks) => entry.delta = greeks.delta().or(entry.delta),
+                EventData::Quote(quote) => entry.mid = quote.mid().or(entry.mid),
+                _ => {}
+            }
+            if complete(&data) {
+                break;
+            }
+        }
+    };
+    // Strikes far out of the money may never quote; go on with what arrived
+    let _ = tokio::time::timeout(MARKET_DATA_TIMEOUT, collect).await;
+    Ok(data)
+}
+
+/// Short strikes at the deltas closest to `-delta` (put) and `delta` (call), wings
+/// `width` further out.
+fn select_strikes<'a>(
+    expiration: &'a Expiration,
+    data: &HashMap<DxFeedSymbol, OptionData>,
+    delta: f64,
+    width: Decimal,
+) -> Result<Condor<'a>> {
+    let closest = |target: f64, symbol: fn(&Strike) -> &DxFeedSymbol| {
+        expiration
+            .strikes
+            .iter()
+            .filter_map(|strike| Some((strike, data.get(symbol(strike))?.delta?)))
+            .min_by(|(_, a), (_, b)| (a - target).abs().total_cmp(&(b - target).abs()))
+            .map(|(strike, _)| strike)
+    };
+    let at = |price: Decimal| {
+        expiration
+            .strikes
+            .iter()
+            .find(|strike| strike.strike_price == price)
+            .ok_or_else(|| anyhow!("no strike at {price}"))
+    };
+
+    let short_put = closest(-delta.abs(), |s| &s.put_streamer_symbol)
+        .ok_or_else(|| anyhow!("no put greeks received"))?;
+    let short_call = closest(delta.abs(), |s| &s.call_streamer_symbol)
+        .ok_or_else(|| anyhow!("no call greeks received"))?;
+    if short_put.strike_price >= short_call.strike_price {
+        bail!("short strikes overlap; use a smaller delta");
+    }
+    Ok(Condor {
+        long_put: at(short_put.strike_price - width)?,
+        short_put,
+        short_call,
+        long_call: at(short_call.strike_price + width)?,
+    })
+}
@@ -27,10 +27,7 @@ async fn main() {
         }
         Err(e) => {
             error!("❌ Login failed: {}", e);
-            error!("\nTroubleshooting:");
-            error!("1. Verify your credentials are correct");
-            error!("2. Make sure TASTYTRADE_USE_DEMO=true for sandbox");
-            error!("3. Check if your account has API access enabled");
+            error!("Run `cargo run -p tastytrade-cli -- doctor` for a step-by-step diagnosis.");
             std::process::exit(1);
         }
     };
@@ -21,7 +21,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Check if we have valid credentials
     if !config.has_valid_credentials() {
-        error!("❌ No valid credentials found. Please set TASTYTRADE_USERNAME and TASTYTRADE_PASSWORD environment variables.");
+        error!(
+            "❌ No valid credentials found. Please set TASTYTRADE_USERNAME and TASTYTRADE_PASSWORD environment variables."
+        );
         return Err("Missing credentials".into());
     }
 
@@ -63,7 +65,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         i + 1,
                         equity.symbol.0,
                         equity.short_description,
-                        equity.listed_market,
+                        equity.listed_market.as_deref().unwrap_or("-"),
                         equity.is_etf,
                         equity.is_index
                     );
@@ -131,7 +133,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             debug!("   📊 {}: {}", equity.symbol.0, equity.short_description);
                             debug!(
                                 "      - Market: {} | ETF: {} | Active: {}",
-                                equity.listed_market, equity.is_etf, equity.active
+                                equity.listed_market.as_deref().unwrap_or("-"),
+                                equity.is_etf,
+                                equity.active
                             );
                         }
                     }
@@ -150,10 +154,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             info!("✅ Retrieved equity details for {}", equity.symbol.0);
                             debug!("   📊 Full details:");
                             debug!("      - Description: {}", equity.description);
-                            debug!("      - Listed Market: {}", equity.listed_market);
+                            debug!(
+                                "      - Listed Market: {}",
+                                equity.listed_market.as_deref().unwrap_or("-")
+                            );
                             debug!(
                                 "      - Market Time Collection: {}",
-                                equity.market_time_instrument_collection
+                                equity
+                                    .market_time_instrument_collection
+                                    .as_deref()
+                                    .unwrap_or("-")
                             );
                             if let Some(lendability) = &equity.lendability {
                                 debug!("      - Lendability: {}", lendability);
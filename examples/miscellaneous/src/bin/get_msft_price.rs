@@ -88,12 +88,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         debug!("Waiting for quote event...");
 
         match tokio::time::timeout(Duration::from_secs(1), quote_sub.get_event()).await {
-            Ok(Ok(Event { sym, data })) => {
+            Ok(Ok(Event { sym, data, .. })) => {
                 debug!("Received event for symbol: {}", sym);
-                if let EventData::Quote(quote) = data {
-                    // Use mid price
-                    let mid_price = (quote.bid_price + quote.ask_price) / 2.0;
-                    current_price = Some(Decimal::from_f64(mid_price).unwrap_or_default());
+                // Use mid price, once both sides are quoted
+                if let EventData::Quote(quote) = &data
+                    && let Some(mid_price) = quote.mid().and_then(Decimal::from_f64)
+                {
+                    current_price = Some(mid_price);
                     info!(
                         "Current price for {}: ${}",
                         symbol.0,
@@ -101,7 +102,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     );
                     break;
                 } else {
-                    debug!("Received non-quote event: {:?}", data);
+                    debug!("Received event without a mid price: {:?}", data);
                 }
             }
             Ok(Err(e)) => {
@@ -117,10 +117,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     while current_price.is_none() && tokio::time::Instant::now() < timeout {
         if let Ok(Event { data, .. }) = quote_sub.get_event().await {
-            if let EventData::Quote(quote) = data {
-                // Use mid price
-                let mid_price = Decimal::from_f64((quote.bid_price + quote.ask_price) / 2.0)
-                    .unwrap_or_default();
+            // Use mid price, once both sides are quoted
+            if let EventData::Quote(quote) = data
+                && let Some(mid_price) = quote.mid().and_then(Decimal::from_f64)
+            {
                 current_price = Some(mid_price);
                 info!(
                     "Current price for {}: ${}",
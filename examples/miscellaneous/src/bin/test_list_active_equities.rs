@@ -68,7 +68,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         // Show additional details for first few items
                         if i < 3 {
                             println!("      - Type: {:?}", equity.instrument_type);
-                            println!("      - Market: {}", equity.listed_market);
+                            println!(
+                                "      - Market: {}",
+                                equity.listed_market.as_deref().unwrap_or("-")
+                            );
                             println!("      - CUSIP: {:?}", equity.cusip);
                             println!("      - Is Index: {}", equity.is_index);
                             println!("      - Is ETF: {}", equity.is_etf);
@@ -27,10 +27,7 @@ async fn main() {
         }
         Err(e) => {
             eprintln!("❌ Login failed: {}", e);
-            eprintln!("\nTroubleshooting:");
-            eprintln!("1. Verify your credentials are correct");
-            eprintln!("2. Make sure TASTYTRADE_USE_DEMO=true for sandbox");
-            eprintln!("3. Check if your account has API access enabled");
+            eprintln!("Run `cargo run -p tastytrade-cli -- doctor` for a step-by-step diagnosis.");
             std::process::exit(1);
         }
     };
@@ -1,22 +1,25 @@
-use super::base::{Items, Paginated};
+use super::base::{Items, Paginated, WriteResponse};
 use crate::api::base::TastyResult;
-use crate::types::balance::{Balance, BalanceSnapshot, SnapshotTimeOfDay};
-use crate::types::order::{DryRunResult, Order, OrderId, OrderPlacedResult};
-use crate::{FullPosition, LiveOrderRecord, TastyTrade};
+use crate::types::balance::{
+    Balance, BalanceSnapshot, NetLiqHistoryPoint, SnapshotTimeOfDay, TimeBack,
+};
+use crate::types::order::{
+    ComplexOrder, ComplexOrderPlacedResult, DryRunResult, Order, OrderId, OrderPlacedResult,
+    OrderStatus, OrderType, PriceEffect, TimeInForce,
+};
+use crate::types::position::AdjustedPosition;
+use crate::types::validation::OrderValidator;
+use crate::{AsSymbol, FullPosition, LiveOrderRecord, Symbol, TastyTrade, TastyTradeError};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use futures_util::{StreamExt, stream};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-#[derive(
-    DebugPretty, DisplaySimple, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone,
-)]
-#[serde(transparent)]
-pub struct AccountNumber(pub String);
+pub use tastytrade_types::account::{AccountNumber, AuthorityLevel};
 
-impl<T: AsRef<str>> From<T> for AccountNumber {
-    fn from(value: T) -> Self {
-        Self(value.as_ref().to_owned())
-    }
-}
+/// Cancellations [`Account::cancel_all_orders`] keeps in flight at once.
+pub const CANCEL_ALL_CONCURRENCY: usize = 4;
 
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -33,6 +36,10 @@ pub struct AccountDetails {
     pub margin_or_cash: String,
     pub is_foreign: bool,
     pub funding_date: Option<String>,
+    #[serde(default)]
+    pub is_futures_approved: bool,
+    #[serde(default)]
+    pub created_at: Option<String>,
 }
 
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
@@ -42,6 +49,53 @@ pub struct AccountInner {
     pub authority_level: String,
 }
 
+/// Body of a price edit on a working order.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct OrderEdit<'a> {
+    time_in_force: &'a TimeInForce,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gtc_date: Option<NaiveDate>,
+    order_type: &'a OrderType,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    price: Decimal,
+    price_effect: &'a PriceEffect,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "rust_decimal::serde::arbitrary_precision_option"
+    )]
+    stop_trigger: Option<Decimal>,
+}
+
+/// Ids of the orders that can still be cancelled.
+fn cancellable_ids(orders: Vec<LiveOrderRecord>) -> Vec<OrderId> {
+    orders
+        .into_iter()
+        .filter(|order| order.cancellable && order.status.is_working())
+        .map(|order| order.id)
+        .collect()
+}
+
+/// Fails with [`TastyTradeError::TradingNotPermitted`] unless `authority` allows placing
+/// orders on `account`.
+fn check_authority(account: &AccountNumber, authority: AuthorityLevel) -> TastyResult<()> {
+    if authority.can_trade() {
+        Ok(())
+    } else {
+        Err(TastyTradeError::TradingNotPermitted(format!(
+            "account {} has {authority} authority",
+            account.0
+        )))
+    }
+}
+
+/// Records `replaced` as the order `replacement` replaces, unless the API already did.
+fn link_replacement(replacement: &mut LiveOrderRecord, replaced: &OrderId) {
+    if replacement.replaces_order_id.is_none() && replacement.id.0 != replaced.0 {
+        replacement.replaces_order_id = Some(replaced.clone());
+    }
+}
+
 pub struct Account<'t> {
     pub(crate) inner: AccountInner,
     pub(crate) tasty: &'t TastyTrade,
@@ -52,6 +106,63 @@ impl Account<'_> {
         self.inner.account.account_number.clone()
     }
 
+    /// The full account resource as returned by `/customers/me/accounts`.
+    pub fn details(&self) -> &AccountDetails {
+        &self.inner.account
+    }
+
+    /// Name given to the account by the customer.
+    pub fn nickname(&self) -> &str {
+        &self.inner.account.nickname
+    }
+
+    /// Account type, e.g. "Individual", "Roth IRA".
+    pub fn account_type_name(&self) -> &str {
+        &self.inner.account.account_type_name
+    }
+
+    /// "Margin" or "Cash".
+    pub fn margin_or_cash(&self) -> &str {
+        &self.inner.account.margin_or_cash
+    }
+
+    /// Whether the account is flagged as a pattern day trader.
+    pub fn day_trader_status(&self) -> bool {
+        self.inner.account.day_trader_status
+    }
+
+    /// Whether the account may trade futures.
+    pub fn is_futures_approved(&self) -> bool {
+        self.inner.account.is_futures_approved
+    }
+
+    /// Date the account was opened.
+    pub fn opened_at(&self) -> &str {
+        &self.inner.account.opened_at
+    }
+
+    /// Creation timestamp of the account record, when the API provides it.
+    pub fn created_at(&self) -> Option<&str> {
+        self.inner.account.created_at.as_deref()
+    }
+
+    /// The customer's authority over the account, e.g. "owner" or "trading-authority".
+    pub fn authority_level(&self) -> &str {
+        &self.inner.authority_level
+    }
+
+    /// The customer's authority over the account, typed.
+    pub fn authority(&self) -> AuthorityLevel {
+        AuthorityLevel::parse(&self.inner.authority_level)
+    }
+
+    /// Returns `true` when the customer may place orders on the account. When it is
+    /// `false`, placing, replacing, repricing or reconfirming an order fails with
+    /// [`TastyTradeError::TradingNotPermitted`] without calling the API.
+    pub fn can_trade(&self) -> bool {
+        self.authority().can_trade()
+    }
+
     pub async fn balance(&self) -> TastyResult<Balance> {
         let resp = self
             .tasty
@@ -65,8 +176,8 @@ impl Account<'_> {
 
     pub async fn balance_snapshot(
         &self,
-        start_date: chrono::NaiveDate,
-        end_date: chrono::NaiveDate,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
         tod: SnapshotTimeOfDay,
         page_offset: usize,
     ) -> TastyResult<Paginated<BalanceSnapshot>> {
@@ -88,6 +199,24 @@ impl Account<'_> {
         Ok(resp)
     }
 
+    /// Net liquidating value bars covering `time_back`, oldest first.
+    pub async fn net_liq_history(
+        &self,
+        time_back: TimeBack,
+    ) -> TastyResult<Vec<NetLiqHistoryPoint>> {
+        let resp: Items<NetLiqHistoryPoint> = self
+            .tasty
+            .get_with_query::<Items<NetLiqHistoryPoint>, _, _>(
+                &format!(
+                    "/accounts/{}/net-liq/history",
+                    self.inner.account.account_number.0
+                ),
+                &[("time-back", &time_back.to_string())],
+            )
+            .await?;
+        Ok(resp.items)
+    }
+
     pub async fn positions(&self) -> TastyResult<Vec<FullPosition>> {
         let resp: Items<FullPosition> = self
             .tasty
@@ -99,6 +228,23 @@ impl Account<'_> {
         Ok(resp.items)
     }
 
+    /// Open [adjusted](FullPosition::is_adjusted) option positions, each with the
+    /// deliverables of its contracts. Makes two requests per adjusted position.
+    pub async fn adjusted_positions(&self) -> TastyResult<Vec<AdjustedPosition>> {
+        let mut adjusted = Vec::new();
+        for position in self.positions().await? {
+            if !position.is_adjusted() {
+                continue;
+            }
+            let deliverables = self.tasty.deliverables(&position.symbol).await?;
+            adjusted.push(AdjustedPosition {
+                position,
+                deliverables,
+            });
+        }
+        Ok(adjusted)
+    }
+
     pub async fn live_orders(&self) -> TastyResult<Vec<LiveOrderRecord>> {
         let resp: Items<LiveOrderRecord> = self
             .tasty
@@ -110,10 +256,104 @@ impl Account<'_> {
         Ok(resp.items)
     }
 
-    pub async fn dry_run(&self, order: &Order) -> TastyResult<DryRunResult> {
-        let resp: DryRunResult = self
+    /// Live orders on `underlying`, filtered by the API.
+    pub async fn live_orders_for(
+        &self,
+        underlying: impl AsSymbol,
+    ) -> TastyResult<Vec<LiveOrderRecord>> {
+        self.live_orders_matching(&LiveOrderQuery::new().underlying(underlying))
+            .await
+    }
+
+    /// Live orders in one of `status_filter`, filtered by the API.
+    ///
+    /// An empty filter selects every [working](OrderStatus::is_working) status.
+    pub async fn working_orders(
+        &self,
+        status_filter: &[OrderStatus],
+    ) -> TastyResult<Vec<LiveOrderRecord>> {
+        let statuses = if status_filter.is_empty() {
+            &OrderStatus::WORKING[..]
+        } else {
+            status_filter
+        };
+        self.live_orders_matching(&LiveOrderQuery::new().statuses(statuses))
+            .await
+    }
+
+    /// One page of the live orders selected by `query`.
+    ///
+    /// Dashboards can render it and fetch the next page with
+    /// [`LiveOrderQuery::page`] while [`Pagination::has_next_page`](crate::api::base::Pagination::has_next_page)
+    /// holds.
+    pub async fn live_orders_page(
+        &self,
+        query: &LiveOrderQuery,
+    ) -> TastyResult<Paginated<LiveOrderRecord>> {
+        let params = query.params();
+        let params: Vec<(&str, &str)> = params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        self.tasty
+            .get_with_query::<Items<LiveOrderRecord>, _, _>(
+                &format!(
+                    "/accounts/{}/orders/live",
+                    self.inner.account.account_number.0
+                ),
+                &params,
+            )
+            .await
+    }
+
+    /// Every live order selected by `query`, from its page onwards.
+    pub async fn live_orders_matching(
+        &self,
+        query: &LiveOrderQuery,
+    ) -> TastyResult<Vec<LiveOrderRecord>> {
+        let mut query = query.clone();
+        let mut orders = Vec::new();
+        loop {
+            let page = self.live_orders_page(&query).await?;
+            orders.extend(page.items);
+            if !page.pagination.has_next_page() {
+                return Ok(orders);
+            }
+            query.page_offset = page.pagination.page_offset + 1;
+        }
+    }
+
+    /// One page of the account's orders selected by `query`, working or not, from
+    /// `/accounts/{account}/orders`. Newest first.
+    ///
+    /// Fetch the next page with [`OrderHistoryQuery::page`] while
+    /// [`Pagination::has_next_page`](crate::api::base::Pagination::has_next_page) holds.
+    pub async fn order_history(
+        &self,
+        query: &OrderHistoryQuery,
+    ) -> TastyResult<Paginated<LiveOrderRecord>> {
+        let params = query.params();
+        let params: Vec<(&str, &str)> = params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        self.tasty
+            .get_with_query::<Items<LiveOrderRecord>, _, _>(
+                &format!("/accounts/{}/orders", self.inner.account.account_number.0),
+                &params,
+            )
+            .await
+    }
+
+    /// The order `id` from `/accounts/{account}/orders/{id}`, with its legs and fills,
+    /// whatever its status. Lets callers poll an order without the account streamer.
+    pub async fn order(&self, id: OrderId) -> TastyResult<LiveOrderRecord> {
+        self.tasty
+            .get(format!(
+                "/accounts/{}/orders/{}",
+                self.inner.account.account_number.0, id.0
+            ))
+            .await
+    }
+
+    pub async fn dry_run(&self, order: &Order) -> TastyResult<WriteResponse<DryRunResult>> {
+        let resp = self
             .tasty
-            .post(
+            .post_write(
                 &format!(
                     "/accounts/{}/orders/dry-run",
                     self.inner.account.account_number.0
@@ -124,23 +364,525 @@ impl Account<'_> {
         Ok(resp)
     }
 
-    pub async fn place_order(&self, order: &Order) -> TastyResult<OrderPlacedResult> {
-        let resp: OrderPlacedResult = self
+    pub async fn place_order(
+        &self,
+        order: &Order,
+    ) -> TastyResult<WriteResponse<OrderPlacedResult>> {
+        self.check_can_trade()?;
+        self.tasty.check_environment_guard(order.notional())?;
+        let path = format!("/accounts/{}/orders", self.inner.account.account_number.0);
+        self.send_order(&path, order, self.tasty.post_write(&path, order))
+            .await
+    }
+
+    /// Checks `order` with `validator` and places it when no problem is found; otherwise
+    /// fails with [`TastyTradeError::OrderValidation`](crate::TastyTradeError::OrderValidation)
+    /// without calling the API.
+    pub async fn place_validated_order(
+        &self,
+        validator: &OrderValidator,
+        order: &Order,
+    ) -> TastyResult<WriteResponse<OrderPlacedResult>> {
+        validator.validate(order)?;
+        self.place_order(order).await
+    }
+
+    /// Submits `order` to `/accounts/{account}/complex-orders`.
+    ///
+    /// The returned complex order carries its own id and the records of the orders it
+    /// groups; contingent orders stay `Contingent` until their trigger fills.
+    pub async fn place_complex_order(
+        &self,
+        order: &ComplexOrder,
+    ) -> TastyResult<WriteResponse<ComplexOrderPlacedResult>> {
+        self.check_can_trade()?;
+        self.tasty.check_environment_guard(order.notional())?;
+        let path = format!(
+            "/accounts/{}/complex-orders",
+            self.inner.account.account_number.0
+        );
+        self.send_order(&path, order, self.tasty.post_write(&path, order))
+            .await
+    }
+
+    /// Changes the price of a working order, keeping its legs, type and time-in-force.
+    ///
+    /// tastytrade replaces the order, so the returned record carries a new id and
+    /// [`replaces_order_id`](LiveOrderRecord::replaces_order_id) points to `order`.
+    pub async fn reprice_order(
+        &self,
+        order: &LiveOrderRecord,
+        price: Decimal,
+    ) -> TastyResult<WriteResponse<LiveOrderRecord>> {
+        self.check_can_trade()?;
+        self.tasty
+            .check_environment_guard(Some(order.notional_at(price)))?;
+        self.tasty.order_throttle.check_placement()?;
+        let mut resp: WriteResponse<LiveOrderRecord> = self
             .tasty
-            .post(
-                &format!("/accounts/{}/orders", self.inner.account.account_number.0),
-                order,
+            .patch_write(
+                &format!(
+                    "/accounts/{}/orders/{}",
+                    self.inner.account.account_number.0, order.id.0
+                ),
+                OrderEdit {
+                    time_in_force: &order.time_in_force,
+                    gtc_date: order.gtc_date,
+                    order_type: &order.order_type,
+                    price,
+                    price_effect: &order.price_effect,
+                    stop_trigger: order.stop_trigger,
+                },
             )
             .await?;
+        link_replacement(&mut resp.data, &order.id);
+        Ok(resp)
+    }
+
+    /// Cancels the working order `id` and places `order` in its stead with
+    /// `PUT /accounts/{account}/orders/{id}`, e.g. to change its legs or quantity.
+    ///
+    /// Returns the new order; its
+    /// [`replaces_order_id`](LiveOrderRecord::replaces_order_id) is `id`. To change only
+    /// the price, [`reprice_order`](Self::reprice_order) is simpler.
+    pub async fn replace_order(
+        &self,
+        id: OrderId,
+        order: &Order,
+    ) -> TastyResult<WriteResponse<LiveOrderRecord>> {
+        self.check_can_trade()?;
+        self.tasty.check_environment_guard(order.notional())?;
+        let path = format!(
+            "/accounts/{}/orders/{}",
+            self.inner.account.account_number.0, id.0
+        );
+        let mut resp: WriteResponse<LiveOrderRecord> = self
+            .send_order(&path, order, self.tasty.put_write(&path, order))
+            .await?;
+        link_replacement(&mut resp.data, &id);
         Ok(resp)
     }
 
-    pub async fn cancel_order(&self, id: OrderId) -> TastyResult<LiveOrderRecord> {
+    /// Previews [`replace_order`](Self::replace_order) with
+    /// `POST /accounts/{account}/orders/{id}/dry-run`: the buying power effect, fees and
+    /// warnings of replacing the working order `id` by `order`, without touching it.
+    pub async fn dry_run_replace(
+        &self,
+        id: OrderId,
+        order: &Order,
+    ) -> TastyResult<WriteResponse<DryRunResult>> {
+        self.tasty
+            .post_write(
+                &format!(
+                    "/accounts/{}/orders/{}/dry-run",
+                    self.inner.account.account_number.0, id.0
+                ),
+                order,
+            )
+            .await
+    }
+
+    /// Reconfirms the order `id` with `POST /accounts/{account}/orders/{id}/reconfirm`.
+    ///
+    /// Some orders, e.g. on equity offerings, stay in `Received` until the customer
+    /// confirms them again; this sends them on. Returns the updated order. Fails with
+    /// [`TastyTradeError::TradingNotPermitted`] like [`place_order`](Self::place_order).
+    pub async fn reconfirm_order(
+        &self,
+        id: OrderId,
+    ) -> TastyResult<WriteResponse<LiveOrderRecord>> {
+        self.check_can_trade()?;
+        self.tasty
+            .post_write(
+                &format!(
+                    "/accounts/{}/orders/{}/reconfirm",
+                    self.inner.account.account_number.0, id.0
+                ),
+                serde_json::json!({}),
+            )
+            .await
+    }
+
+    /// Cancels the working order `id` with `DELETE /accounts/{account}/orders/{id}`.
+    ///
+    /// Returns the order as updated by the API, usually `Cancel Requested` until the
+    /// exchange confirms; follow it on the account stream or with
+    /// [`live_orders`](Self::live_orders). Cancelling an order that already filled or
+    /// ended fails with the API's error. Counted by the order throttle.
+    pub async fn cancel_order(&self, id: OrderId) -> TastyResult<WriteResponse<LiveOrderRecord>> {
+        self.tasty.order_throttle.check_cancel(id.0)?;
         self.tasty
-            .delete(&format!(
+            .delete_write(&format!(
                 "/accounts/{}/orders/{}",
                 self.inner.account.account_number.0, id.0
             ))
             .await
     }
+
+    /// Cancels every live order selected by `filter` that can still be cancelled, e.g.
+    /// `LiveOrderQuery::new()` for all of them or `.underlying("SPY")` for one
+    /// underlying. A kill switch for bots.
+    ///
+    /// Up to [`CANCEL_ALL_CONCURRENCY`] cancellations run at once. Returns one result
+    /// per order, in completion order; a failed cancellation does not stop the others.
+    pub async fn cancel_all_orders(
+        &self,
+        filter: &LiveOrderQuery,
+    ) -> TastyResult<Vec<(OrderId, TastyResult<LiveOrderRecord>)>> {
+        let ids = cancellable_ids(self.live_orders_matching(filter).await?);
+        Ok(stream::iter(ids)
+            .map(|id| async move {
+                let result = self.cancel_order(id.clone()).await.map(|resp| resp.data);
+                (id, result)
+            })
+            .buffer_unordered(CANCEL_ALL_CONCURRENCY)
+            .collect()
+            .await)
+    }
+
+    /// Fails with [`TastyTradeError::TradingNotPermitted`] when [`can_trade`](Self::can_trade)
+    /// is `false`, before an order reaches the API.
+    fn check_can_trade(&self) -> TastyResult<()> {
+        check_authority(&self.inner.account.account_number, self.authority())
+    }
+
+    /// Runs `send`, the request writing `order` to `path`, behind the duplicate check
+    /// and the placement limit of the order throttle.
+    async fn send_order<T>(
+        &self,
+        path: &str,
+        order: &impl Serialize,
+        send: impl Future<Output = TastyResult<T>>,
+    ) -> TastyResult<T> {
+        let throttle = &self.tasty.order_throttle;
+        throttle.claim_order(path, order)?;
+        if let Err(e) = throttle.check_placement() {
+            throttle.release_order(path, order);
+            return Err(e);
+        }
+        let result = send.await;
+        throttle.settle_order(path, order, &result);
+        result
+    }
+}
+
+/// Filters and page of a `/orders/live` request.
+///
+/// ```rust,ignore
+/// let query = LiveOrderQuery::new()
+///     .underlying("SPY")
+///     .between(monday, friday)
+///     .per_page(50);
+/// let first = account.live_orders_page(&query).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LiveOrderQuery {
+    /// Only orders on this underlying.
+    pub underlying_symbol: Option<Symbol>,
+    /// Only orders in one of these statuses; empty for any.
+    pub statuses: Vec<OrderStatus>,
+    /// Only orders received on or after this date.
+    pub start_date: Option<NaiveDate>,
+    /// Only orders received on or before this date.
+    pub end_date: Option<NaiveDate>,
+    /// Orders per page; the API default when `None`.
+    pub per_page: Option<usize>,
+    /// Zero-based page to fetch.
+    pub page_offset: usize,
+}
+
+impl LiveOrderQuery {
+    /// A query for every live order, first page.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only orders on `underlying`.
+    pub fn underlying(mut self, underlying: impl AsSymbol) -> Self {
+        self.underlying_symbol = Some(underlying.as_symbol());
+        self
+    }
+
+    /// Only orders in one of `statuses`.
+    pub fn statuses(mut self, statuses: &[OrderStatus]) -> Self {
+        self.statuses = statuses.to_vec();
+        self
+    }
+
+    /// Only orders received between `start` and `end`, both included.
+    pub fn between(mut self, start: NaiveDate, end: NaiveDate) -> Self {
+        self.start_date = Some(start);
+        self.end_date = Some(end);
+        self
+    }
+
+    /// `per_page` orders per page.
+    pub fn per_page(mut self, per_page: usize) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Fetches page `page_offset`, counted from zero.
+    pub fn page(mut self, page_offset: usize) -> Self {
+        self.page_offset = page_offset;
+        self
+    }
+
+    /// Query parameters of the request; `status[]` repeats once per status.
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let date = |date: &NaiveDate| date.format("%Y-%m-%d").to_string();
+        let mut params: Vec<(&'static str, String)> = Vec::new();
+        if let Some(symbol) = &self.underlying_symbol {
+            params.push(("underlying-symbol", symbol.0.clone()));
+        }
+        params.extend(
+            self.statuses
+                .iter()
+                .map(|status| ("status[]", status.to_string())),
+        );
+        if let Some(start) = &self.start_date {
+            params.push(("start-date", date(start)));
+        }
+        if let Some(end) = &self.end_date {
+            params.push(("end-date", date(end)));
+        }
+        if let Some(per_page) = self.per_page {
+            params.push(("per-page", per_page.to_string()));
+        }
+        if self.page_offset > 0 {
+            params.push(("page-offset", self.page_offset.to_string()));
+        }
+        params
+    }
+}
+
+/// Filters and page of an [`Account::order_history`] request.
+///
+/// ```rust,ignore
+/// let query = OrderHistoryQuery::new()
+///     .statuses(&[OrderStatus::Filled])
+///     .since(Utc::now() - chrono::Duration::days(7));
+/// let page = account.order_history(&query).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OrderHistoryQuery {
+    /// Only orders on this underlying.
+    pub underlying_symbol: Option<Symbol>,
+    /// Only orders in one of these statuses; empty for any.
+    pub statuses: Vec<OrderStatus>,
+    /// Only orders updated at or after this time.
+    pub start_at: Option<DateTime<Utc>>,
+    /// Only orders updated at or before this time.
+    pub end_at: Option<DateTime<Utc>>,
+    /// Orders per page; the API default when `None`.
+    pub per_page: Option<usize>,
+    /// Zero-based page to fetch.
+    pub page_offset: usize,
+}
+
+impl OrderHistoryQuery {
+    /// A query for every order of the account, first page.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only orders on `underlying`.
+    pub fn underlying(mut self, underlying: impl AsSymbol) -> Self {
+        self.underlying_symbol = Some(underlying.as_symbol());
+        self
+    }
+
+    /// Only orders in one of `statuses`.
+    pub fn statuses(mut self, statuses: &[OrderStatus]) -> Self {
+        self.statuses = statuses.to_vec();
+        self
+    }
+
+    /// Only orders updated at or after `start`.
+    pub fn since(mut self, start: DateTime<Utc>) -> Self {
+        self.start_at = Some(start);
+        self
+    }
+
+    /// Only orders updated at or before `end`.
+    pub fn until(mut self, end: DateTime<Utc>) -> Self {
+        self.end_at = Some(end);
+        self
+    }
+
+    /// `per_page` orders per page.
+    pub fn per_page(mut self, per_page: usize) -> Self {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Fetches page `page_offset`, counted from zero.
+    pub fn page(mut self, page_offset: usize) -> Self {
+        self.page_offset = page_offset;
+        self
+    }
+
+    /// Query parameters of the request; `status[]` repeats once per status.
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let time = |at: &DateTime<Utc>| at.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut params: Vec<(&'static str, String)> = Vec::new();
+        if let Some(symbol) = &self.underlying_symbol {
+            params.push(("underlying-symbol", symbol.0.clone()));
+        }
+        params.extend(
+            self.statuses
+                .iter()
+                .map(|status| ("status[]", status.to_string())),
+        );
+        if let Some(start) = &self.start_at {
+            params.push(("start-at", time(start)));
+        }
+        if let Some(end) = &self.end_at {
+            params.push(("end-at", time(end)));
+        }
+        if let Some(per_page) = self.per_page {
+            params.push(("per-page", per_page.to_string()));
+        }
+        if self.page_offset > 0 {
+            params.push(("page-offset", self.page_offset.to_string()));
+        }
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::base::Pagination;
+
+    #[test]
+    fn test_order_history_query_params() {
+        let start: DateTime<Utc> = "2024-06-03T13:30:00Z".parse().unwrap();
+        let params = OrderHistoryQuery::new()
+            .underlying("SPY")
+            .statuses(&[OrderStatus::Filled])
+            .since(start)
+            .until(start + chrono::Duration::hours(8))
+            .page(1)
+            .params();
+        assert_eq!(
+            params,
+            vec![
+                ("underlying-symbol", "SPY".to_string()),
+                ("status[]", "Filled".to_string()),
+                ("start-at", "2024-06-03T13:30:00Z".to_string()),
+                ("end-at", "2024-06-03T21:30:00Z".to_string()),
+                ("page-offset", "1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cancellable_ids() {
+        let body = include_str!("../../tests/fixtures/live_orders.json");
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        let orders: Vec<LiveOrderRecord> =
+            serde_json::from_value(body["data"]["items"].clone()).unwrap();
+        let ids = cancellable_ids(orders);
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids[0].0, 318440071);
+    }
+
+    #[test]
+    fn test_check_authority() {
+        let account = AccountNumber("5WT00001".to_string());
+        assert!(check_authority(&account, AuthorityLevel::Owner).is_ok());
+        assert!(matches!(
+            check_authority(&account, AuthorityLevel::ReadOnly),
+            Err(TastyTradeError::TradingNotPermitted(msg)) if msg.contains("read-only")
+        ));
+    }
+
+    #[test]
+    fn test_link_replacement() {
+        let body = include_str!("../../tests/fixtures/live_orders.json");
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        let mut orders: Vec<LiveOrderRecord> =
+            serde_json::from_value(body["data"]["items"].clone()).unwrap();
+        let mut replacement = orders.pop().unwrap();
+        assert!(replacement.replaces_order_id.is_none());
+
+        link_replacement(&mut replacement, &OrderId(1));
+        assert_eq!(replacement.replaces_order_id.as_ref().unwrap().0, 1);
+        link_replacement(&mut replacement, &OrderId(2));
+        assert_eq!(replacement.replaces_order_id.as_ref().unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_live_order_query_params() {
+        assert_eq!(
+            LiveOrderQuery::new().underlying("SPY").params(),
+            vec![("underlying-symbol", "SPY".to_string())]
+        );
+
+        let working = LiveOrderQuery::new()
+            .statuses(&OrderStatus::WORKING)
+            .params();
+        assert_eq!(working.len(), 7);
+        assert!(working.iter().all(|(key, _)| *key == "status[]"));
+        assert!(working.contains(&("status[]", "In Flight".to_string())));
+        assert!(OrderStatus::WORKING.iter().all(OrderStatus::is_working));
+
+        let day = |d| NaiveDate::from_ymd_opt(2024, 6, d).unwrap();
+        let paged = LiveOrderQuery::new()
+            .between(day(3), day(7))
+            .per_page(50)
+            .page(2)
+            .params();
+        assert_eq!(
+            paged,
+            vec![
+                ("start-date", "2024-06-03".to_string()),
+                ("end-date", "2024-06-07".to_string()),
+                ("per-page", "50".to_string()),
+                ("page-offset", "2".to_string()),
+            ]
+        );
+
+        let mut pagination = Pagination::single_page(3);
+        assert!(!pagination.has_next_page());
+        pagination.total_pages = 2;
+        assert!(pagination.has_next_page());
+    }
+
+    #[test]
+    fn test_account_inner_deserialization() {
+        let json = r#"{
+            "account": {
+                "account-number": "5WT00001",
+                "external-id": null,
+                "opened-at": "2023-01-05T14:00:00.000+00:00",
+                "nickname": "Roth",
+                "account-type-name": "Roth IRA",
+                "day-trader-status": false,
+                "is-firm-error": false,
+                "is-firm-proprietary": false,
+                "is-test-drive": false,
+                "margin-or-cash": "Cash",
+                "is-foreign": false,
+                "funding-date": null,
+                "is-futures-approved": true,
+                "created-at": "2023-01-05T14:00:00.000+00:00"
+            },
+            "authority-level": "owner"
+        }"#;
+        let inner: AccountInner = serde_json::from_str(json).unwrap();
+        assert_eq!(inner.account.nickname, "Roth");
+        assert_eq!(inner.account.margin_or_cash, "Cash");
+        assert!(inner.account.is_futures_approved);
+        assert!(inner.account.created_at.is_some());
+
+        let without_optional = json.replace(r#""is-futures-approved": true,"#, "").replace(
+            r#""created-at": "2023-01-05T14:00:00.000+00:00""#,
+            r#""unused": 0"#,
+        );
+        let inner: AccountInner = serde_json::from_str(&without_optional).unwrap();
+        assert!(!inner.account.is_futures_approved);
+        assert_eq!(inner.account.created_at, None);
+    }
 }
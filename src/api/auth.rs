@@ -0,0 +1,647 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Session acquisition.
+//!
+//! [`TastyTrade`](crate::TastyTrade) never talks to a credential store itself: it asks
+//! an [`AuthProvider`] for a session when it logs in and whenever it needs a fresh
+//! token. The crate ships providers for a password, a remember token, an OAuth2 app
+//! ([`OAuth2Auth`]) and an externally obtained session token; institutions can plug
+//! their own credential broker in by implementing the trait.
+//!
+//! ```rust,ignore
+//! #[derive(Debug)]
+//! struct Vault;
+//!
+//! impl AuthProvider for Vault {
+//!     fn acquire<'a>(
+//!         &'a self,
+//!         _http: &'a reqwest::Client,
+//!         _base_url: &'a str,
+//!     ) -> BoxFuture<'a, TastyResult<AuthSession>> {
+//!         Box::pin(async { Ok(AuthSession::new(fetch_token_from_vault().await?)) })
+//!     }
+//! }
+//!
+//! let tasty = TastyTrade::login_with(&config, Arc::new(Vault)).await?;
+//! ```
+
+use crate::api::base::{TastyApiResponse, TastyResult};
+use crate::types::login::{LoginCredentials, LoginResponse};
+use crate::utils::config::TastyTradeConfig;
+use crate::{TastyTrade, TastyTradeError};
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+/// Authorization page of tastytrade OAuth apps in production.
+pub const OAUTH_AUTHORIZE_URL: &str = "https://my.tastytrade.com/auth.html";
+
+/// Header carrying the one-time passcode of accounts with two-factor authentication.
+pub const OTP_HEADER: &str = "X-Tastyworks-OTP";
+
+/// Asks for a one-time passcode when a login needs one, e.g. by prompting the user or
+/// reading it from an authenticator service.
+pub type OtpCallback = Arc<dyn Fn() -> BoxFuture<'static, TastyResult<String>> + Send + Sync>;
+
+/// A session handed to the client by an [`AuthProvider`].
+#[derive(Clone)]
+pub struct AuthSession {
+    /// The value of the `Authorization` header: a raw session token, or `Bearer <token>`
+    /// for OAuth2 access tokens.
+    pub session_token: String,
+    /// A token that can open the next session without the password, when issued.
+    pub remember_token: Option<String>,
+    /// When the session expires, when known.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Account streamer websocket URL reported with the session, when any.
+    pub streamer_url: Option<String>,
+}
+
+impl AuthSession {
+    /// A session with only a token, expiry unknown.
+    pub fn new(session_token: impl Into<String>) -> Self {
+        Self {
+            session_token: session_token.into(),
+            remember_token: None,
+            expires_at: None,
+            streamer_url: None,
+        }
+    }
+}
+
+impl From<LoginResponse> for AuthSession {
+    fn from(resp: LoginResponse) -> Self {
+        Self {
+            session_token: resp.session_token,
+            remember_token: resp.remember_token,
+            expires_at: resp.session_expiration,
+            streamer_url: resp.streamer_url,
+        }
+    }
+}
+
+impl fmt::Debug for AuthSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthSession")
+            .field("session_token", &"<redacted>")
+            .field(
+                "remember_token",
+                &self.remember_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("expires_at", &self.expires_at)
+            .field("streamer_url", &self.streamer_url)
+            .finish()
+    }
+}
+
+/// A source of sessions for the client.
+///
+/// `acquire` is called on login and each time the client needs a fresh token, so it
+/// must return a new session rather than a cached one that may have expired.
+pub trait AuthProvider: fmt::Debug + Send + Sync {
+    /// Obtains a session for the API at `base_url`.
+    ///
+    /// Requests to the API go through `http`, the client's own HTTP client, so they
+    /// carry the configured `User-Agent` and extra headers.
+    fn acquire<'a>(
+        &'a self,
+        http: &'a reqwest::Client,
+        base_url: &'a str,
+    ) -> BoxFuture<'a, TastyResult<AuthSession>>;
+}
+
+/// Body of a `POST /sessions` that uses a remember token instead of the password.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct RememberTokenCredentials<'a> {
+    login: &'a str,
+    remember_token: &'a str,
+    remember_me: bool,
+}
+
+/// Opens a session with `POST /sessions`, sending `otp` in the [`OTP_HEADER`] when set.
+pub(crate) async fn create_session<B: Serialize>(
+    http: &reqwest::Client,
+    base_url: &str,
+    body: &B,
+    otp: Option<&str>,
+) -> TastyResult<LoginResponse> {
+    let mut request = http.post(format!("{base_url}/sessions"));
+    if let Some(otp) = otp {
+        request = request.header(OTP_HEADER, otp);
+    }
+    let resp = request.json(body).send().await?;
+    match resp.json::<TastyApiResponse<LoginResponse>>().await? {
+        TastyApiResponse::Success(s) => Ok(s.data),
+        TastyApiResponse::Error { error } => Err(error.into()),
+    }
+}
+
+/// Where a [`PasswordAuth`] gets its one-time passcode from.
+#[derive(Clone)]
+enum OtpSource {
+    Code(String),
+    Callback(OtpCallback),
+}
+
+/// Logs in with a username and password, plus a one-time passcode for accounts with
+/// two-factor authentication.
+#[derive(Clone)]
+pub struct PasswordAuth {
+    username: String,
+    password: String,
+    remember_me: bool,
+    otp: Option<OtpSource>,
+}
+
+impl PasswordAuth {
+    /// Logs in as `username`, without asking for a remember token.
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+            remember_me: false,
+            otp: None,
+        }
+    }
+
+    /// Asks the API for a remember token along with each session.
+    pub fn remember_me(mut self, remember_me: bool) -> Self {
+        self.remember_me = remember_me;
+        self
+    }
+
+    /// Sends `code` as the one-time passcode.
+    ///
+    /// Passcodes expire within seconds, so this only suits a single login: a session
+    /// renewed later would send a stale code. Use [`with_otp_callback`](Self::with_otp_callback)
+    /// for clients that may log in again.
+    pub fn with_otp(mut self, code: impl Into<String>) -> Self {
+        self.otp = Some(OtpSource::Code(code.into()));
+        self
+    }
+
+    /// Calls `callback` for a fresh one-time passcode on every login.
+    ///
+    /// Combine with [`remember_me`](Self::remember_me) and log in later with the remember
+    /// token to avoid being asked again on each restart.
+    pub fn with_otp_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() -> BoxFuture<'static, TastyResult<String>> + Send + Sync + 'static,
+    {
+        self.otp = Some(OtpSource::Callback(Arc::new(callback)));
+        self
+    }
+
+    /// The credentials and remember-me flag of `config`.
+    pub fn from_config(config: &TastyTradeConfig) -> Self {
+        Self::new(config.username.clone(), config.password.clone()).remember_me(config.remember_me)
+    }
+
+    async fn otp(&self) -> TastyResult<Option<String>> {
+        match &self.otp {
+            None => Ok(None),
+            Some(OtpSource::Code(code)) => Ok(Some(code.clone())),
+            Some(OtpSource::Callback(callback)) => callback().await.map(Some),
+        }
+    }
+}
+
+impl fmt::Debug for PasswordAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PasswordAuth")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .field("remember_me", &self.remember_me)
+            .field("otp", &self.otp.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl AuthProvider for PasswordAuth {
+    fn acquire<'a>(
+        &'a self,
+        http: &'a reqwest::Client,
+        base_url: &'a str,
+    ) -> BoxFuture<'a, TastyResult<AuthSession>> {
+        Box::pin(async move {
+            let body = LoginCredentials {
+                login: self.username.clone(),
+                password: self.password.clone(),
+                remember_me: self.remember_me,
+            };
+            let otp = self.otp().await?;
+            Ok(create_session(http, base_url, &body, otp.as_deref())
+                .await?
+                .into())
+        })
+    }
+}
+
+/// Logs in with a remember token obtained by an earlier password login.
+///
+/// Remember tokens are single use: each login returns the next one, which this provider
+/// keeps for the following call.
+pub struct RememberTokenAuth {
+    username: String,
+    remember_token: Mutex<String>,
+}
+
+impl RememberTokenAuth {
+    /// Logs in as `username` with `remember_token`.
+    pub fn new(username: impl Into<String>, remember_token: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            remember_token: Mutex::new(remember_token.into()),
+        }
+    }
+
+    /// The token the next login will use.
+    pub fn remember_token(&self) -> String {
+        self.remember_token.lock().unwrap().clone()
+    }
+}
+
+impl fmt::Debug for RememberTokenAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RememberTokenAuth")
+            .field("username", &self.username)
+            .field("remember_token", &"<redacted>")
+            .finish()
+    }
+}
+
+impl AuthProvider for RememberTokenAuth {
+    fn acquire<'a>(
+        &'a self,
+        http: &'a reqwest::Client,
+        base_url: &'a str,
+    ) -> BoxFuture<'a, TastyResult<AuthSession>> {
+        Box::pin(async move {
+            let remember_token = self.remember_token();
+            let body = RememberTokenCredentials {
+                login: &self.username,
+                remember_token: &remember_token,
+                remember_me: true,
+            };
+            let session = AuthSession::from(create_session(http, base_url, &body, None).await?);
+            if let Some(next) = &session.remember_token {
+                *self.remember_token.lock().unwrap() = next.clone();
+            }
+            Ok(session)
+        })
+    }
+}
+
+/// A session token obtained outside this crate, e.g. by another process.
+///
+/// It cannot be renewed: every call returns the same token, so once the API rejects it,
+/// refreshing the session fails with [`TastyTradeError::Auth`] and the rejected request
+/// is not sent again.
+pub struct StaticTokenAuth {
+    session: AuthSession,
+}
+
+impl StaticTokenAuth {
+    /// Uses `session_token` for every request.
+    pub fn new(session_token: impl Into<String>) -> Self {
+        Self {
+            session: AuthSession::new(session_token),
+        }
+    }
+}
+
+impl fmt::Debug for StaticTokenAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticTokenAuth").finish_non_exhaustive()
+    }
+}
+
+impl AuthProvider for StaticTokenAuth {
+    fn acquire<'a>(
+        &'a self,
+        _http: &'a reqwest::Client,
+        _base_url: &'a str,
+    ) -> BoxFuture<'a, TastyResult<AuthSession>> {
+        Box::pin(async move { Ok(self.session.clone()) })
+    }
+}
+
+/// Asks `primary` for a session and, when it fails, `fallback`.
+///
+/// [`TastyTrade::login`](crate::TastyTrade::login) uses it to renew sessions opened
+/// with a remember token: once the token is rejected, the configured password takes over.
+#[derive(Debug)]
+pub struct FallbackAuth {
+    primary: Arc<dyn AuthProvider>,
+    fallback: Arc<dyn AuthProvider>,
+}
+
+impl FallbackAuth {
+    /// Tries `primary` first, then `fallback`.
+    pub fn new(primary: Arc<dyn AuthProvider>, fallback: Arc<dyn AuthProvider>) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+impl AuthProvider for FallbackAuth {
+    fn acquire<'a>(
+        &'a self,
+        http: &'a reqwest::Client,
+        base_url: &'a str,
+    ) -> BoxFuture<'a, TastyResult<AuthSession>> {
+        Box::pin(async move {
+            match self.primary.acquire(http, base_url).await {
+                Ok(session) => Ok(session),
+                Err(e) => {
+                    warn!("Primary auth provider failed, using the fallback: {e}");
+                    self.fallback.acquire(http, base_url).await
+                }
+            }
+        })
+    }
+}
+
+/// Logs in as an OAuth2 app, exchanging a refresh token for access tokens.
+///
+/// The refresh token comes from the authorization-code flow: send the user to
+/// [`authorization_url`](Self::authorization_url), then trade the returned code with
+/// [`exchange_code`](Self::exchange_code). It is kept for later sessions, so no
+/// password is ever stored.
+///
+/// ```rust,ignore
+/// let url = OAuth2Auth::authorization_url(OAUTH_AUTHORIZE_URL, &client_id, &redirect, &["read", "trade"], "xyz")?;
+/// // ... the user approves, the redirect receives `code` ...
+/// let oauth = OAuth2Auth::exchange_code(&config, &client_id, &secret, &code, &redirect).await?;
+/// save(oauth.refresh_token());
+/// let tasty = TastyTrade::login_oauth(&config, oauth).await?;
+/// ```
+pub struct OAuth2Auth {
+    client_id: String,
+    client_secret: String,
+    refresh_token: Mutex<String>,
+}
+
+/// Successful response of `POST /oauth/token`.
+#[derive(Deserialize)]
+struct OAuthTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+}
+
+impl OAuth2Auth {
+    /// Uses `refresh_token` of the app `client_id`.
+    pub fn new(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        refresh_token: impl Into<String>,
+    ) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            refresh_token: Mutex::new(refresh_token.into()),
+        }
+    }
+
+    /// The page the user must open to grant the app `scopes`. The grant comes back to
+    /// `redirect_uri` as a `code` parameter, along with `state`.
+    pub fn authorization_url(
+        authorize_url: &str,
+        client_id: &str,
+        redirect_uri: &str,
+        scopes: &[&str],
+        state: &str,
+    ) -> TastyResult<String> {
+        let url = reqwest::Url::parse_with_params(
+            authorize_url,
+            &[
+                ("client_id", client_id),
+                ("redirect_uri", redirect_uri),
+                ("response_type", "code"),
+                ("scope", &scopes.join(" ")),
+                ("state", state),
+            ],
+        )
+        .map_err(|e| TastyTradeError::Auth(format!("invalid authorization URL: {e}")))?;
+        Ok(url.into())
+    }
+
+    /// Trades an authorization `code` for a refresh token at the API of `config`,
+    /// returning a provider that uses it.
+    pub async fn exchange_code(
+        config: &TastyTradeConfig,
+        client_id: &str,
+        client_secret: &str,
+        code: &str,
+        redirect_uri: &str,
+    ) -> TastyResult<Self> {
+        let resp = request_token(
+            &TastyTrade::create_client(config),
+            &config.base_url,
+            &[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+                ("client_id", client_id),
+                ("client_secret", client_secret),
+            ],
+        )
+        .await?;
+        let refresh_token = resp.refresh_token.ok_or_else(|| {
+            TastyTradeError::Auth("token response has no refresh token".to_string())
+        })?;
+        Ok(Self::new(client_id, client_secret, refresh_token))
+    }
+
+    /// The refresh token the next session will be requested with. Persist it if the API
+    /// rotates it.
+    pub fn refresh_token(&self) -> String {
+        self.refresh_token.lock().unwrap().clone()
+    }
+}
+
+/// Calls `POST /oauth/token` with a form body.
+async fn request_token(
+    http: &reqwest::Client,
+    base_url: &str,
+    form: &[(&str, &str)],
+) -> TastyResult<OAuthTokenResponse> {
+    let resp = http
+        .post(format!("{base_url}/oauth/token"))
+        .form(form)
+        .send()
+        .await?;
+    let status = resp.status();
+    let body = resp.text().await?;
+    if !status.is_success() {
+        return Err(TastyTradeError::Auth(format!(
+            "OAuth2 token request failed with HTTP {}: {}",
+            status.as_u16(),
+            body
+        )));
+    }
+    Ok(serde_json::from_str(&body)?)
+}
+
+impl fmt::Debug for OAuth2Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuth2Auth")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"<redacted>")
+            .field("refresh_token", &"<redacted>")
+            .finish()
+    }
+}
+
+impl AuthProvider for OAuth2Auth {
+    fn acquire<'a>(
+        &'a self,
+        http: &'a reqwest::Client,
+        base_url: &'a str,
+    ) -> BoxFuture<'a, TastyResult<AuthSession>> {
+        Box::pin(async move {
+            let refresh_token = self.refresh_token();
+            let resp = request_token(
+                http,
+                base_url,
+                &[
+                    ("grant_type", "refresh_token"),
+                    ("refresh_token", &refresh_token),
+                    ("client_id", &self.client_id),
+                    ("client_secret", &self.client_secret),
+                ],
+            )
+            .await?;
+            if let Some(next) = &resp.refresh_token {
+                *self.refresh_token.lock().unwrap() = next.clone();
+            }
+            Ok(AuthSession {
+                session_token: format!("Bearer {}", resp.access_token),
+                remember_token: None,
+                expires_at: resp
+                    .expires_in
+                    .map(|secs| Utc::now() + chrono::Duration::seconds(secs)),
+                streamer_url: None,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_providers_keep_secrets_out_of_debug() {
+        let providers: Vec<Arc<dyn AuthProvider>> = vec![
+            Arc::new(
+                PasswordAuth::new("trader", "hunter2")
+                    .remember_me(true)
+                    .with_otp("123456"),
+            ),
+            Arc::new(RememberTokenAuth::new("trader", "remember-abc")),
+            Arc::new(StaticTokenAuth::new("session-xyz")),
+            Arc::new(OAuth2Auth::new("app-1", "secret-123", "refresh-456")),
+        ];
+        for provider in &providers {
+            let debug = format!("{provider:?}");
+            for secret in [
+                "hunter2",
+                "remember-abc",
+                "session-xyz",
+                "secret-123",
+                "refresh-456",
+                "123456",
+            ] {
+                assert!(!debug.contains(secret), "{debug}");
+            }
+        }
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let session = runtime
+            .block_on(providers[2].acquire(&reqwest::Client::new(), "https://api.example.com"))
+            .unwrap();
+        assert_eq!(session.session_token, "session-xyz");
+        assert!(format!("{session:?}").contains("<redacted>"));
+
+        let auth = PasswordAuth::new("trader", "hunter2")
+            .with_otp_callback(|| Box::pin(async { Ok("654321".to_string()) }));
+        assert_eq!(
+            runtime.block_on(auth.otp()).unwrap().as_deref(),
+            Some("654321")
+        );
+
+        let url = OAuth2Auth::authorization_url(
+            OAUTH_AUTHORIZE_URL,
+            "app-1",
+            "https://example.com/callback",
+            &["read", "trade"],
+            "xyz",
+        )
+        .unwrap();
+        assert!(url.starts_with("https://my.tastytrade.com/auth.html?client_id=app-1"));
+        assert!(url.contains("redirect_uri=https%3A%2F%2Fexample.com%2Fcallback"));
+        assert!(url.contains("response_type=code") && url.contains("scope=read+trade"));
+    }
+
+    #[tokio::test]
+    async fn test_logins_send_the_configured_headers() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 4096];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request).to_lowercase()
+        });
+
+        let config = TastyTradeConfig::default()
+            .with_user_agent("desk-7/1.2")
+            .with_header("X-Desk", "7");
+        let http = TastyTrade::create_client(&config);
+        let login = PasswordAuth::new("trader", "hunter2").acquire(&http, &base_url);
+        assert!(login.await.is_err());
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("post /sessions"), "{request}");
+        assert!(request.contains("user-agent: desk-7/1.2"), "{request}");
+        assert!(request.contains("x-desk: 7"), "{request}");
+    }
+
+    #[test]
+    fn test_fallback_auth_uses_fallback_on_failure() {
+        let auth = FallbackAuth::new(
+            Arc::new(OAuth2Auth::new("app-1", "secret-123", "refresh-456")),
+            Arc::new(StaticTokenAuth::new("session-xyz")),
+        );
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        // Nothing listens on port 9, so the OAuth2 refresh fails.
+        let session = runtime
+            .block_on(auth.acquire(&reqwest::Client::new(), "http://127.0.0.1:9"))
+            .unwrap();
+        assert_eq!(session.session_token, "session-xyz");
+    }
+}
@@ -0,0 +1,183 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Placing an order and waiting for its outcome.
+//!
+//! [`Account::place_order_and_await`] subscribes an account streamer, places the order
+//! and follows its updates until it reaches a final status (filled, cancelled,
+//! rejected, expired...) or the timeout elapses. The order is fetched once more at the
+//! end, so the report carries its fills even if the stream missed an update.
+//!
+//! ```rust,ignore
+//! let report = account
+//!     .place_order_and_await(&order, Duration::from_secs(30))
+//!     .await?;
+//! if report.is_filled() {
+//!     println!("filled {} in {} fills", report.filled_quantity(), report.fills().len());
+//! } else if !report.is_final() {
+//!     account.cancel_order(report.order.id.clone()).await?;
+//! }
+//! ```
+
+use crate::LiveOrderRecord;
+use crate::accounts::Account;
+use crate::api::base::TastyResult;
+use crate::streaming::account_streaming::{AccountEvent, AccountMessage};
+use crate::types::order::{Order, OrderStatus};
+use serde::Serialize;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Outcome of [`Account::place_order_and_await`].
+#[derive(Debug, Clone, Serialize)]
+pub struct FillReport {
+    /// The last state seen of the order.
+    pub order: LiveOrderRecord,
+    /// Number of order updates received from the account stream.
+    pub updates: usize,
+}
+
+impl FillReport {
+    /// Returns `true` when the order filled.
+    pub fn is_filled(&self) -> bool {
+        matches!(self.order.status, OrderStatus::Filled)
+    }
+
+    /// Returns `true` when the order stopped working, filled or not. `false` means the
+    /// timeout elapsed with the order still working.
+    pub fn is_final(&self) -> bool {
+        !self.order.status.is_working()
+    }
+
+    /// Quantity filled over all legs.
+    pub fn filled_quantity(&self) -> u64 {
+        self.order
+            .legs
+            .iter()
+            .map(|leg| leg.quantity.saturating_sub(leg.remaining_quantity))
+            .sum()
+    }
+
+    /// Every fill of every leg, as reported by the API.
+    pub fn fills(&self) -> Vec<&serde_json::Value> {
+        self.order.legs.iter().flat_map(|leg| &leg.fills).collect()
+    }
+}
+
+/// Applies `event` to `order` when it is an update of the same order. Returns `true`
+/// when it was.
+fn apply_update(order: &mut LiveOrderRecord, event: &AccountEvent) -> bool {
+    match event {
+        AccountEvent::AccountMessage(message) => match message.as_ref() {
+            AccountMessage::Order(record) if record.id.0 == order.id.0 => {
+                *order = record.clone();
+                true
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+impl Account<'_> {
+    /// Places `order` and waits up to `timeout` for it to stop working. See the module
+    /// docs.
+    ///
+    /// Reaching the timeout is not an error: the report then holds the order still
+    /// working, to be cancelled or left as the caller sees fit.
+    pub async fn place_order_and_await(
+        &self,
+        order: &Order,
+        timeout: Duration,
+    ) -> TastyResult<FillReport> {
+        let deadline = Instant::now() + timeout;
+        let streamer = self.tasty.create_account_streamer().await?;
+        streamer.subscribe_to_account(self).await;
+
+        let mut current = self.place_order(order).await?.into_data().order;
+        let mut updates = 0;
+        while current.status.is_working() {
+            match tokio::time::timeout_at(deadline, streamer.get_event()).await {
+                Ok(Ok(event)) => {
+                    if apply_update(&mut current, &event) {
+                        updates += 1;
+                    }
+                }
+                Ok(Err(_)) | Err(_) => break,
+            }
+        }
+
+        let order = self.order(current.id.clone()).await.unwrap_or(current);
+        Ok(FillReport { order, updates })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order_event(id: u64, status: &str, remaining: u64) -> AccountEvent {
+        let json = format!(
+            r#"{{"type": "Order", "data": {{
+                "id": {id},
+                "account-number": "TEST123",
+                "time-in-force": "Day",
+                "order-type": "Limit",
+                "size": 10,
+                "underlying-symbol": "AAPL",
+                "price": "150.00",
+                "price-effect": "Debit",
+                "status": "{status}",
+                "cancellable": true,
+                "editable": true,
+                "edited": false,
+                "legs": [{{
+                    "instrument-type": "Equity",
+                    "symbol": "AAPL",
+                    "quantity": 10,
+                    "remaining-quantity": {remaining},
+                    "action": "Buy to Open",
+                    "fills": [{{"fill-price": "150.0", "quantity": "4"}}]
+                }}]
+            }}}}"#
+        );
+        AccountEvent::AccountMessage(Box::new(serde_json::from_str(&json).unwrap()))
+    }
+
+    fn record(event: AccountEvent) -> LiveOrderRecord {
+        match event {
+            AccountEvent::AccountMessage(message) => match *message {
+                AccountMessage::Order(record) => record,
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_apply_update_follows_the_placed_order() {
+        let mut current = record(order_event(1, "Received", 10));
+        assert!(!apply_update(&mut current, &order_event(2, "Filled", 0)));
+        assert!(current.status.is_working());
+
+        assert!(apply_update(&mut current, &order_event(1, "Live", 6)));
+        let report = FillReport {
+            order: current.clone(),
+            updates: 1,
+        };
+        assert!(!report.is_final());
+        assert_eq!(report.filled_quantity(), 4);
+        assert_eq!(report.fills().len(), 1);
+
+        assert!(apply_update(&mut current, &order_event(1, "Filled", 0)));
+        let report = FillReport {
+            order: current,
+            updates: 2,
+        };
+        assert!(report.is_filled() && report.is_final());
+        assert_eq!(report.filled_quantity(), 10);
+    }
+}
@@ -1,16 +1,30 @@
+use crate::error::InnerApiError;
+use crate::types::order::Warning;
 use crate::{ApiError, TastyTradeError};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use reqwest::header::HeaderMap;
 use std::fmt::Display;
+use std::ops::Deref;
+use std::time::Duration;
 use tracing::warn;
 
+/// Header names checked, in order, for the id the API gives each request.
+const REQUEST_ID_HEADERS: [&str; 2] = ["x-request-id", "x-amzn-requestid"];
+
+/// Body of every API response: either the `data` envelope or an `error`.
 #[derive(thiserror::Error, Debug, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum TastyApiResponse<T: Serialize + std::fmt::Debug> {
+    /// A successful response.
     Success(Response<T>),
-    Error { error: ApiError },
+    /// A rejected request.
+    Error {
+        /// What the API reported.
+        error: ApiError,
+    },
 }
 
 impl Display for TastyApiResponse<String> {
@@ -22,29 +36,48 @@ impl Display for TastyApiResponse<String> {
     }
 }
 
+/// The `data` envelope of a successful response.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Response<T: Serialize + std::fmt::Debug> {
+    /// The payload.
     pub data: T,
+    /// The path the API answered, e.g. `/accounts/5WT00001/positions`.
     pub context: String,
+    /// Position of this page in a paginated listing; absent on single-page responses.
     pub pagination: Option<Pagination>,
 }
 
+/// Position of a page in a paginated listing.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Pagination {
+    /// Items per page.
     pub per_page: usize,
+    /// Zero-based index of this page.
     pub page_offset: usize,
+    /// Index of the first item of this page in the whole listing.
     pub item_offset: usize,
+    /// Items in the whole listing.
     pub total_items: usize,
+    /// Pages in the whole listing.
     pub total_pages: usize,
+    /// Items on this page.
     pub current_item_count: usize,
+    /// Link to the previous page, if any.
     pub previous_link: Option<String>,
+    /// Link to the next page, if any.
     pub next_link: Option<String>,
+    /// Template of the links to other pages.
     pub paging_link_template: Option<String>,
 }
 
+/// The `{"items": [...]}` payload of list endpoints.
+///
+/// Items that fail to deserialize are logged and skipped rather than failing the whole
+/// list, so one unexpected record does not hide the others.
 #[derive(Debug, Serialize)]
 pub struct Items<T: DeserializeOwned + Serialize + std::fmt::Debug> {
+    /// The items that deserialized.
     pub items: Vec<T>,
 }
 
@@ -96,10 +129,220 @@ where
     }
 }
 
+impl Pagination {
+    /// Pagination of a response that fits in a single page of `items` items.
+    pub fn single_page(items: usize) -> Self {
+        Self {
+            per_page: items,
+            page_offset: 0,
+            item_offset: 0,
+            total_items: items,
+            total_pages: 1,
+            current_item_count: items,
+            previous_link: None,
+            next_link: None,
+            paging_link_template: None,
+        }
+    }
+
+    /// Returns `true` when a page follows this one.
+    pub fn has_next_page(&self) -> bool {
+        self.page_offset + 1 < self.total_pages
+    }
+}
+
+/// One page of a list endpoint, with its position in the listing.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Paginated<T> {
+    /// The items of this page.
     pub items: Vec<T>,
+    /// Position of this page; a single page when the API sent none.
     pub pagination: Pagination,
 }
 
+/// Transport details of a response, returned next to its data by
+/// [`TastyTrade::get_with_context`](crate::TastyTrade::get_with_context).
+#[derive(Debug, Clone, Serialize)]
+pub struct ResponseContext {
+    /// HTTP status code.
+    pub status: u16,
+    /// The id the API assigned to the request, when it sent one. Quote it when
+    /// reporting a problem to support.
+    pub request_id: Option<String>,
+    /// The path the API answered, from the response envelope.
+    pub api_context: String,
+    /// Time until the response headers arrived, including a session refresh and retry
+    /// when the first attempt was rejected.
+    pub latency: Duration,
+    /// Time until the body was read and parsed.
+    pub total: Duration,
+}
+
+impl ResponseContext {
+    /// The id of a request from the response `headers`.
+    pub(crate) fn request_id(headers: &HeaderMap) -> Option<String> {
+        REQUEST_ID_HEADERS
+            .iter()
+            .filter_map(|name| headers.get(*name))
+            .find_map(|value| value.to_str().ok().map(str::to_string))
+    }
+}
+
+/// Data of a response together with its [`ResponseContext`]. Dereferences to the data.
+#[derive(Debug, Serialize)]
+pub struct WithContext<T> {
+    /// The typed payload.
+    pub data: T,
+    /// Status, request id and timings of the response.
+    pub context: ResponseContext,
+}
+
+impl<T> Deref for WithContext<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<T> WithContext<T> {
+    /// Discards the context, returning the payload.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+/// Result of a mutating call (order placement, cancellation, dry run...).
+///
+/// Write endpoints can succeed while still reporting `warnings` and partial `errors`,
+/// either next to `data` or inside it. Both places are collected here so nothing is
+/// lost when `T` does not model them. The wrapper dereferences to `data`.
+#[derive(Debug, Serialize)]
+pub struct WriteResponse<T> {
+    /// The typed payload.
+    pub data: T,
+    /// Warnings reported by the API.
+    pub warnings: Vec<Warning>,
+    /// Non-fatal errors reported alongside a successful response.
+    pub errors: Vec<InnerApiError>,
+}
+
+impl<T> Deref for WriteResponse<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<T: DeserializeOwned> WriteResponse<T> {
+    /// Parses a raw response body, turning an `error` envelope into `TastyTradeError::Api`.
+    pub fn from_body(body: &str) -> TastyResult<Self> {
+        let mut root: serde_json::Value = serde_json::from_str(body)?;
+        if let Some(error) = root.get_mut("error") {
+            let error: ApiError = serde_json::from_value(error.take())?;
+            return Err(error.into());
+        }
+
+        let data = root
+            .get_mut("data")
+            .map(serde_json::Value::take)
+            .unwrap_or_default();
+        let mut warnings = Vec::new();
+        let mut errors = Vec::new();
+        for source in [&root, &data] {
+            warnings.extend(Self::lenient_list::<Warning>(source, "warnings"));
+            errors.extend(Self::lenient_list::<InnerApiError>(source, "errors"));
+        }
+
+        Ok(Self {
+            data: serde_json::from_value(data)?,
+            warnings,
+            errors,
+        })
+    }
+
+    fn lenient_list<E: DeserializeOwned>(value: &serde_json::Value, key: &str) -> Vec<E> {
+        value
+            .get(key)
+            .and_then(serde_json::Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| serde_json::from_value(item.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` when the API reported neither warnings nor errors.
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty() && self.errors.is_empty()
+    }
+}
+
+impl<T> WriteResponse<T> {
+    /// Discards warnings and errors, returning the payload.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
 pub type TastyResult<T> = Result<T, TastyTradeError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Placed {
+        id: u64,
+    }
+
+    #[test]
+    fn test_write_response_collects_warnings_and_errors() {
+        let body = r#"{
+            "data": {
+                "id": 42,
+                "warnings": [{"code": "w1", "message": "first"}],
+                "errors": [{"code": "e1", "message": "partial"}]
+            },
+            "warnings": [{"code": "w2", "message": "second"}],
+            "context": "/accounts/ABC/orders"
+        }"#;
+
+        let resp = WriteResponse::<Placed>::from_body(body).unwrap();
+        assert_eq!(resp.id, 42);
+        assert_eq!(resp.warnings.len(), 2);
+        assert_eq!(resp.warnings[0].code, "w2");
+        assert_eq!(resp.warnings[1].message, "first");
+        assert_eq!(resp.errors.len(), 1);
+        assert_eq!(resp.errors[0].message, "partial");
+        assert!(!resp.is_clean());
+    }
+
+    #[test]
+    fn test_response_context_request_id() {
+        use reqwest::header::HeaderValue;
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(ResponseContext::request_id(&headers), None);
+        headers.insert("x-amzn-requestid", HeaderValue::from_static("amzn-1"));
+        assert_eq!(
+            ResponseContext::request_id(&headers).as_deref(),
+            Some("amzn-1")
+        );
+        headers.insert("x-request-id", HeaderValue::from_static("req-1"));
+        assert_eq!(
+            ResponseContext::request_id(&headers).as_deref(),
+            Some("req-1")
+        );
+    }
+
+    #[test]
+    fn test_write_response_error_envelope() {
+        let body = r#"{"error": {"code": "invalid_order", "message": "Bad order"}}"#;
+        let err = WriteResponse::<Placed>::from_body(body).unwrap_err();
+        assert!(matches!(err, TastyTradeError::Api(_)));
+    }
+}
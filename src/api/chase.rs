@@ -0,0 +1,212 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Chasing a limit order toward the market.
+//!
+//! [`Account::place_and_chase`] places a limit order and, while it stays unfilled,
+//! replaces it at a price one `step` closer to the market every `interval`: higher for
+//! debits, lower for credits. It stops when the order fills or ends, or after
+//! `max_reprices` replacements, leaving the last order working.
+//!
+//! ```rust,ignore
+//! let report = account
+//!     .place_and_chase_with(&order, dec!(0.01), 5, Duration::from_secs(10), |event| {
+//!         info!("{event:?}")
+//!     })
+//!     .await?;
+//! if !report.is_filled() {
+//!     account.cancel_order(report.order.id.clone()).await?;
+//! }
+//! ```
+
+use crate::LiveOrderRecord;
+use crate::accounts::Account;
+use crate::api::base::TastyResult;
+use crate::types::order::{Order, OrderStatus, PriceEffect};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Progress of a chase.
+#[derive(Debug, Clone, Serialize)]
+pub enum ChaseEvent {
+    /// The order was placed at `price`.
+    Placed {
+        /// Id of the order.
+        order_id: u64,
+        /// Its limit price.
+        price: Decimal,
+    },
+    /// The order was replaced at a price closer to the market.
+    Repriced {
+        /// Number of this replacement, from 1.
+        attempt: u32,
+        /// Id of the replaced order.
+        previous_order_id: u64,
+        /// Id of the replacement.
+        order_id: u64,
+        /// The new limit price.
+        price: Decimal,
+    },
+    /// The order filled.
+    Filled {
+        /// Id of the order.
+        order_id: u64,
+        /// Its limit price.
+        price: Decimal,
+    },
+    /// The order stopped working without filling, e.g. cancelled elsewhere or rejected.
+    Ended {
+        /// Id of the order.
+        order_id: u64,
+        /// Its final status.
+        status: OrderStatus,
+    },
+    /// No more replacements are allowed, or the price cannot step further; the order is
+    /// left working.
+    GaveUp {
+        /// Id of the order.
+        order_id: u64,
+        /// Its limit price.
+        price: Decimal,
+    },
+}
+
+/// Outcome of [`Account::place_and_chase`].
+#[derive(Debug, Clone)]
+pub struct ChaseReport {
+    /// The last state seen of the last order of the chase.
+    pub order: LiveOrderRecord,
+    /// Number of replacements made.
+    pub reprices: u32,
+    /// Every event, in order.
+    pub events: Vec<ChaseEvent>,
+}
+
+impl ChaseReport {
+    /// Returns `true` when the chase ended with a fill.
+    pub fn is_filled(&self) -> bool {
+        matches!(self.order.status, OrderStatus::Filled)
+    }
+}
+
+/// The price one `step` closer to the market, `None` when it cannot move: credits do
+/// not go to zero or below, and orders without a debit or credit are not chased.
+pub fn chase_price(price: Decimal, step: Decimal, effect: &PriceEffect) -> Option<Decimal> {
+    match effect {
+        PriceEffect::Debit => Some(price + step.abs()),
+        PriceEffect::Credit => Some(price - step.abs()).filter(|p| *p > Decimal::ZERO),
+        _ => None,
+    }
+}
+
+impl Account<'_> {
+    /// Places the limit `order` and chases it toward the market. See the module docs.
+    pub async fn place_and_chase(
+        &self,
+        order: &Order,
+        step: Decimal,
+        max_reprices: u32,
+        interval: Duration,
+    ) -> TastyResult<ChaseReport> {
+        self.place_and_chase_with(order, step, max_reprices, interval, |_| {})
+            .await
+    }
+
+    /// Like [`place_and_chase`](Self::place_and_chase), calling `on_event` as the chase
+    /// progresses.
+    ///
+    /// An error after placement, e.g. a replacement rejected because the order filled
+    /// meanwhile, ends the chase with the order in whatever state it reached: check the
+    /// account before placing it again.
+    pub async fn place_and_chase_with(
+        &self,
+        order: &Order,
+        step: Decimal,
+        max_reprices: u32,
+        interval: Duration,
+        mut on_event: impl FnMut(&ChaseEvent),
+    ) -> TastyResult<ChaseReport> {
+        let mut events = Vec::new();
+        let mut emit = |event: ChaseEvent| {
+            on_event(&event);
+            events.push(event);
+        };
+
+        let mut current = self.place_order(order).await?.into_data().order;
+        emit(ChaseEvent::Placed {
+            order_id: current.id.0,
+            price: current.price,
+        });
+
+        let mut reprices = 0;
+        loop {
+            tokio::time::sleep(interval).await;
+            current = self.order(current.id.clone()).await?;
+            if matches!(current.status, OrderStatus::Filled) {
+                emit(ChaseEvent::Filled {
+                    order_id: current.id.0,
+                    price: current.price,
+                });
+                break;
+            }
+            if !current.status.is_working() {
+                emit(ChaseEvent::Ended {
+                    order_id: current.id.0,
+                    status: current.status.clone(),
+                });
+                break;
+            }
+            let next = chase_price(current.price, step, &current.price_effect);
+            let Some(price) = next.filter(|_| reprices < max_reprices) else {
+                emit(ChaseEvent::GaveUp {
+                    order_id: current.id.0,
+                    price: current.price,
+                });
+                break;
+            };
+
+            let previous_order_id = current.id.0;
+            current = self.reprice_order(&current, price).await?.into_data();
+            reprices += 1;
+            emit(ChaseEvent::Repriced {
+                attempt: reprices,
+                previous_order_id,
+                order_id: current.id.0,
+                price,
+            });
+        }
+
+        Ok(ChaseReport {
+            order: current,
+            reprices,
+            events,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chase_price_moves_toward_the_market() {
+        let step = Decimal::new(5, 2);
+        assert_eq!(
+            chase_price(Decimal::new(120, 2), step, &PriceEffect::Debit),
+            Some(Decimal::new(125, 2))
+        );
+        assert_eq!(
+            chase_price(Decimal::new(120, 2), -step, &PriceEffect::Credit),
+            Some(Decimal::new(115, 2))
+        );
+        assert_eq!(
+            chase_price(Decimal::new(5, 2), step, &PriceEffect::Credit),
+            None
+        );
+        assert_eq!(chase_price(Decimal::ONE, step, &PriceEffect::None), None);
+    }
+}
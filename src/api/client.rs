@@ -1,27 +1,52 @@
 use std::fmt::Display;
 
 use crate::accounts::{Account, AccountInner, AccountNumber};
+use crate::api::auth::{
+    AuthProvider, AuthSession, FallbackAuth, OAuth2Auth, PasswordAuth, RememberTokenAuth,
+    StaticTokenAuth,
+};
 use crate::api::base::Items;
 use crate::api::base::Paginated;
+use crate::api::base::Pagination;
 use crate::api::base::Response;
+use crate::api::base::ResponseContext;
 use crate::api::base::TastyApiResponse;
 use crate::api::base::TastyResult;
+use crate::api::base::WithContext;
+use crate::api::base::WriteResponse;
+use crate::api::keepalive::Keepalive;
+use crate::api::session::{SESSION_REFRESH_MARGIN, SessionManager};
+use crate::api::stats::ClientStats;
+use crate::api::throttle::OrderThrottle;
+use crate::streaming::cancellation::CancellationToken;
 use crate::streaming::quote_streamer::QuoteStreamer;
-use crate::types::login::{LoginCredentials, LoginResponse};
+use crate::types::customer::Customer;
+use crate::types::login::SessionValidation;
+use crate::utils::clock::{SharedClock, system_clock};
 use crate::utils::config::TastyTradeConfig;
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
 use reqwest::ClientBuilder;
+use reqwest::StatusCode;
 use reqwest::header;
 use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
-use tracing::debug;
+use std::sync::Arc;
+use tracing::{debug, warn};
 
 #[derive(Debug, Clone)]
 pub struct TastyTrade {
     pub(crate) client: reqwest::Client,
-    pub(crate) session_token: String,
+    pub(crate) session: Arc<SessionManager>,
     pub(crate) config: TastyTradeConfig,
+    pub(crate) order_throttle: Arc<OrderThrottle>,
+    pub(crate) clock: SharedClock,
+    pub(crate) auth: Arc<dyn AuthProvider>,
+    pub(crate) stats: Arc<ClientStats>,
+    pub(crate) keepalive: Arc<Keepalive>,
 }
 
 impl Display for TastyTrade {
@@ -49,9 +74,10 @@ impl<T: DeserializeOwned + Serialize + std::fmt::Debug> FromTastyResponse<Items<
         debug!("🔍 resp.data.items.len(): {}", resp.data.items.len());
         debug!("🔍 resp.pagination: {:?}", resp.pagination);
 
+        // Endpoints that fit everything in one response omit the pagination block
         let pagination = resp
             .pagination
-            .expect("Pagination should be present for paginated responses");
+            .unwrap_or_else(|| Pagination::single_page(resp.data.items.len()));
         debug!(
             "🔍 pagination.current_item_count: {}",
             pagination.current_item_count
@@ -66,32 +92,399 @@ impl<T: DeserializeOwned + Serialize + std::fmt::Debug> FromTastyResponse<Items<
 }
 
 impl TastyTrade {
+    /// Logs in with the credentials of `config`.
+    ///
+    /// When `config.remember_token` is set it is tried first, falling back to the password
+    /// if the API rejects it and a password is configured. Remember tokens are single use,
+    /// so persist the next one after each login:
+    ///
+    /// ```rust,ignore
+    /// let tasty = TastyTrade::login(&config).await?;
+    /// config.remember_token = tasty.remember_token();
+    /// config.save_to_file("tastytrade.json")?;
+    /// ```
     pub async fn login(config: &TastyTradeConfig) -> TastyResult<Self> {
-        let creds = Self::do_login_request(
-            &config.username,
-            &config.password,
-            config.remember_me,
-            &config.base_url,
-        )
-        .await?;
-
-        debug!("{creds:?}");
-        let client = Self::create_client(&creds);
-
-        Ok(Self {
+        config.ensure_valid()?;
+        let password = Arc::new(PasswordAuth::from_config(config));
+        let Some(token) = &config.remember_token else {
+            return Self::open(config, password).await;
+        };
+        let remember = Arc::new(RememberTokenAuth::new(
+            config.username.clone(),
+            token.clone(),
+        ));
+        if config.password.is_empty() {
+            return Self::open(config, remember).await;
+        }
+        // Later sessions also fall back to the password once the remember token is rejected
+        Self::open(config, Arc::new(FallbackAuth::new(remember, password))).await
+    }
+
+    /// The remember token issued with the latest session, if any. Requires
+    /// `remember_me` in the configuration, or a login with a remember token.
+    pub fn remember_token(&self) -> Option<String> {
+        self.session.remember_token()
+    }
+
+    /// Logs in with sessions obtained from `auth` instead of the configured password.
+    ///
+    /// The username and password of `config` may be empty; the rest of it is validated
+    /// as in [`login`](Self::login).
+    pub async fn login_with(
+        config: &TastyTradeConfig,
+        auth: Arc<dyn AuthProvider>,
+    ) -> TastyResult<Self> {
+        config.ensure_valid_without_credentials()?;
+        Self::open(config, auth).await
+    }
+
+    /// Logs in with the configured password and the one-time passcode `otp`, for accounts
+    /// with two-factor authentication.
+    ///
+    /// The passcode is only valid once: if the session has to be renewed later, the client
+    /// cannot log in again by itself. Long-running clients should use
+    /// [`login_with_otp_callback`](Self::login_with_otp_callback), or set `remember_me` and
+    /// continue with the remember token.
+    pub async fn login_with_otp(config: &TastyTradeConfig, otp: &str) -> TastyResult<Self> {
+        config.ensure_valid()?;
+        let auth = PasswordAuth::from_config(config).with_otp(otp);
+        Self::open(config, Arc::new(auth)).await
+    }
+
+    /// Logs in with the configured password, calling `otp` for a one-time passcode on this
+    /// login and every later one, e.g. to prompt the user.
+    pub async fn login_with_otp_callback<F>(config: &TastyTradeConfig, otp: F) -> TastyResult<Self>
+    where
+        F: Fn() -> BoxFuture<'static, TastyResult<String>> + Send + Sync + 'static,
+    {
+        config.ensure_valid()?;
+        let auth = PasswordAuth::from_config(config).with_otp_callback(otp);
+        Self::open(config, Arc::new(auth)).await
+    }
+
+    /// Logs in as the OAuth2 app `oauth`, without a username or password.
+    ///
+    /// Shorthand for [`login_with`](Self::login_with); every fresh session is a new access
+    /// token obtained with the app's refresh token.
+    pub async fn login_oauth(config: &TastyTradeConfig, oauth: OAuth2Auth) -> TastyResult<Self> {
+        Self::login_with(config, Arc::new(oauth)).await
+    }
+
+    /// Resumes `session_token`, saved from an earlier session, when the API still accepts
+    /// it, and logs in with the configured password otherwise.
+    ///
+    /// Saves a login per process restart; the password is only sent when needed.
+    pub async fn login_or_resume(
+        config: &TastyTradeConfig,
+        session_token: Option<&str>,
+    ) -> TastyResult<Self> {
+        config.ensure_valid()?;
+        let auth: Arc<dyn AuthProvider> = Arc::new(PasswordAuth::from_config(config));
+        if let Some(token) = session_token {
+            let tasty = Self::with_session(
+                config,
+                Self::create_client(config),
+                auth.clone(),
+                AuthSession::new(token),
+            );
+            match tasty.validate_session().await {
+                Ok(validation) => {
+                    debug!("Resumed session of {}", validation.username);
+                    return Ok(tasty);
+                }
+                Err(e) => debug!("Saved session rejected, logging in again: {e}"),
+            }
+        }
+        Self::open(config, auth).await
+    }
+
+    /// Builds a client on `session_token`, a session opened elsewhere, e.g. by another
+    /// process sharing it, without logging in or checking it.
+    ///
+    /// When `config` carries a username and password, they open a new session once the
+    /// API rejects the token; otherwise the token is used as long as it lasts and requests
+    /// fail with `401 Unauthorized` afterwards.
+    /// Call [`is_session_valid`](Self::is_session_valid) to check the token first.
+    pub fn from_session_token(
+        session_token: impl Into<String>,
+        config: &TastyTradeConfig,
+    ) -> TastyResult<Self> {
+        config.ensure_valid_without_credentials()?;
+        let session_token = session_token.into();
+        let auth: Arc<dyn AuthProvider> =
+            if config.username.is_empty() || config.password.is_empty() {
+                Arc::new(StaticTokenAuth::new(session_token.clone()))
+            } else {
+                Arc::new(PasswordAuth::from_config(config))
+            };
+        Ok(Self::with_session(
+            config,
+            Self::create_client(config),
+            auth,
+            AuthSession::new(session_token),
+        ))
+    }
+
+    /// Asks the API whether the current session token is still valid.
+    ///
+    /// Returns the user it belongs to and, when reported, its expiration; a rejected or
+    /// expired token is an error. Cheap enough for health checks.
+    ///
+    /// Unlike other requests, a rejected token is not refreshed and retried: the point is
+    /// to learn whether the session held now is accepted.
+    pub async fn validate_session(&self) -> TastyResult<SessionValidation> {
+        let result = self
+            .send_validation()
+            .await?
+            .json::<TastyApiResponse<SessionValidation>>()
+            .await?;
+        match result {
+            TastyApiResponse::Success(s) => Ok(s.data),
+            TastyApiResponse::Error { error } => Err(error.into()),
+        }
+    }
+
+    /// Whether the API still accepts the current session token, e.g. before placing
+    /// orders from a long-running service.
+    ///
+    /// `false` when the token is rejected with `401 Unauthorized` or the session was
+    /// [logged out](Self::logout); other failures are errors.
+    pub async fn is_session_valid(&self) -> TastyResult<bool> {
+        if self.session.is_closed() {
+            return Ok(false);
+        }
+        let response = self.send_validation().await?;
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            return Ok(false);
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(crate::TastyTradeError::Unknown(format!(
+                "HTTP {} for session validation: {}",
+                status.as_u16(),
+                body
+            )));
+        }
+        Ok(true)
+    }
+
+    async fn send_validation(&self) -> TastyResult<reqwest::Response> {
+        self.session.ensure_open()?;
+        let url = format!("{}/sessions/validate", self.config.base_url);
+        self.send_once(self.client.post(&url).body("{}"), &self.session.token())
+            .await
+    }
+
+    async fn open(config: &TastyTradeConfig, auth: Arc<dyn AuthProvider>) -> TastyResult<Self> {
+        let client = Self::create_client(config);
+        let session = auth.acquire(&client, &config.base_url).await?;
+        debug!("{session:?}");
+        Ok(Self::with_session(config, client, auth, session))
+    }
+
+    fn with_session(
+        config: &TastyTradeConfig,
+        client: reqwest::Client,
+        auth: Arc<dyn AuthProvider>,
+        session: AuthSession,
+    ) -> Self {
+        Self {
             client,
-            session_token: creds.session_token,
+            session: Arc::new(SessionManager::new(session)),
             config: config.clone(),
-        })
+            order_throttle: Arc::new(OrderThrottle::new(config.order_throttle.clone())),
+            clock: system_clock(),
+            auth,
+            stats: Arc::new(ClientStats::default()),
+            keepalive: Arc::default(),
+        }
     }
 
-    fn create_client(creds: &LoginResponse) -> reqwest::Client {
-        let mut headers = HeaderMap::new();
+    /// Asks the auth provider for a fresh session and uses it for every later request,
+    /// in this client and its clones.
+    ///
+    /// Requests refresh the session on their own when it is about to expire or gets
+    /// rejected; this forces it, e.g. after changing the password.
+    pub async fn reauthenticate(&self) -> TastyResult<AuthSession> {
+        let session = self
+            .auth
+            .acquire(&self.client, &self.config.base_url)
+            .await?;
+        self.session.replace(session.clone());
+        Ok(session)
+    }
 
-        headers.insert(
-            header::AUTHORIZATION,
-            HeaderValue::from_str(&creds.session_token).unwrap(),
+    /// The session token in use, shared with clones.
+    pub fn session(&self) -> &SessionManager {
+        &self.session
+    }
+
+    /// Websocket URL of the account streamer: `config.websocket_url` when it was changed
+    /// from the environment's default, else the URL reported with the session, else that
+    /// default.
+    ///
+    /// Following the session keeps demo and production streamers from being mixed up
+    /// when only `base_url` was pointed at another environment.
+    pub fn account_streamer_url(&self) -> String {
+        if let Some(url) = self.config.websocket_url_override() {
+            return url.to_string();
+        }
+        self.session
+            .streamer_url()
+            .unwrap_or_else(|| self.config.websocket_url.clone())
+    }
+
+    /// When the current session expires, when the API reported it. Changes as the
+    /// session is refreshed.
+    pub fn session_expires_at(&self) -> Option<DateTime<Utc>> {
+        self.session.expires_at()
+    }
+
+    /// Calls `callback` with every new session obtained by this client or its clones,
+    /// after a proactive refresh, a `401 Unauthorized` or
+    /// [`reauthenticate`](Self::reauthenticate).
+    ///
+    /// Useful to persist the token, or the remember token, for the next process, or to
+    /// schedule re-authentication from [`AuthSession::expires_at`]. The callback runs on
+    /// the task making the request: keep it short and do not block.
+    ///
+    /// ```rust,ignore
+    /// tasty.on_session_refreshed(|session| {
+    ///     let _ = std::fs::write("session.token", &session.session_token);
+    /// });
+    /// ```
+    pub fn on_session_refreshed<F>(&self, callback: F)
+    where
+        F: Fn(&AuthSession) + Send + Sync + 'static,
+    {
+        self.session.on_refreshed(Arc::new(callback));
+    }
+
+    /// Destroys the session on the server with `DELETE /sessions`.
+    ///
+    /// Afterwards this client and its clones fail every request with
+    /// [`TastyTradeError::LoggedOut`](crate::TastyTradeError::LoggedOut); log in again for a
+    /// new session. A session the API already considers invalid counts as logged out.
+    /// Calling it twice is a no-op. Stops the [keep-alive](Self::start_keepalive) task.
+    pub async fn logout(&self) -> TastyResult<()> {
+        if self.session.is_closed() {
+            return Ok(());
+        }
+        self.stop_keepalive();
+        let url = format!("{}/sessions", self.config.base_url);
+        let response = self
+            .send_once(self.client.delete(&url), &self.session.token())
+            .await?;
+        let status = response.status();
+        if !status.is_success() && status != StatusCode::UNAUTHORIZED {
+            let body = response.text().await.unwrap_or_default();
+            return Err(crate::TastyTradeError::Unknown(format!(
+                "HTTP {} for logout: {}",
+                status.as_u16(),
+                body
+            )));
+        }
+        self.session.close();
+        Ok(())
+    }
+
+    /// Latency and rate-limit statistics of the requests made so far, shared with clones.
+    pub fn stats(&self) -> &ClientStats {
+        &self.stats
+    }
+
+    /// Sends `request` with the session token, recording its latency and rate-limit
+    /// headers in [`stats`](Self::stats).
+    ///
+    /// The session is refreshed first when it expires within [`SESSION_REFRESH_MARGIN`].
+    /// A request rejected with `401 Unauthorized` is sent once more with a fresh session,
+    /// unless its body cannot be replayed or `auto_relogin` is off in the configuration.
+    pub(crate) async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> TastyResult<reqwest::Response> {
+        self.session.ensure_open()?;
+        let mut token = self.session.token();
+        if self
+            .session
+            .expires_within(self.clock.now(), SESSION_REFRESH_MARGIN)
+        {
+            match self
+                .session
+                .refresh(
+                    self.auth.as_ref(),
+                    &self.client,
+                    &self.config.base_url,
+                    &token,
+                )
+                .await
+            {
+                Ok(fresh) => token = fresh,
+                Err(e) => warn!("Could not refresh expiring session: {e}"),
+            }
+        }
+
+        let retry = request.try_clone().filter(|_| self.config.auto_relogin);
+        let response = self.send_once(request, &token).await?;
+        let Some(retry) = retry.filter(|_| response.status() == StatusCode::UNAUTHORIZED) else {
+            return Ok(response);
+        };
+        debug!("Session rejected, retrying once with a fresh session");
+        let token = self
+            .session
+            .refresh(
+                self.auth.as_ref(),
+                &self.client,
+                &self.config.base_url,
+                &token,
+            )
+            .await?;
+        self.send_once(retry, &token).await
+    }
+
+    async fn send_once(
+        &self,
+        request: reqwest::RequestBuilder,
+        token: &str,
+    ) -> TastyResult<reqwest::Response> {
+        let started = self.clock.instant();
+        let response = request.header(header::AUTHORIZATION, token).send().await?;
+        self.stats.record(
+            response.status().as_u16(),
+            response.headers(),
+            self.clock.instant().saturating_duration_since(started),
+        );
+        Ok(response)
+    }
+
+    /// The provider sessions are obtained from.
+    pub fn auth_provider(&self) -> &Arc<dyn AuthProvider> {
+        &self.auth
+    }
+
+    /// Replaces the session's time source, e.g. with a [`MockClock`](crate::utils::clock::MockClock)
+    /// in tests.
+    ///
+    /// The order throttle is rebuilt on the new clock, forgetting recent placements.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.order_throttle = Arc::new(
+            OrderThrottle::new(self.config.order_throttle.clone()).with_clock(clock.clone()),
         );
+        self.clock = clock;
+        self
+    }
+
+    /// The session's time source.
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// HTTP client sending the headers of `config` with every request, logins included.
+    /// Headers that are not valid are skipped; [`TastyTradeConfig::validate`] reports them.
+    pub(crate) fn create_client(config: &TastyTradeConfig) -> reqwest::Client {
+        let mut headers = HeaderMap::new();
+
         headers.insert(
             header::CONTENT_TYPE,
             HeaderValue::from_str("application/json").unwrap(),
@@ -100,6 +493,25 @@ impl TastyTrade {
             header::USER_AGENT,
             HeaderValue::from_str("tastytrade").unwrap(),
         );
+        for (name, value) in &config.extra_headers {
+            match (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                (Ok(name), Ok(value)) => {
+                    headers.insert(name, value);
+                }
+                _ => warn!("Skipping invalid header {name}"),
+            }
+        }
+        if let Some(agent) = &config.user_agent {
+            match HeaderValue::from_str(agent) {
+                Ok(agent) => {
+                    headers.insert(header::USER_AGENT, agent);
+                }
+                Err(_) => warn!("Skipping invalid User-Agent {agent:?}"),
+            }
+        }
 
         ClientBuilder::new()
             .default_headers(headers)
@@ -107,39 +519,31 @@ impl TastyTrade {
             .expect("Could not create client")
     }
 
-    async fn do_login_request(
-        login: &str,
-        password: &str,
-        remember_me: bool,
-        base_url: &str,
-    ) -> TastyResult<LoginResponse> {
-        let client = reqwest::Client::default();
-
-        let resp = client
-            .post(format!("{base_url}/sessions"))
-            .header(header::CONTENT_TYPE, "application/json")
-            .header(header::USER_AGENT, "tastytrade")
-            .json(&LoginCredentials {
-                login: login.to_string(),
-                password: password.to_string(),
-                remember_me,
-            })
-            .send()
-            .await?;
-        let json = resp
-            //.inspect_json::<TastyApiResponse<LoginResponse>, TastyError>(|text| println!("{text}"))
-            .json()
-            .await?;
-        let response = match json {
-            TastyApiResponse::Success(s) => Ok(s),
-            TastyApiResponse::Error { error } => Err(error),
-        }?
-        .data;
-
-        Ok(response)
+    pub async fn get_with_query<T, R, U>(&self, url: U, query: &[(&str, &str)]) -> TastyResult<R>
+    where
+        T: DeserializeOwned + Serialize + std::fmt::Debug,
+        R: FromTastyResponse<T>,
+        U: AsRef<str>,
+    {
+        self.get_with_context(url, query)
+            .await
+            .map(WithContext::into_data)
     }
 
-    pub async fn get_with_query<T, R, U>(&self, url: U, query: &[(&str, &str)]) -> TastyResult<R>
+    /// Like [`get_with_query`](Self::get_with_query), also returning the status, request
+    /// id and timings of the response.
+    ///
+    /// ```rust,ignore
+    /// let page: WithContext<Paginated<FullPosition>> = tasty
+    ///     .get_with_context::<Items<FullPosition>, _, _>("/accounts/5WT00001/positions", &[])
+    ///     .await?;
+    /// debug!("{:?} in {:?}", page.context.request_id, page.context.latency);
+    /// ```
+    pub async fn get_with_context<T, R, U>(
+        &self,
+        url: U,
+        query: &[(&str, &str)],
+    ) -> TastyResult<WithContext<R>>
     where
         T: DeserializeOwned + Serialize + std::fmt::Debug,
         R: FromTastyResponse<T>,
@@ -157,9 +561,12 @@ impl TastyTrade {
             format!("{}?{}", full_url, query_string)
         };
 
-        let response = self.client.get(&full_url).query(query).send().await?;
+        let started = self.clock.instant();
+        let response = self.send(self.client.get(&full_url).query(query)).await?;
+        let latency = self.clock.instant().saturating_duration_since(started);
 
         let status = response.status();
+        let request_id = ResponseContext::request_id(response.headers());
 
         if !status.is_success() {
             let error_text = response
@@ -185,7 +592,19 @@ impl TastyTrade {
         })?;
 
         match result {
-            TastyApiResponse::Success(s) => Ok(R::from_tasty(s)),
+            TastyApiResponse::Success(s) => {
+                let context = ResponseContext {
+                    status: status.as_u16(),
+                    request_id,
+                    api_context: s.context.clone(),
+                    latency,
+                    total: self.clock.instant().saturating_duration_since(started),
+                };
+                Ok(WithContext {
+                    data: R::from_tasty(s),
+                    context,
+                })
+            }
             TastyApiResponse::Error { error } => Err(error.into()),
         }
     }
@@ -205,10 +624,11 @@ impl TastyTrade {
     {
         let url = format!("{}{}", self.config.base_url, url.as_ref());
         let result = self
-            .client
-            .post(url)
-            .body(serde_json::to_string(&payload).unwrap())
-            .send()
+            .send(
+                self.client
+                    .post(url)
+                    .body(serde_json::to_string(&payload).unwrap()),
+            )
             .await?
             .json::<TastyApiResponse<R>>()
             .await?;
@@ -219,6 +639,69 @@ impl TastyTrade {
         }
     }
 
+    /// Like [`post`](Self::post), but keeps the warnings and errors reported with the response.
+    pub async fn post_write<R, P, U>(&self, url: U, payload: P) -> TastyResult<WriteResponse<R>>
+    where
+        R: DeserializeOwned,
+        P: Serialize,
+        U: AsRef<str>,
+    {
+        let url = format!("{}{}", self.config.base_url, url.as_ref());
+        let body = self
+            .send(self.client.post(url).body(serde_json::to_string(&payload)?))
+            .await?
+            .text()
+            .await?;
+        WriteResponse::from_body(&body)
+    }
+
+    /// Sends a `PATCH` request, keeping the warnings and errors reported with the response.
+    pub async fn patch_write<R, P, U>(&self, url: U, payload: P) -> TastyResult<WriteResponse<R>>
+    where
+        R: DeserializeOwned,
+        P: Serialize,
+        U: AsRef<str>,
+    {
+        let url = format!("{}{}", self.config.base_url, url.as_ref());
+        let body = self
+            .send(
+                self.client
+                    .patch(url)
+                    .body(serde_json::to_string(&payload)?),
+            )
+            .await?
+            .text()
+            .await?;
+        WriteResponse::from_body(&body)
+    }
+
+    /// Sends a `PUT` request, keeping the warnings and errors reported with the response.
+    pub async fn put_write<R, P, U>(&self, url: U, payload: P) -> TastyResult<WriteResponse<R>>
+    where
+        R: DeserializeOwned,
+        P: Serialize,
+        U: AsRef<str>,
+    {
+        let url = format!("{}{}", self.config.base_url, url.as_ref());
+        let body = self
+            .send(self.client.put(url).body(serde_json::to_string(&payload)?))
+            .await?
+            .text()
+            .await?;
+        WriteResponse::from_body(&body)
+    }
+
+    /// Like [`delete`](Self::delete), but keeps the warnings and errors reported with the response.
+    pub async fn delete_write<R, U>(&self, url: U) -> TastyResult<WriteResponse<R>>
+    where
+        R: DeserializeOwned,
+        U: AsRef<str>,
+    {
+        let url = format!("{}{}", self.config.base_url, url.as_ref());
+        let body = self.send(self.client.delete(url)).await?.text().await?;
+        WriteResponse::from_body(&body)
+    }
+
     pub async fn delete<R, U>(&self, url: U) -> TastyResult<R>
     where
         R: DeserializeOwned + Serialize + std::fmt::Debug,
@@ -226,9 +709,7 @@ impl TastyTrade {
     {
         let url = format!("{}{}", self.config.base_url, url.as_ref());
         let result = self
-            .client
-            .delete(url)
-            .send()
+            .send(self.client.delete(url))
             .await?
             // .inspect_json::<TastyApiResponse<R>, TastyError>(move |text| {
             //     println!("{text}");
@@ -242,6 +723,13 @@ impl TastyTrade {
         }
     }
 
+    /// The account holder: name, contact details, entity data and suitability profile.
+    ///
+    /// The accounts of the customer are listed by [`accounts`](Self::accounts).
+    pub async fn customer_info(&self) -> TastyResult<Customer> {
+        self.get("/customers/me").await
+    }
+
     pub async fn accounts(&self) -> TastyResult<Vec<Account<'_>>> {
         let resp: Items<AccountInner> = self.get("/customers/me/accounts").await?;
         Ok(resp
@@ -251,6 +739,13 @@ impl TastyTrade {
             .collect())
     }
 
+    /// The accounts the customer may place orders on, skipping read-only ones.
+    pub async fn tradable_accounts(&self) -> TastyResult<Vec<Account<'_>>> {
+        let mut accounts = self.accounts().await?;
+        accounts.retain(Account::can_trade);
+        Ok(accounts)
+    }
+
     pub async fn account(
         &self,
         account_number: impl Into<AccountNumber>,
@@ -265,8 +760,28 @@ impl TastyTrade {
         Ok(None)
     }
 
+    /// Finds an account by its nickname, ignoring case and surrounding whitespace.
+    ///
+    /// Returns the first match if several accounts share a nickname.
+    pub async fn account_by_nickname(&self, nickname: &str) -> TastyResult<Option<Account<'_>>> {
+        let nickname = nickname.trim();
+        let accounts = self.accounts().await?;
+        Ok(accounts
+            .into_iter()
+            .find(|account| account.nickname().trim().eq_ignore_ascii_case(nickname)))
+    }
+
     pub async fn create_quote_streamer(&self) -> TastyResult<QuoteStreamer> {
-        debug!("Session token: {}", self.session_token);
+        debug!("Session expires at: {:?}", self.session.expires_at());
         QuoteStreamer::connect(self).await
     }
+
+    /// Like [`create_quote_streamer`](Self::create_quote_streamer), stopping the DXLink
+    /// tasks once `cancellation` is cancelled.
+    pub async fn create_quote_streamer_with_cancellation(
+        &self,
+        cancellation: CancellationToken,
+    ) -> TastyResult<QuoteStreamer> {
+        QuoteStreamer::connect_with_cancellation(self, cancellation).await
+    }
 }
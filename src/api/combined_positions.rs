@@ -0,0 +1,112 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Positions of every account under one login, grouped by underlying.
+//!
+//! Households often split the same exposure across an IRA and a margin account.
+//! [`TastyTrade::combined_positions`] fetches all accounts concurrently and returns
+//! one [`UnderlyingPositions`] per underlying, keeping the per-account breakdown.
+
+use crate::accounts::AccountNumber;
+use crate::api::base::TastyResult;
+use crate::streaming::portfolio_state::signed_quantity;
+use crate::types::order::Symbol;
+use crate::{FullPosition, TastyTrade};
+use futures_util::future::try_join_all;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Positions in one underlying, across accounts.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnderlyingPositions {
+    /// The underlying shared by every position in the group.
+    pub underlying_symbol: Symbol,
+    /// Positions held in each account.
+    pub by_account: BTreeMap<AccountNumber, Vec<FullPosition>>,
+}
+
+impl UnderlyingPositions {
+    /// All positions in the group, account by account.
+    pub fn positions(&self) -> impl Iterator<Item = &FullPosition> {
+        self.by_account.values().flatten()
+    }
+
+    /// Signed quantity of `symbol` summed over all accounts.
+    pub fn net_quantity(&self, symbol: &Symbol) -> Decimal {
+        self.positions()
+            .filter(|p| p.symbol == *symbol)
+            .map(|p| signed_quantity(p.quantity, p.quantity_direction))
+            .sum()
+    }
+}
+
+/// Groups positions by underlying, then by account.
+pub fn group_by_underlying(
+    positions: impl IntoIterator<Item = FullPosition>,
+) -> BTreeMap<Symbol, UnderlyingPositions> {
+    let mut groups: BTreeMap<Symbol, UnderlyingPositions> = BTreeMap::new();
+    for position in positions {
+        groups
+            .entry(position.underlying_symbol.clone())
+            .or_insert_with(|| UnderlyingPositions {
+                underlying_symbol: position.underlying_symbol.clone(),
+                by_account: BTreeMap::new(),
+            })
+            .by_account
+            .entry(position.account_number.clone())
+            .or_default()
+            .push(position);
+    }
+    groups
+}
+
+impl TastyTrade {
+    /// Fetches the positions of every account concurrently and groups them by underlying.
+    ///
+    /// Fails if any account's positions cannot be fetched.
+    pub async fn combined_positions(&self) -> TastyResult<BTreeMap<Symbol, UnderlyingPositions>> {
+        let accounts = self.accounts().await?;
+        let positions = try_join_all(accounts.iter().map(|account| account.positions())).await?;
+        Ok(group_by_underlying(positions.into_iter().flatten()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(
+        account: &str,
+        symbol: &str,
+        underlying: &str,
+        qty: &str,
+        dir: &str,
+    ) -> FullPosition {
+        crate::test_fixtures::position(serde_json::json!({
+            "account-number": account,
+            "symbol": symbol,
+            "underlying-symbol": underlying,
+            "quantity": qty,
+            "quantity-direction": dir,
+        }))
+    }
+
+    #[test]
+    fn test_group_by_underlying_keeps_account_breakdown() {
+        let groups = group_by_underlying(vec![
+            position("IRA1", "AAPL", "AAPL", "100", "Long"),
+            position("MRG1", "AAPL", "AAPL", "30", "Short"),
+            position("MRG1", "SPY", "SPY", "10", "Long"),
+        ]);
+
+        assert_eq!(groups.len(), 2);
+        let aapl = &groups[&Symbol("AAPL".into())];
+        assert_eq!(aapl.by_account.len(), 2);
+        assert_eq!(aapl.positions().count(), 2);
+        assert_eq!(aapl.net_quantity(&Symbol("AAPL".into())), Decimal::from(70));
+    }
+}
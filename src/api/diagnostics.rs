@@ -0,0 +1,190 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Step-by-step connectivity checks for the quote streaming stack.
+//!
+//! [`TastyTrade::diagnose_streaming`] walks through every stage needed to receive
+//! market data and records the outcome of each one, so a failure deep inside DXLink
+//! setup can be pinned to a single step. The resulting [`DiagnosticReport`] prints as
+//! a plain-text table and serializes to JSON for support tickets.
+
+use crate::TastyTrade;
+use crate::api::quote_streaming::QuoteStreamerTokens;
+use dxlink::DXLinkClient;
+use serde::Serialize;
+use std::fmt;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Outcome of a single diagnostic step.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticStep {
+    /// Short name of the step (e.g. "dns").
+    pub name: String,
+    /// Whether the step succeeded.
+    pub passed: bool,
+    /// Details on success, or the error message on failure.
+    pub detail: String,
+    /// Time spent on the step.
+    pub elapsed: Duration,
+}
+
+/// Ordered list of diagnostic steps.
+///
+/// Steps after the first failure are not executed, so the last entry is
+/// always the one that needs attention when [`passed`](DiagnosticReport::passed) is false.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DiagnosticReport {
+    /// Steps in execution order.
+    pub steps: Vec<DiagnosticStep>,
+}
+
+impl DiagnosticReport {
+    /// Returns `true` when every executed step passed.
+    pub fn passed(&self) -> bool {
+        self.steps.iter().all(|step| step.passed)
+    }
+
+    /// Returns the first failing step, if any.
+    pub fn first_failure(&self) -> Option<&DiagnosticStep> {
+        self.steps.iter().find(|step| !step.passed)
+    }
+
+    pub(crate) fn record<T, E: fmt::Display>(
+        &mut self,
+        name: &str,
+        started: Instant,
+        result: Result<T, E>,
+        describe: impl FnOnce(&T) -> String,
+    ) -> Option<T> {
+        let elapsed = started.elapsed();
+        let (passed, detail, value) = match result {
+            Ok(value) => (true, describe(&value), Some(value)),
+            Err(e) => (false, e.to_string(), None),
+        };
+        debug!("diagnostic step {}: passed={} {}", name, passed, detail);
+        self.steps.push(DiagnosticStep {
+            name: name.to_string(),
+            passed,
+            detail,
+            elapsed,
+        });
+        value
+    }
+}
+
+impl fmt::Display for DiagnosticReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(
+                f,
+                "[{}] {:<12} {:>6} ms  {}",
+                if step.passed { "PASS" } else { "FAIL" },
+                step.name,
+                step.elapsed.as_millis(),
+                step.detail
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TastyTrade {
+    /// Runs the quote streaming connectivity checks and returns a report.
+    ///
+    /// The steps are: quote token retrieval, DNS resolution of the DXLink host,
+    /// a raw websocket handshake, and a DXLink feed channel setup/teardown.
+    /// Execution stops at the first failing step.
+    pub async fn diagnose_streaming(&self) -> DiagnosticReport {
+        let mut report = DiagnosticReport::default();
+
+        let started = Instant::now();
+        let Some(tokens) = report.record(
+            "token",
+            started,
+            self.quote_streamer_tokens().await,
+            |t: &QuoteStreamerTokens| format!("level={} url={}", t.level, t.streamer_url),
+        ) else {
+            return report;
+        };
+
+        let started = Instant::now();
+        let endpoint = reqwest::Url::parse(&tokens.streamer_url)
+            .map_err(|e| e.to_string())
+            .and_then(|url| {
+                let host = url
+                    .host_str()
+                    .ok_or("streamer URL has no host")?
+                    .to_string();
+                let port = url.port_or_known_default().unwrap_or(443);
+                Ok((host, port))
+            });
+        let resolved = match endpoint {
+            Ok((host, port)) => tokio::net::lookup_host((host.as_str(), port))
+                .await
+                .map(|addrs| (host, addrs.count()))
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        };
+        if report
+            .record("dns", started, resolved, |(host, count)| {
+                format!("{} resolved to {} address(es)", host, count)
+            })
+            .is_none()
+        {
+            return report;
+        }
+
+        let started = Instant::now();
+        let handshake = tokio_tungstenite::connect_async(tokens.streamer_url.as_str()).await;
+        let Some((mut ws, _)) = report.record("websocket", started, handshake, |(_, resp)| {
+            format!("HTTP {}", resp.status())
+        }) else {
+            return report;
+        };
+        let _ = ws.close(None).await;
+
+        let started = Instant::now();
+        let mut client = DXLinkClient::new(&tokens.streamer_url, &tokens.token);
+        let channel = match client.connect().await {
+            Ok(_) => client.create_feed_channel("AUTO").await,
+            Err(e) => Err(e),
+        };
+        report.record("channel", started, channel, |id| {
+            format!("feed channel {} opened", id)
+        });
+        let _ = client.disconnect().await;
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_records_steps() {
+        let mut report = DiagnosticReport::default();
+        let ok = report.record("token", Instant::now(), Ok::<_, String>(1), |v| {
+            format!("value {}", v)
+        });
+        assert_eq!(ok, Some(1));
+        assert!(report.passed());
+
+        let failed = report.record("dns", Instant::now(), Err::<u8, _>("no such host"), |_| {
+            String::new()
+        });
+        assert!(failed.is_none());
+        assert!(!report.passed());
+        assert_eq!(report.first_failure().unwrap().name, "dns");
+
+        let text = report.to_string();
+        assert!(text.contains("[PASS] token"));
+        assert!(text.contains("[FAIL] dns"));
+        assert!(text.contains("no such host"));
+    }
+}
@@ -0,0 +1,255 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Bracket exits for open positions.
+//!
+//! [`Account::attach_exit`] closes a position with an OCO (one-cancels-other) complex
+//! order: a GTC limit at the profit target and a GTC stop at the stop loss, both sized
+//! to the full position. Whichever fills first cancels the other.
+//!
+//! ```rust,ignore
+//! let position = &account.positions().await?[0];
+//! account.attach_exit(position, dec!(190), dec!(170)).await?;
+//! ```
+
+use crate::accounts::Account;
+use crate::api::base::{TastyResult, WriteResponse};
+use crate::types::instrument::InstrumentType;
+use crate::types::order::{
+    Action, ComplexOrderPlacedResult, OrderLeg, OrderLegBuilder, OrderType, PriceEffect,
+    TimeInForce,
+};
+use crate::types::position::QuantityDirection;
+use crate::{FullPosition, LiveOrderRecord, TastyTradeError};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// Body of `POST /accounts/{account}/complex-orders` for an OCO exit.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct OcoExit {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    orders: [ExitOrder; 2],
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct ExitOrder {
+    time_in_force: TimeInForce,
+    order_type: OrderType,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "rust_decimal::serde::arbitrary_precision_option"
+    )]
+    price: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    price_effect: Option<PriceEffect>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        with = "rust_decimal::serde::arbitrary_precision_option"
+    )]
+    stop_trigger: Option<Decimal>,
+    legs: Vec<OrderLeg>,
+}
+
+/// Whether `action` reduces a position held in `direction`.
+fn closes(action: &Action, direction: &QuantityDirection) -> bool {
+    match direction {
+        QuantityDirection::Long => matches!(action, Action::SellToClose | Action::Sell),
+        QuantityDirection::Short => matches!(action, Action::BuyToClose | Action::Buy),
+        QuantityDirection::Zero => false,
+    }
+}
+
+/// Working orders that already close part of `position`.
+fn existing_exits<'a>(
+    position: &FullPosition,
+    live_orders: &'a [LiveOrderRecord],
+) -> Vec<&'a LiveOrderRecord> {
+    live_orders
+        .iter()
+        .filter(|order| order.status.is_working())
+        .filter(|order| {
+            order.legs.iter().any(|leg| {
+                leg.symbol == position.symbol && closes(&leg.action, &position.quantity_direction)
+            })
+        })
+        .collect()
+}
+
+/// Builds the OCO closing `position`, validating the prices against its direction.
+fn oco_exit(
+    position: &FullPosition,
+    profit_target: Decimal,
+    stop_loss: Decimal,
+) -> TastyResult<OcoExit> {
+    let invalid = |msg: String| Err(TastyTradeError::InvalidOrder(msg));
+    if position.quantity.is_zero() {
+        return invalid(format!("{} has no open quantity", position.symbol.0));
+    }
+    if profit_target <= Decimal::ZERO || stop_loss <= Decimal::ZERO {
+        return invalid("profit target and stop loss must be positive".to_string());
+    }
+
+    let crypto = matches!(position.instrument_type, InstrumentType::Cryptocurrency);
+    let (action, price_effect) = match position.quantity_direction {
+        QuantityDirection::Long if stop_loss < profit_target => (
+            if crypto {
+                Action::Sell
+            } else {
+                Action::SellToClose
+            },
+            PriceEffect::Credit,
+        ),
+        QuantityDirection::Short if stop_loss > profit_target => (
+            if crypto {
+                Action::Buy
+            } else {
+                Action::BuyToClose
+            },
+            PriceEffect::Debit,
+        ),
+        _ => {
+            return invalid(format!(
+                "stop loss {} and profit target {} are on the wrong side for a {:?} position",
+                stop_loss, profit_target, position.quantity_direction
+            ));
+        }
+    };
+
+    let legs = vec![
+        OrderLegBuilder::default()
+            .instrument_type(position.instrument_type.clone())
+            .symbol(position.symbol.clone())
+            .quantity(position.quantity)
+            .action(action)
+            .build()
+            .map_err(|e| TastyTradeError::InvalidOrder(e.to_string()))?,
+    ];
+    Ok(OcoExit {
+        kind: "OCO",
+        orders: [
+            ExitOrder {
+                time_in_force: TimeInForce::Gtc,
+                order_type: OrderType::Limit,
+                price: Some(profit_target),
+                price_effect: Some(price_effect),
+                stop_trigger: None,
+                legs: legs.clone(),
+            },
+            ExitOrder {
+                time_in_force: TimeInForce::Gtc,
+                order_type: OrderType::Stop,
+                price: None,
+                price_effect: None,
+                stop_trigger: Some(stop_loss),
+                legs,
+            },
+        ],
+    })
+}
+
+impl Account<'_> {
+    /// Attaches a profit target and a stop loss to `position` as a GTC OCO order.
+    ///
+    /// Fails with [`TastyTradeError::InvalidOrder`] without calling the API when the
+    /// prices are on the wrong side for the position's direction, or when working
+    /// orders already close part of the position: cancel them first.
+    pub async fn attach_exit(
+        &self,
+        position: &FullPosition,
+        profit_target: Decimal,
+        stop_loss: Decimal,
+    ) -> TastyResult<WriteResponse<ComplexOrderPlacedResult>> {
+        let body = oco_exit(position, profit_target, stop_loss)?;
+
+        let live_orders = self.live_orders().await?;
+        let attached = existing_exits(position, &live_orders);
+        if !attached.is_empty() {
+            let ids: Vec<String> = attached.iter().map(|o| o.id.0.to_string()).collect();
+            return Err(TastyTradeError::InvalidOrder(format!(
+                "{} already has working exit orders: {}",
+                position.symbol.0,
+                ids.join(", ")
+            )));
+        }
+
+        self.tasty.order_throttle.check_placement()?;
+        self.tasty
+            .post_write(
+                &format!(
+                    "/accounts/{}/complex-orders",
+                    self.inner.account.account_number.0
+                ),
+                body,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn position(direction: &str) -> FullPosition {
+        crate::test_fixtures::position(serde_json::json!({
+            "account-number": "TEST123",
+            "quantity": "100",
+            "quantity-direction": direction,
+            "close-price": "180.00",
+        }))
+    }
+
+    #[test]
+    fn test_oco_exit_body_and_validation() {
+        let d = |s| Decimal::from_str(s).unwrap();
+        let long = position("Long");
+
+        let body = serde_json::to_value(oco_exit(&long, d("190"), d("170")).unwrap()).unwrap();
+        assert_eq!(body["type"], "OCO");
+        let [target, stop] = [&body["orders"][0], &body["orders"][1]];
+        assert_eq!(target["order-type"], "Limit");
+        assert_eq!(target["price-effect"], "Credit");
+        assert_eq!(target["legs"][0]["action"], "Sell to Close");
+        assert_eq!(stop["order-type"], "Stop");
+        assert_eq!(stop["stop-trigger"].to_string().trim_matches('"'), "170");
+        assert!(stop.get("price").is_none());
+
+        assert!(oco_exit(&long, d("170"), d("190")).is_err());
+        assert!(oco_exit(&position("Short"), d("170"), d("190")).is_ok());
+
+        let live: LiveOrderRecord = serde_json::from_str(
+            r#"{
+                "id": 42,
+                "account-number": "TEST123",
+                "time-in-force": "GTC",
+                "order-type": "Limit",
+                "size": 100,
+                "underlying-symbol": "AAPL",
+                "price": "200.0",
+                "price-effect": "Credit",
+                "status": "Live",
+                "cancellable": true,
+                "editable": true,
+                "edited": false,
+                "legs": [{
+                    "instrument-type": "Equity",
+                    "symbol": "AAPL",
+                    "quantity": 100,
+                    "remaining-quantity": 100,
+                    "action": "Sell to Close",
+                    "fills": []
+                }]
+            }"#,
+        )
+        .unwrap();
+        let live = [live];
+        assert_eq!(existing_exits(&long, &live).len(), 1);
+        assert!(existing_exits(&position("Short"), &live).is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Environment guard for order placement.
+//!
+//! The sandbox fills anything at any size, so a strategy tested there with oversized
+//! orders says little about production. Set
+//! [`TastyTradeConfig::environment_guard`](crate::utils::config::TastyTradeConfig::environment_guard)
+//! and [`Account::place_order`](crate::accounts::Account::place_order),
+//! [`place_complex_order`](crate::accounts::Account::place_complex_order),
+//! [`replace_order`](crate::accounts::Account::replace_order) and
+//! [`reprice_order`](crate::accounts::Account::reprice_order) check every order first:
+//!
+//! - in demo and certification, an order whose [`notional`](crate::Order::notional)
+//!   exceeds `max_demo_notional` fails with [`TastyTradeError::NotionalLimitExceeded`],
+//!   and so does an order without a notional, such as a market order, since its size
+//!   cannot be checked;
+//! - in production, every order fails with [`TastyTradeError::ProductionNotConfirmed`]
+//!   unless `confirm_production` is `true`.
+//!
+//! Without a guard configured nothing is checked.
+//!
+//! ```rust,ignore
+//! let mut config = TastyTradeConfig::from_env();
+//! config.environment_guard = Some(EnvironmentGuardConfig {
+//!     max_demo_notional: Some(dec!(10000)),
+//!     confirm_production: false,
+//! });
+//! ```
+
+use crate::api::base::TastyResult;
+use crate::utils::config::Environment;
+use crate::{TastyTrade, TastyTradeError};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Limits of the environment guard.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct EnvironmentGuardConfig {
+    /// Largest order notional accepted in demo and certification; `None` for no limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_demo_notional: Option<Decimal>,
+    /// Whether orders may be placed in production at all.
+    pub confirm_production: bool,
+}
+
+impl EnvironmentGuardConfig {
+    /// Fails when an order of `notional` may not be placed in `environment`. `None` is an
+    /// order without a price, refused whenever a notional limit applies.
+    pub fn check(&self, environment: Environment, notional: Option<Decimal>) -> TastyResult<()> {
+        if !environment.is_sandbox() {
+            if self.confirm_production {
+                return Ok(());
+            }
+            return Err(TastyTradeError::ProductionNotConfirmed);
+        }
+        match (self.max_demo_notional, notional) {
+            (Some(limit), None) => Err(TastyTradeError::NotionalLimitExceeded(format!(
+                "an order without a price cannot be checked against the {environment} limit of {limit}"
+            ))),
+            (Some(limit), Some(notional)) if notional > limit => {
+                Err(TastyTradeError::NotionalLimitExceeded(format!(
+                    "order notional {notional} is above the {environment} limit of {limit}"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl TastyTrade {
+    /// Checks an order of `notional` against the configured guard, if any.
+    pub(crate) fn check_environment_guard(&self, notional: Option<Decimal>) -> TastyResult<()> {
+        match &self.config.environment_guard {
+            Some(guard) => guard.check(self.config.environment, notional),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Order;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_environment_guard() {
+        let order = Order::sell_to_open_option(
+            "SPY   261218P00500000",
+            2,
+            Decimal::from_str("1.25").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(order.notional(), Some(Decimal::from(250)));
+
+        let guard = EnvironmentGuardConfig {
+            max_demo_notional: Some(Decimal::from(200)),
+            confirm_production: false,
+        };
+        assert!(matches!(
+            guard.check(Environment::Demo, order.notional()),
+            Err(TastyTradeError::NotionalLimitExceeded(_))
+        ));
+        assert!(
+            guard
+                .check(Environment::Certification, Some(Decimal::from(200)))
+                .is_ok()
+        );
+        assert!(matches!(
+            guard.check(Environment::Production, Some(Decimal::ONE)),
+            Err(TastyTradeError::ProductionNotConfirmed)
+        ));
+
+        // A market order has no notional to check against the limit
+        let market = Order::market_buy("SPY", 1).unwrap();
+        assert_eq!(market.notional(), None);
+        assert!(matches!(
+            guard.check(Environment::Demo, market.notional()),
+            Err(TastyTradeError::NotionalLimitExceeded(_))
+        ));
+        assert!(
+            EnvironmentGuardConfig::default()
+                .check(Environment::Demo, market.notional())
+                .is_ok()
+        );
+
+        let guard = EnvironmentGuardConfig {
+            confirm_production: true,
+            ..guard
+        };
+        assert!(
+            guard
+                .check(Environment::Production, order.notional())
+                .is_ok()
+        );
+    }
+}
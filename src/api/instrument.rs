@@ -4,12 +4,67 @@
    Date: 9/3/25
 ******************************************************************************/
 use crate::api::base::{Items, Paginated};
+use crate::api::query::{EquityOptionsQuery, FuturesQuery, PageQuery, SymbolsQuery};
 use crate::types::instrument::{
-    CompactOptionChain, CompactOptionChainResponse, Cryptocurrency, EquityInstrument,
-    EquityInstrumentInfo, EquityOption, FutureOption, FutureOptionProduct, FutureProduct,
-    FuturesNestedOptionChain, NestedOptionChain, QuantityDecimalPrecision, Warrant,
+    CompactOptionChain, CompactOptionChainResponse, Cryptocurrency, Deliverable, EquityInstrument,
+    EquityInstrumentInfo, EquityOption, ExpirationSummary, FutureOption, FutureOptionProduct,
+    FutureProduct, FuturesCompactOptionChain, FuturesNestedOptionChain, NestedOptionChain,
+    QuantityDecimalPrecision, Warrant, occ_root,
 };
-use crate::{AsSymbol, TastyResult, TastyTrade};
+use crate::{AsSymbol, Symbol, TastyResult, TastyTrade, TastyTradeError};
+use serde::{Deserialize, Serialize};
+
+/// Nested chain as returned by `/option-chains/{symbol}/nested`, keeping only what
+/// [`TastyTrade::chain_expirations`] needs. Strike arrays are skipped while parsing.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct NestedChainOutline {
+    root_symbol: Symbol,
+    expirations: Vec<ExpirationOutline>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ExpirationOutline {
+    expiration_type: String,
+    expiration_date: String,
+    days_to_expiration: u64,
+    settlement_type: String,
+}
+
+/// Nested chain as returned by `/option-chains/{symbol}/nested`, keeping only what
+/// [`TastyTrade::deliverables`] needs.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ChainDeliverables {
+    root_symbol: Symbol,
+    #[serde(default)]
+    deliverables: Vec<Deliverable>,
+}
+
+/// Flattens chain outlines into summaries ordered by date, then root symbol.
+fn expiration_summaries(chains: Vec<NestedChainOutline>) -> Vec<ExpirationSummary> {
+    let mut summaries: Vec<ExpirationSummary> = chains
+        .into_iter()
+        .flat_map(|chain| {
+            let root_symbol = chain.root_symbol;
+            chain
+                .expirations
+                .into_iter()
+                .map(move |exp| ExpirationSummary {
+                    root_symbol: root_symbol.clone(),
+                    expiration_type: exp.expiration_type,
+                    expiration_date: exp.expiration_date,
+                    days_to_expiration: exp.days_to_expiration,
+                    settlement_type: exp.settlement_type,
+                })
+        })
+        .collect();
+    summaries.sort_by(|a, b| {
+        (a.days_to_expiration, &a.root_symbol).cmp(&(b.days_to_expiration, &b.root_symbol))
+    });
+    summaries
+}
 
 impl TastyTrade {
     pub async fn get_equity_info(
@@ -24,16 +79,8 @@ impl TastyTrade {
         &self,
         symbols: &[impl AsSymbol],
     ) -> TastyResult<Vec<EquityInstrument>> {
-        let mut query = Vec::<(&str, String)>::new();
-        for symbol in symbols {
-            let symbol_str = symbol.as_symbol().0.clone();
-            query.push(("symbol[]", symbol_str));
-        }
-
-        let query_refs: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
-
         let resp: Items<EquityInstrument> = self
-            .get_with_query("/instruments/equities", &query_refs)
+            .get_with_params("/instruments/equities", &SymbolsQuery::new(symbols))
             .await?;
         Ok(resp.items)
     }
@@ -42,14 +89,11 @@ impl TastyTrade {
         &self,
         page_offset: usize,
     ) -> TastyResult<Paginated<EquityInstrument>> {
-        let page_offset_str = page_offset.to_string();
-        let query = vec![
-            ("per-page", "1000"),
-            ("page-offset", page_offset_str.as_str()),
-        ];
-
-        self.get_with_query::<Items<EquityInstrument>, _, _>("/instruments/equities/active", &query)
-            .await
+        self.get_with_params::<Items<EquityInstrument>, _, _, _>(
+            "/instruments/equities/active",
+            &PageQuery::new(1000, page_offset),
+        )
+        .await
     }
 
     pub async fn get_equity(&self, symbol: impl AsSymbol) -> TastyResult<EquityInstrument> {
@@ -77,7 +121,7 @@ impl TastyTrade {
         let url = format!("/option-chains/{}/compact", underlying_symbol.as_symbol().0);
         let full_url = format!("{}{}", self.config.base_url, url);
 
-        let response = self.client.get(&full_url).send().await?;
+        let response = self.send(self.client.get(&full_url)).await?;
         let text = response.text().await?;
 
         let parsed: CompactOptionChainResponse = serde_json::from_str(&text).map_err(|e| {
@@ -107,29 +151,87 @@ impl TastyTrade {
         Ok(resp.items)
     }
 
-    pub async fn list_equity_options(
+    /// Lists the expirations of `underlying_symbol`'s option chain without their strikes.
+    ///
+    /// Uses the nested chain endpoint but never materializes strikes, which keeps the
+    /// call cheap for large chains such as SPX.
+    pub async fn chain_expirations(
         &self,
-        symbols: &[impl AsSymbol],
-        active: Option<bool>,
-    ) -> TastyResult<Vec<EquityOption>> {
-        let mut query = Vec::new();
-
-        let mut symbol_strings = Vec::new();
+        underlying_symbol: impl AsSymbol,
+    ) -> TastyResult<Vec<ExpirationSummary>> {
+        let resp: Items<NestedChainOutline> = self
+            .get(format!(
+                "/option-chains/{}/nested",
+                underlying_symbol.as_symbol().0
+            ))
+            .await?;
+        Ok(expiration_summaries(resp.items))
+    }
 
-        for symbol in symbols {
-            symbol_strings.push(symbol.as_symbol().0.clone());
-        }
+    /// Returns what one contract of the equity option `option_symbol` delivers on
+    /// exercise.
+    ///
+    /// Normally 100 shares of the underlying, but options adjusted after a split, merger
+    /// or special dividend may deliver another number of shares, other securities or cash
+    /// in lieu. Deliverables are looked up on the option's chain, found by root symbol.
+    pub async fn deliverables(
+        &self,
+        option_symbol: impl AsSymbol,
+    ) -> TastyResult<Vec<Deliverable>> {
+        let option = self.get_equity_option(option_symbol).await?;
+        let resp: Items<ChainDeliverables> = self
+            .get(format!(
+                "/option-chains/{}/nested",
+                option.underlying_symbol.0
+            ))
+            .await?;
+        resp.items
+            .into_iter()
+            .find(|chain| chain.root_symbol == option.root_symbol)
+            .map(|chain| chain.deliverables)
+            .ok_or_else(|| {
+                TastyTradeError::Unknown(format!(
+                    "No option chain with root {} found for {}",
+                    option.root_symbol.0, option.symbol.0
+                ))
+            })
+    }
 
-        for symbol_str in &symbol_strings {
-            query.push(("symbol[]", symbol_str.as_str()));
+    /// Resolves the underlying of `symbol`.
+    ///
+    /// Equity options (OCC symbols) and future options (`./` symbols) are looked up to
+    /// read their underlying; any other symbol (equities, futures, cryptocurrencies) is
+    /// its own underlying and is returned without a request.
+    pub async fn underlying_of(&self, symbol: impl AsSymbol) -> TastyResult<Symbol> {
+        let symbol = symbol.as_symbol();
+        if symbol.0.starts_with("./") {
+            Ok(self.get_future_option(symbol).await?.underlying_symbol)
+        } else if occ_root(&symbol.0).is_some() {
+            Ok(self.get_equity_option(symbol).await?.underlying_symbol)
+        } else {
+            Ok(symbol)
         }
+    }
 
-        if let Some(active_val) = active {
-            query.push(("active", if active_val { "true" } else { "false" }));
-        }
+    pub async fn list_equity_options(
+        &self,
+        symbols: &[impl AsSymbol],
+        active: Option<bool>,
+    ) -> TastyResult<Vec<EquityOption>> {
+        let query = EquityOptionsQuery {
+            active,
+            ..EquityOptionsQuery::new(symbols)
+        };
+        self.list_equity_options_matching(&query).await
+    }
 
+    /// Equity options selected by `query`, from `/instruments/equity-options`.
+    pub async fn list_equity_options_matching(
+        &self,
+        query: &EquityOptionsQuery,
+    ) -> TastyResult<Vec<EquityOption>> {
         let resp: Items<EquityOption> = self
-            .get_with_query("/instruments/equity-options", &query)
+            .get_with_params("/instruments/equity-options", query)
             .await?;
         Ok(resp.items)
     }
@@ -143,7 +245,7 @@ impl TastyTrade {
         let url = format!("/instruments/equity-options/{}", symbol.as_symbol().0);
         let full_url = format!("{}{}", self.config.base_url, url);
 
-        let response = self.client.get(&full_url).send().await?;
+        let response = self.send(self.client.get(&full_url)).await?;
         let text = response.text().await?;
 
         let parsed: EquityOptionResponse = serde_json::from_str(&text).map_err(|e| {
@@ -164,43 +266,23 @@ impl TastyTrade {
         only_active_futures: Option<bool>,
         security_ids: Option<&[&str]>,
     ) -> TastyResult<Vec<crate::types::instrument::Future>> {
-        let mut query = Vec::new();
-
-        let mut symbol_strings = Vec::new();
-
+        let mut query = FuturesQuery::new().security_ids(security_ids.unwrap_or_default());
         if let Some(symbols) = symbols {
-            for symbol in symbols {
-                symbol_strings.push(symbol.as_symbol().0.clone());
-            }
-
-            for symbol_str in &symbol_strings {
-                query.push(("symbol[]", symbol_str.as_str()));
-            }
-        }
-
-        if let Some(code) = product_code {
-            query.push(("product-code", code));
-        }
-
-        if let Some(exchange_name) = exchange {
-            query.push(("exchange", exchange_name));
-        }
-
-        if let Some(only_active) = only_active_futures {
-            query.push((
-                "only-active-futures",
-                if only_active { "true" } else { "false" },
-            ));
-        }
-
-        if let Some(security_id_list) = security_ids {
-            for security_id in security_id_list {
-                query.push(("security-id[]", security_id));
-            }
+            query = query.symbols(symbols);
         }
+        query.product_code = product_code.map(str::to_string);
+        query.exchange = exchange.map(str::to_string);
+        query.only_active = only_active_futures;
+        self.list_futures_matching(&query).await
+    }
 
+    /// Futures selected by `query`, from `/instruments/futures`.
+    pub async fn list_futures_matching(
+        &self,
+        query: &FuturesQuery,
+    ) -> TastyResult<Vec<crate::types::instrument::Future>> {
         let resp: Items<crate::types::instrument::Future> =
-            self.get_with_query("/instruments/futures", &query).await?;
+            self.get_with_params("/instruments/futures", query).await?;
         Ok(resp.items)
     }
 
@@ -282,23 +364,26 @@ impl TastyTrade {
         Ok(vec![nested_chain])
     }
 
+    /// Fetches the compact option chains of a futures product (e.g. `"ES"`).
+    ///
+    /// Each chain lists option and streamer symbols without strike breakdown; use
+    /// [`FuturesCompactOptionChain::symbol_pairs`] to subscribe to a whole chain at once.
+    pub async fn get_futures_compact_option_chain(
+        &self,
+        product_code: &str,
+    ) -> TastyResult<Vec<FuturesCompactOptionChain>> {
+        let resp: Items<FuturesCompactOptionChain> = self
+            .get(format!("/futures-option-chains/{}/compact", product_code))
+            .await?;
+        Ok(resp.items)
+    }
+
     pub async fn list_future_options(
         &self,
         symbols: &[impl AsSymbol],
     ) -> TastyResult<Vec<FutureOption>> {
-        let mut query = Vec::new();
-        let mut symbol_strings = Vec::new();
-
-        for symbol in symbols {
-            symbol_strings.push(symbol.as_symbol().0.clone());
-        }
-
-        for symbol_str in &symbol_strings {
-            query.push(("symbol[]", symbol_str.as_str()));
-        }
-
         let resp: Items<FutureOption> = self
-            .get_with_query("/instruments/future-options", &query)
+            .get_with_params("/instruments/future-options", &SymbolsQuery::new(symbols))
             .await?;
         Ok(resp.items)
     }
@@ -318,19 +403,8 @@ impl TastyTrade {
         &self,
         symbols: &[impl AsSymbol],
     ) -> TastyResult<Vec<Cryptocurrency>> {
-        let mut query = Vec::new();
-        let mut symbol_strings = Vec::new();
-
-        for symbol in symbols {
-            symbol_strings.push(symbol.as_symbol().0.clone());
-        }
-
-        for symbol_str in &symbol_strings {
-            query.push(("symbol[]", symbol_str.as_str()));
-        }
-
         let resp: Items<Cryptocurrency> = self
-            .get_with_query("/instruments/cryptocurrencies", &query)
+            .get_with_params("/instruments/cryptocurrencies", &SymbolsQuery::new(symbols))
             .await?;
         Ok(resp.items)
     }
@@ -345,20 +419,10 @@ impl TastyTrade {
         &self,
         symbols: Option<&[impl AsSymbol]>,
     ) -> TastyResult<Vec<Warrant>> {
-        let mut query = Vec::new();
-        let mut symbol_strings = Vec::new();
-
-        if let Some(symbols) = symbols {
-            for symbol in symbols {
-                symbol_strings.push(symbol.as_symbol().0.clone());
-            }
-
-            for symbol_str in &symbol_strings {
-                query.push(("symbol[]", symbol_str.as_str()));
-            }
-        }
-
-        let resp: Items<Warrant> = self.get_with_query("/instruments/warrants", &query).await?;
+        let query = symbols.map(SymbolsQuery::new).unwrap_or_default();
+        let resp: Items<Warrant> = self
+            .get_with_params("/instruments/warrants", &query)
+            .await?;
         Ok(resp.items)
     }
 
@@ -375,3 +439,51 @@ impl TastyTrade {
         Ok(resp.items)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiration_summaries_skip_strikes_and_sort() {
+        let json = r#"[
+            {
+                "underlying-symbol": "SPX",
+                "root-symbol": "SPXW",
+                "option-chain-type": "Standard",
+                "shares-per-contract": 100,
+                "expirations": [
+                    {
+                        "expiration-type": "Weekly",
+                        "expiration-date": "2024-01-12",
+                        "days-to-expiration": 3,
+                        "settlement-type": "PM",
+                        "strikes": [{"strike-price": "4700.0", "call": "SPXW  240112C04700000", "put": "SPXW  240112P04700000"}]
+                    }
+                ]
+            },
+            {
+                "underlying-symbol": "SPX",
+                "root-symbol": "SPX",
+                "option-chain-type": "Standard",
+                "shares-per-contract": 100,
+                "expirations": [
+                    {
+                        "expiration-type": "Regular",
+                        "expiration-date": "2024-01-19",
+                        "days-to-expiration": 10,
+                        "settlement-type": "AM",
+                        "strikes": []
+                    }
+                ]
+            }
+        ]"#;
+
+        let chains: Vec<NestedChainOutline> = serde_json::from_str(json).unwrap();
+        let summaries = expiration_summaries(chains);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].root_symbol, Symbol("SPXW".to_string()));
+        assert_eq!(summaries[0].days_to_expiration, 3);
+        assert_eq!(summaries[1].settlement_type, "AM");
+    }
+}
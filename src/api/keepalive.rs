@@ -0,0 +1,137 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Background task keeping the session alive in long-running processes.
+//!
+//! Sessions are refreshed when a request finds them about to expire, so a service that
+//! sends nothing overnight wakes up to a dead token. [`TastyTrade::start_keepalive`]
+//! spawns a task that validates the session every [`KEEPALIVE_INTERVAL`] and refreshes
+//! it through the auth provider when it expires before the next check or the API
+//! rejects it. The task is shared by the client and its clones and stops with
+//! [`TastyTrade::stop_keepalive`] or [`TastyTrade::logout`].
+//!
+//! ```rust,ignore
+//! let tasty = TastyTrade::login(&config).await?;
+//! tasty.start_keepalive();
+//! // ... hours later, requests still go through
+//! tasty.stop_keepalive();
+//! ```
+
+use crate::TastyTrade;
+use crate::api::session::SESSION_REFRESH_MARGIN;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior, interval_at};
+use tracing::{debug, warn};
+
+/// How often the keep-alive task checks the session.
+pub const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// The running keep-alive task, if any.
+#[derive(Debug, Default)]
+pub(crate) struct Keepalive(Mutex<Option<JoinHandle<()>>>);
+
+impl Keepalive {
+    fn replace(&self, task: Option<JoinHandle<()>>) {
+        if let Some(previous) = std::mem::replace(&mut *self.0.lock().unwrap(), task) {
+            previous.abort();
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.0
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+    }
+}
+
+impl TastyTrade {
+    /// Starts keeping the session alive in the background. See the
+    /// [module documentation](crate::api::keepalive).
+    ///
+    /// A task already running is replaced. Must be called within a Tokio runtime.
+    pub fn start_keepalive(&self) {
+        let tasty = self.clone();
+        let task = tokio::spawn(async move {
+            let mut ticks = interval_at(Instant::now() + KEEPALIVE_INTERVAL, KEEPALIVE_INTERVAL);
+            ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                if tasty.session.is_closed() {
+                    debug!("Session closed, stopping keep-alive");
+                    return;
+                }
+                tasty.keep_session_alive().await;
+            }
+        });
+        self.keepalive.replace(Some(task));
+    }
+
+    /// Stops the keep-alive task, if one is running.
+    pub fn stop_keepalive(&self) {
+        self.keepalive.replace(None);
+    }
+
+    /// Whether a keep-alive task is running.
+    pub fn is_keepalive_running(&self) -> bool {
+        self.keepalive.is_running()
+    }
+
+    /// One keep-alive round: refreshes the session when it would expire before the next
+    /// one, otherwise validates it and refreshes it when rejected.
+    async fn keep_session_alive(&self) {
+        let margin = SESSION_REFRESH_MARGIN
+            + chrono::Duration::from_std(KEEPALIVE_INTERVAL).unwrap_or_default();
+        let expiring = self.session.expires_within(self.clock.now(), margin);
+        if !expiring {
+            match self.is_session_valid().await {
+                Ok(true) => return,
+                Ok(false) => debug!("Session rejected, refreshing it"),
+                Err(e) => {
+                    warn!("Keep-alive could not validate the session: {e}");
+                    return;
+                }
+            }
+        }
+        let token = self.session.token();
+        if let Err(e) = self
+            .session
+            .refresh(
+                self.auth.as_ref(),
+                &self.client,
+                &self.config.base_url,
+                &token,
+            )
+            .await
+        {
+            warn!("Keep-alive could not refresh the session: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TastyTrade;
+    use crate::utils::config::TastyTradeConfig;
+
+    #[tokio::test]
+    async fn test_keepalive_lifecycle() {
+        let tasty = TastyTrade::from_session_token("token", &TastyTradeConfig::default()).unwrap();
+        let clone = tasty.clone();
+        assert!(!tasty.is_keepalive_running());
+
+        tasty.start_keepalive();
+        assert!(clone.is_keepalive_running());
+        tasty.start_keepalive();
+        assert!(tasty.is_keepalive_running());
+
+        clone.stop_keepalive();
+        assert!(!tasty.is_keepalive_running());
+    }
+}
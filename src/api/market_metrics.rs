@@ -0,0 +1,131 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Market metrics and the implied volatility term structure.
+//!
+//! [`TastyTrade::market_metrics`] wraps `/market-metrics`; [`TastyTrade::iv_term_structure`]
+//! joins its per-expiration implied volatilities with the expirations of the nested option
+//! chain.
+//!
+//! ```rust,ignore
+//! let curve = tasty.iv_term_structure("SPY").await?;
+//! for point in curve.known_points() {
+//!     println!("{} {:>4}d {}", point.expiration_date, point.days_to_expiration,
+//!         point.implied_volatility.unwrap());
+//! }
+//! ```
+
+use crate::api::base::{Items, TastyResult};
+use crate::api::option_chain::Expiration;
+use crate::types::market_metrics::{MarketMetrics, TermStructure, TermStructurePoint};
+use crate::{AsSymbol, Symbol, TastyTrade, TastyTradeError};
+use chrono::NaiveDate;
+use tracing::debug;
+
+impl TastyTrade {
+    /// Market metrics of `symbols`, in one request. Unknown symbols are left out of the
+    /// result.
+    pub async fn market_metrics<S: AsSymbol>(
+        &self,
+        symbols: &[S],
+    ) -> TastyResult<Vec<MarketMetrics>> {
+        let symbols = symbols
+            .iter()
+            .map(|symbol| symbol.as_symbol().0)
+            .collect::<Vec<_>>()
+            .join(",");
+        let resp: Items<MarketMetrics> = self
+            .get_with_query("/market-metrics", &[("symbols", symbols.as_str())])
+            .await?;
+        Ok(resp.items)
+    }
+
+    /// Implied volatility by expiration of `underlying`'s option chain.
+    ///
+    /// Every expiration of the chain gets a point; those the market metrics do not
+    /// cover have no implied volatility.
+    pub async fn iv_term_structure(&self, underlying: impl AsSymbol) -> TastyResult<TermStructure> {
+        let underlying = underlying.as_symbol();
+        let (metrics, chain) = tokio::try_join!(
+            self.market_metrics(std::slice::from_ref(&underlying)),
+            self.nested_option_chain_for(underlying.clone())
+        )?;
+        let metrics = metrics
+            .into_iter()
+            .find(|m| m.symbol == underlying)
+            .ok_or_else(|| {
+                TastyTradeError::Unknown(format!("no market metrics for {}", underlying.0))
+            })?;
+        Ok(term_structure(underlying, &metrics, &chain.expirations))
+    }
+}
+
+/// Lines the implied volatilities of `metrics` up with `expirations`.
+fn term_structure(
+    underlying: Symbol,
+    metrics: &MarketMetrics,
+    expirations: &[Expiration],
+) -> TermStructure {
+    let mut points: Vec<TermStructurePoint> = expirations
+        .iter()
+        .filter_map(|expiration| {
+            let Ok(date) = NaiveDate::parse_from_str(&expiration.expiration_date, "%Y-%m-%d")
+            else {
+                debug!("Skipping expiration {}", expiration.expiration_date);
+                return None;
+            };
+            let iv = metrics.expiration_iv(date, Some(&expiration.settlement_type));
+            Some(TermStructurePoint {
+                expiration_date: date,
+                days_to_expiration: expiration.days_to_expiration,
+                expiration_type: expiration.expiration_type.clone(),
+                settlement_type: expiration.settlement_type.clone(),
+                implied_volatility: iv.and_then(|iv| iv.implied_volatility),
+            })
+        })
+        .collect();
+    points.sort_by_key(|point| point.expiration_date);
+    TermStructure {
+        underlying,
+        implied_volatility_index: metrics.implied_volatility_index,
+        historical_volatility_30_day: metrics.historical_volatility_30_day,
+        points,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::option_chain::NestedOptionChain;
+    use rust_decimal::Decimal;
+    use serde_json::Value;
+
+    fn items<T: serde::de::DeserializeOwned>(fixture: &str) -> Vec<T> {
+        let body: Value = serde_json::from_str(fixture).unwrap();
+        serde_json::from_value(body["data"]["items"].clone()).unwrap()
+    }
+
+    #[test]
+    fn test_term_structure_joins_chain_and_metrics() {
+        let metrics: Vec<MarketMetrics> =
+            items(include_str!("../../tests/fixtures/market_metrics.json"));
+        let chains: Vec<NestedOptionChain> = items(include_str!(
+            "../../tests/fixtures/option_chain_nested.json"
+        ));
+
+        let curve = term_structure(Symbol::from("SPY"), &metrics[0], &chains[0].expirations);
+        assert_eq!(curve.points.len(), chains[0].expirations.len());
+        assert_eq!(curve.points[0].days_to_expiration, 18);
+        assert_eq!(
+            curve.points[0].implied_volatility,
+            Some(Decimal::new(1181, 4))
+        );
+        assert_eq!(
+            curve.historical_volatility_30_day,
+            Some(Decimal::new(1085, 2))
+        );
+    }
+}
@@ -1,8 +1,26 @@
 pub mod accounts;
+pub mod auth;
+pub mod await_fill;
 pub mod base;
+pub mod chase;
 pub mod client;
+pub mod combined_positions;
+pub mod diagnostics;
+pub mod exits;
+pub mod guard;
 
 pub mod option_chain;
+pub mod portfolio;
 
 pub mod instrument;
+pub mod keepalive;
+pub mod market_metrics;
+pub mod query;
 pub mod quote_streaming;
+pub mod reconcile;
+pub mod risk;
+pub mod sandbox;
+pub mod session;
+pub mod stats;
+pub mod tags;
+pub mod throttle;
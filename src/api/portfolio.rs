@@ -0,0 +1,294 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! One entry point over every account of a login, for dashboards.
+//!
+//! [`TastyTrade::portfolio`] returns a [`Portfolio`] that loads balances, positions and
+//! live orders of all accounts on the first query and answers later ones from memory.
+//! [`Portfolio::refresh`] reloads them; [`Portfolio::stream_updates`] keeps them current
+//! from the account stream instead.
+//!
+//! ```rust,ignore
+//! let portfolio = tasty.portfolio();
+//! tokio::spawn({
+//!     let portfolio = portfolio.clone();
+//!     async move { portfolio.stream_updates().await }
+//! });
+//! println!("net liq {}", portfolio.total_net_liq().await?);
+//! ```
+
+use crate::accounts::AccountNumber;
+use crate::api::base::TastyResult;
+use crate::api::combined_positions::{UnderlyingPositions, group_by_underlying};
+use crate::streaming::account_streaming::{AccountEvent, AccountMessage};
+use crate::types::balance::Balance;
+use crate::types::order::Symbol;
+use crate::{FullPosition, LiveOrderRecord, TastyTrade, TastyTradeError};
+use futures_util::future::try_join_all;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::{RwLock, RwLockReadGuard};
+use tracing::debug;
+
+/// What is known of one account.
+#[derive(Debug)]
+struct AccountView {
+    balance: Balance,
+    positions: Vec<FullPosition>,
+    live_orders: Vec<LiveOrderRecord>,
+}
+
+#[derive(Debug, Default)]
+struct Holdings {
+    accounts: BTreeMap<AccountNumber, AccountView>,
+    loaded: bool,
+    /// An update could not be applied; reload before answering.
+    stale: bool,
+}
+
+/// Balances, positions and live orders of every account. See the module docs.
+///
+/// Clones share the same data.
+#[derive(Debug, Clone)]
+pub struct Portfolio {
+    tasty: TastyTrade,
+    holdings: Arc<RwLock<Holdings>>,
+}
+
+impl TastyTrade {
+    /// A [`Portfolio`] over every account of this login. Nothing is fetched until the
+    /// first query.
+    pub fn portfolio(&self) -> Portfolio {
+        Portfolio {
+            tasty: self.clone(),
+            holdings: Arc::default(),
+        }
+    }
+}
+
+impl Portfolio {
+    /// Fetches balances, positions and live orders of every account again.
+    pub async fn refresh(&self) -> TastyResult<()> {
+        let accounts = self.tasty.accounts().await?;
+        let views = try_join_all(accounts.iter().map(|account| async move {
+            let (balance, positions, live_orders) = tokio::try_join!(
+                account.balance(),
+                account.positions(),
+                account.live_orders()
+            )?;
+            let view = AccountView {
+                balance,
+                positions,
+                live_orders,
+            };
+            Ok::<_, TastyTradeError>((account.number(), view))
+        }))
+        .await?;
+        *self.holdings.write().await = Holdings {
+            accounts: views.into_iter().collect(),
+            loaded: true,
+            stale: false,
+        };
+        Ok(())
+    }
+
+    async fn holdings(&self) -> TastyResult<RwLockReadGuard<'_, Holdings>> {
+        {
+            let holdings = self.holdings.read().await;
+            if holdings.loaded && !holdings.stale {
+                return Ok(holdings);
+            }
+        }
+        self.refresh().await?;
+        Ok(self.holdings.read().await)
+    }
+
+    /// The accounts of the portfolio.
+    pub async fn account_numbers(&self) -> TastyResult<Vec<AccountNumber>> {
+        Ok(self.holdings().await?.accounts.keys().cloned().collect())
+    }
+
+    /// Net liquidating value summed over all accounts.
+    pub async fn total_net_liq(&self) -> TastyResult<Decimal> {
+        let holdings = self.holdings().await?;
+        Ok(holdings
+            .accounts
+            .values()
+            .map(|view| view.balance.net_liquidating_value)
+            .sum())
+    }
+
+    /// Maintenance requirement summed over all accounts: the capital held against the
+    /// open positions, i.e. what they can lose before margin calls start.
+    pub async fn open_risk(&self) -> TastyResult<Decimal> {
+        let holdings = self.holdings().await?;
+        Ok(holdings
+            .accounts
+            .values()
+            .map(|view| view.balance.maintenance_requirement)
+            .sum())
+    }
+
+    /// Positions of all accounts grouped by underlying, as
+    /// [`TastyTrade::combined_positions`] returns them.
+    pub async fn positions_by_underlying(
+        &self,
+    ) -> TastyResult<BTreeMap<Symbol, UnderlyingPositions>> {
+        let holdings = self.holdings().await?;
+        Ok(group_by_underlying(
+            holdings
+                .accounts
+                .values()
+                .flat_map(|view| view.positions.iter().cloned()),
+        ))
+    }
+
+    /// Working orders of all accounts.
+    pub async fn live_orders(&self) -> TastyResult<Vec<LiveOrderRecord>> {
+        let holdings = self.holdings().await?;
+        Ok(holdings
+            .accounts
+            .values()
+            .flat_map(|view| view.live_orders.iter().cloned())
+            .collect())
+    }
+
+    /// Applies a streamed account event.
+    ///
+    /// Balances and orders are replaced in place. A position update is applied to the
+    /// matching position; a new position cannot be built from the stream, so the next
+    /// query reloads everything instead. Events received before the first load are
+    /// ignored.
+    pub async fn apply(&self, event: AccountEvent) {
+        let AccountEvent::AccountMessage(message) = event else {
+            return;
+        };
+        let mut holdings = self.holdings.write().await;
+        if !holdings.loaded {
+            return;
+        }
+        let holdings = &mut *holdings;
+        match *message {
+            AccountMessage::Order(order) => {
+                if let Some(view) = holdings.accounts.get_mut(&order.account_number) {
+                    view.live_orders.retain(|o| o.id.0 != order.id.0);
+                    if order.status.is_working() {
+                        view.live_orders.push(order);
+                    }
+                }
+            }
+            AccountMessage::AccountBalance(balance) => {
+                if let Some(view) = holdings.accounts.get_mut(&balance.account_number) {
+                    view.balance = *balance;
+                }
+            }
+            AccountMessage::CurrentPosition(position) => {
+                let Some(view) = holdings.accounts.get_mut(&position.account_number) else {
+                    return;
+                };
+                match view
+                    .positions
+                    .iter_mut()
+                    .find(|p| p.symbol == position.symbol)
+                {
+                    Some(existing) => {
+                        existing.quantity = position.quantity;
+                        existing.quantity_direction = position.quantity_direction;
+                        existing.close_price = position.close_price;
+                        existing.average_open_price = position.average_open_price;
+                        view.positions.retain(|p| !p.quantity.is_zero());
+                    }
+                    None if position.quantity.is_zero() => {}
+                    None => {
+                        debug!("New position in {}, reloading portfolio", position.symbol.0);
+                        holdings.stale = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Follows the account stream of every account, applying each event, until the
+    /// stream closes. Run it on its own task.
+    ///
+    /// The portfolio is reloaded once subscribed, so no update is missed between the
+    /// snapshot and the stream.
+    pub async fn stream_updates(&self) -> TastyResult<()> {
+        let streamer = self.tasty.create_account_streamer().await?;
+        let accounts = self.tasty.accounts().await?;
+        for account in &accounts {
+            streamer.subscribe_to_account(account).await;
+        }
+        self.refresh().await?;
+        while let Ok(event) = streamer.get_event().await {
+            self.apply(event).await;
+        }
+        Err(TastyTradeError::Streaming(
+            "account stream closed".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::config::TastyTradeConfig;
+    use serde_json::Value;
+
+    fn data(fixture: &str) -> Value {
+        let body: Value = serde_json::from_str(fixture).unwrap();
+        body["data"].clone()
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_applies_stream_updates() {
+        let tasty = TastyTrade::from_session_token("token", &TastyTradeConfig::default()).unwrap();
+        let portfolio = tasty.portfolio();
+
+        let balance: Balance =
+            serde_json::from_value(data(include_str!("../../tests/fixtures/balances.json")))
+                .unwrap();
+        let positions: Vec<FullPosition> = serde_json::from_value(
+            data(include_str!("../../tests/fixtures/positions.json"))["items"].clone(),
+        )
+        .unwrap();
+        let mut orders: Vec<LiveOrderRecord> = serde_json::from_value(
+            data(include_str!("../../tests/fixtures/live_orders.json"))["items"].clone(),
+        )
+        .unwrap();
+        let filled = orders.pop().unwrap();
+        let mut live = orders.pop().unwrap();
+        *portfolio.holdings.write().await = Holdings {
+            accounts: BTreeMap::from([(
+                balance.account_number.clone(),
+                AccountView {
+                    balance,
+                    positions,
+                    live_orders: vec![live.clone()],
+                },
+            )]),
+            loaded: true,
+            stale: false,
+        };
+
+        assert_eq!(
+            portfolio.total_net_liq().await.unwrap(),
+            Decimal::new(2114725, 2)
+        );
+        assert_eq!(portfolio.positions_by_underlying().await.unwrap().len(), 2);
+        assert_eq!(portfolio.live_orders().await.unwrap().len(), 1);
+
+        live.status = filled.status;
+        portfolio
+            .apply(AccountEvent::AccountMessage(Box::new(
+                AccountMessage::Order(live),
+            )))
+            .await;
+        assert!(portfolio.live_orders().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Typed query parameters of the instrument listings.
+//!
+//! Each listing endpoint takes a query struct deriving [`Serialize`]: fields are sent
+//! in kebab-case, lists repeat their `name[]` key once per value and unset filters are
+//! left out. A new filter is a new field, so adding one does not break callers building
+//! queries through [`Default`] and the builder methods.
+//!
+//! ```rust,ignore
+//! let query = FuturesQuery::new().product_code("ES").only_active(true);
+//! let futures = tasty.list_futures_matching(&query).await?;
+//! ```
+
+use crate::api::client::FromTastyResponse;
+use crate::{AsSymbol, TastyResult, TastyTrade};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Flattens a query struct into `(key, value)` pairs ordered by key: arrays repeat their
+/// key, `null` is skipped and scalars are sent as written.
+pub(crate) fn query_pairs<Q: Serialize>(query: &Q) -> TastyResult<Vec<(String, String)>> {
+    fn scalar(value: Value) -> Option<String> {
+        match value {
+            Value::Null => None,
+            Value::String(s) => Some(s),
+            other => Some(other.to_string()),
+        }
+    }
+
+    let Value::Object(fields) = serde_json::to_value(query)? else {
+        return Ok(Vec::new());
+    };
+    let mut pairs = Vec::new();
+    for (key, value) in fields {
+        match value {
+            Value::Array(values) => pairs.extend(
+                values
+                    .into_iter()
+                    .filter_map(scalar)
+                    .map(|value| (key.clone(), value)),
+            ),
+            value => pairs.extend(scalar(value).map(|value| (key, value))),
+        }
+    }
+    Ok(pairs)
+}
+
+fn symbol_strings(symbols: &[impl AsSymbol]) -> Vec<String> {
+    symbols.iter().map(|symbol| symbol.as_symbol().0).collect()
+}
+
+impl TastyTrade {
+    /// [`get_with_query`](Self::get_with_query) with the parameters of a typed query.
+    pub(crate) async fn get_with_params<T, R, U, Q>(&self, url: U, query: &Q) -> TastyResult<R>
+    where
+        T: DeserializeOwned + Serialize + std::fmt::Debug,
+        R: FromTastyResponse<T>,
+        U: AsRef<str>,
+        Q: Serialize,
+    {
+        let pairs = query_pairs(query)?;
+        let pairs: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        self.get_with_query(url, &pairs).await
+    }
+}
+
+/// Instruments selected by symbol, for the equity, future option, cryptocurrency and
+/// warrant listings.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SymbolsQuery {
+    /// The symbols to fetch; empty for every instrument where the endpoint allows it.
+    #[serde(rename = "symbol[]", skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<String>,
+}
+
+impl SymbolsQuery {
+    /// A query for `symbols`.
+    pub fn new(symbols: &[impl AsSymbol]) -> Self {
+        Self {
+            symbols: symbol_strings(symbols),
+        }
+    }
+}
+
+/// One page of a paginated listing.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PageQuery {
+    /// Items per page; the API default when `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<usize>,
+    /// Zero-based page to fetch.
+    pub page_offset: usize,
+}
+
+impl PageQuery {
+    /// Page `page_offset` of `per_page` items.
+    pub fn new(per_page: usize, page_offset: usize) -> Self {
+        Self {
+            per_page: Some(per_page),
+            page_offset,
+        }
+    }
+}
+
+/// Filters of [`TastyTrade::list_equity_options_matching`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EquityOptionsQuery {
+    /// Option symbols to fetch.
+    #[serde(rename = "symbol[]", skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<String>,
+    /// Only active options when `true`, only expired ones when `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+}
+
+impl EquityOptionsQuery {
+    /// A query for `symbols`, active or not.
+    pub fn new(symbols: &[impl AsSymbol]) -> Self {
+        Self {
+            symbols: symbol_strings(symbols),
+            ..Self::default()
+        }
+    }
+
+    /// Only active options when `active`, only expired ones otherwise.
+    pub fn active(mut self, active: bool) -> Self {
+        self.active = Some(active);
+        self
+    }
+}
+
+/// Filters of [`TastyTrade::list_futures_matching`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FuturesQuery {
+    /// Future symbols to fetch, e.g. `/ESZ6`.
+    #[serde(rename = "symbol[]", skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<String>,
+    /// Only contracts of this product, e.g. `ES`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_code: Option<String>,
+    /// Only contracts listed on this exchange, e.g. `CME`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exchange: Option<String>,
+    /// Only contracts still trading when `true`.
+    #[serde(
+        rename = "only-active-futures",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub only_active: Option<bool>,
+    /// Contracts to fetch by exchange security id.
+    #[serde(rename = "security-id[]", skip_serializing_if = "Vec::is_empty")]
+    pub security_ids: Vec<String>,
+}
+
+impl FuturesQuery {
+    /// A query for every future.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only `symbols`.
+    pub fn symbols(mut self, symbols: &[impl AsSymbol]) -> Self {
+        self.symbols = symbol_strings(symbols);
+        self
+    }
+
+    /// Only contracts of `product_code`.
+    pub fn product_code(mut self, product_code: impl Into<String>) -> Self {
+        self.product_code = Some(product_code.into());
+        self
+    }
+
+    /// Only contracts listed on `exchange`.
+    pub fn exchange(mut self, exchange: impl Into<String>) -> Self {
+        self.exchange = Some(exchange.into());
+        self
+    }
+
+    /// Only contracts still trading when `only_active`.
+    pub fn only_active(mut self, only_active: bool) -> Self {
+        self.only_active = Some(only_active);
+        self
+    }
+
+    /// Only the contracts with one of `security_ids`.
+    pub fn security_ids(mut self, security_ids: &[&str]) -> Self {
+        self.security_ids = security_ids.iter().map(|id| id.to_string()).collect();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_pairs() {
+        let query = FuturesQuery::new()
+            .symbols(&["/ESZ6", "/ESH7"])
+            .product_code("ES")
+            .only_active(true);
+        let pairs = query_pairs(&query).unwrap();
+        let pairs: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("only-active-futures", "true"),
+                ("product-code", "ES"),
+                ("symbol[]", "/ESZ6"),
+                ("symbol[]", "/ESH7"),
+            ]
+        );
+
+        let page = query_pairs(&PageQuery::new(1000, 2)).unwrap();
+        assert_eq!(
+            page,
+            [
+                ("page-offset".to_string(), "2".to_string()),
+                ("per-page".to_string(), "1000".to_string()),
+            ]
+        );
+        assert!(query_pairs(&SymbolsQuery::default()).unwrap().is_empty());
+    }
+}
@@ -1,24 +1,40 @@
 use crate::TastyTrade;
 use crate::api::base::TastyApiResponse;
+use crate::types::dxfeed::DataQuality;
 use crate::types::instrument::InstrumentType;
-use crate::{AsSymbol, Symbol, TastyResult};
+use crate::{Symbol, TastyResult};
+use chrono::{DateTime, Utc};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::Deserialize;
 use serde::Serialize;
 use tracing::{debug, error};
 
 impl TastyTrade {
+    /// Requests a DXLink token and streamer URL from `/api-quote-tokens`.
+    ///
+    /// This is what [`create_quote_streamer`](TastyTrade::create_quote_streamer) uses
+    /// internally; it is exposed so the token can be inspected or handed to another client.
+    /// When [`TastyTradeConfig::dxlink_url`](crate::utils::config::TastyTradeConfig::dxlink_url)
+    /// is set it replaces the returned streamer URL.
+    ///
+    /// Fails with [`TastyTradeError::MarketDataNotEntitled`](crate::TastyTradeError::MarketDataNotEntitled)
+    /// when the customer may not stream quotes, so callers can tell it apart from a network failure.
     pub async fn quote_streamer_tokens(&self) -> TastyResult<QuoteStreamerTokens> {
         let url = format!("{}/api-quote-tokens", self.config.base_url);
         debug!("Requesting quote streamer tokens from: {}", url);
 
         // Hacer la solicitud HTTP directamente para poder examinar la respuesta
-        let response = self.client.get(&url).send().await?;
+        let response = self.send(self.client.get(&url)).await?;
 
         // Verificar el código de estado
         let status = response.status();
         debug!("Response status: {}", status);
 
+        if status == reqwest::StatusCode::FORBIDDEN {
+            let text = response.text().await?;
+            return Err(crate::TastyTradeError::MarketDataNotEntitled(text));
+        }
+
         if !status.is_success() {
             error!("Failed to get quote streamer tokens: HTTP {}", status);
             let text = response.text().await?;
@@ -34,7 +50,20 @@ impl TastyTrade {
         debug!("Response body: {}", text);
 
         match serde_json::from_str::<TastyApiResponse<QuoteStreamerTokens>>(&text) {
-            Ok(TastyApiResponse::Success(s)) => Ok(s.data),
+            Ok(TastyApiResponse::Success(s)) => {
+                let mut tokens = s.data;
+                if tokens.token.is_empty() {
+                    return Err(crate::TastyTradeError::MarketDataNotEntitled(format!(
+                        "no streamer token issued (level '{}')",
+                        tokens.level
+                    )));
+                }
+                if let Some(url) = &self.config.dxlink_url {
+                    debug!("Overriding DXLink URL {} with {}", tokens.streamer_url, url);
+                    tokens.streamer_url = url.clone();
+                }
+                Ok(tokens)
+            }
             Ok(TastyApiResponse::Error { error }) => Err(error.into()),
             Err(e) => {
                 error!("Failed to parse response: {}", e);
@@ -44,33 +73,43 @@ impl TastyTrade {
     }
 }
 
-#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+/// DXLink credentials returned by the `/api-quote-tokens` endpoint.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct QuoteStreamerTokens {
+    /// Token used to authenticate against the DXLink server.
     pub token: String,
+    /// Websocket URL of the DXLink server.
     #[serde(rename = "dxlink-url")]
     pub streamer_url: String,
+    /// Market data entitlement level (e.g. "api", "delayed").
     pub level: String,
+    /// When the token was issued, if reported by the API.
+    #[serde(default)]
+    pub issued_at: Option<DateTime<Utc>>,
+    /// When the token expires, if reported by the API.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
-#[derive(
-    DebugPretty, DisplaySimple, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
-)]
-#[serde(transparent)]
-pub struct DxFeedSymbol(pub String);
-
-impl AsSymbol for DxFeedSymbol {
-    fn as_symbol(&self) -> Symbol {
-        Symbol(self.0.clone())
+impl QuoteStreamerTokens {
+    /// Whether the token only grants delayed quotes.
+    pub fn is_delayed(&self) -> bool {
+        self.level.eq_ignore_ascii_case("delayed")
     }
-}
 
-impl AsSymbol for &DxFeedSymbol {
-    fn as_symbol(&self) -> Symbol {
-        Symbol(self.0.clone())
+    /// [`DataQuality`] of the quotes streamed with this token.
+    pub fn data_quality(&self) -> DataQuality {
+        if self.is_delayed() {
+            DataQuality::Delayed
+        } else {
+            DataQuality::Realtime
+        }
     }
 }
 
+pub use tastytrade_types::order::DxFeedSymbol;
+
 impl TastyTrade {
     pub async fn get_streamer_symbol(
         &self,
@@ -93,6 +132,7 @@ impl TastyTrade {
             FixedIncomeSecurity => DxFeedSymbol(symbol.0.clone()), // Handle as basic symbol
             LiquidityPool => DxFeedSymbol(symbol.0.clone()), // Handle as basic symbol
             Warrant => DxFeedSymbol(self.get_warrant(symbol).await?.symbol.0), // Convert to DxFeedSymbol
+            Unknown(_) => DxFeedSymbol(symbol.0.clone()), // Handle as basic symbol
         };
         Ok(sym)
     }
@@ -101,6 +141,7 @@ impl TastyTrade {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::AsSymbol;
     use crate::types::instrument::InstrumentType;
 
     #[test]
@@ -115,6 +156,27 @@ mod tests {
         assert_eq!(tokens.token, "abc123token");
         assert_eq!(tokens.streamer_url, "wss://streamer.example.com");
         assert_eq!(tokens.level, "delayed");
+        assert!(tokens.is_delayed());
+        assert!(tokens.issued_at.is_none());
+        assert!(tokens.expires_at.is_none());
+    }
+
+    #[test]
+    fn test_quote_streamer_tokens_with_timestamps() {
+        let json = r#"{
+            "token": "abc123token",
+            "dxlink-url": "wss://streamer.example.com",
+            "level": "api",
+            "issued-at": "2024-01-01T10:00:00Z",
+            "expires-at": "2024-01-02T10:00:00Z"
+        }"#;
+
+        let tokens: QuoteStreamerTokens = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            tokens.expires_at.unwrap() - tokens.issued_at.unwrap(),
+            chrono::Duration::hours(24)
+        );
+        assert!(!tokens.is_delayed());
     }
 
     #[test]
@@ -123,6 +185,8 @@ mod tests {
             token: "test_token".to_string(),
             streamer_url: "wss://test.com".to_string(),
             level: "realtime".to_string(),
+            issued_at: None,
+            expires_at: None,
         };
 
         let debug_str = format!("{:?}", tokens);
@@ -0,0 +1,274 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Reconciliation of streamed portfolio state against REST snapshots.
+//!
+//! Account streaming can silently miss messages (reconnects, dropped frames), and a
+//! bot that trusts its [`PortfolioState`] for days accumulates the error. Running
+//! [`Account::reconcile`] on a timer bounds that drift: it fetches fresh positions and
+//! balances, reports every difference as a [`Drift`], and can overwrite the local
+//! state with the server's view.
+//!
+//! ```rust,ignore
+//! let options = ReconcileOptions { auto_correct: true, ..Default::default() };
+//! let report = account.reconcile(&mut state, &options).await?;
+//! for drift in &report.drifts {
+//!     warn!("{}", drift);
+//! }
+//! ```
+
+use crate::FullPosition;
+use crate::accounts::Account;
+use crate::api::base::TastyResult;
+use crate::streaming::portfolio_state::{PortfolioState, signed_quantity};
+use crate::types::balance::Balance;
+use crate::types::order::Symbol;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A single difference between local state and the server.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Drift {
+    /// The server holds a position the local state does not know about (likely a missed fill).
+    MissingPosition { symbol: Symbol, remote: Decimal },
+    /// The local state holds a position the server no longer reports.
+    StalePosition { symbol: Symbol, local: Decimal },
+    /// Both sides hold the symbol but with different signed quantities.
+    QuantityMismatch {
+        symbol: Symbol,
+        local: Decimal,
+        remote: Decimal,
+    },
+    /// A balance figure differs by more than the configured tolerance.
+    BalanceMismatch {
+        field: &'static str,
+        local: Decimal,
+        remote: Decimal,
+    },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::MissingPosition { symbol, remote } => {
+                write!(f, "{}: missing locally, server holds {}", symbol.0, remote)
+            }
+            Drift::StalePosition { symbol, local } => {
+                write!(
+                    f,
+                    "{}: stale, held {} locally but flat on server",
+                    symbol.0, local
+                )
+            }
+            Drift::QuantityMismatch {
+                symbol,
+                local,
+                remote,
+            } => write!(f, "{}: local {} vs server {}", symbol.0, local, remote),
+            Drift::BalanceMismatch {
+                field,
+                local,
+                remote,
+            } => write!(f, "{}: local {} vs server {}", field, local, remote),
+        }
+    }
+}
+
+/// Settings for [`Account::reconcile`].
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileOptions {
+    /// Largest balance difference that is not reported as drift.
+    pub balance_tolerance: Decimal,
+    /// Overwrite the local state with the server snapshot when drift is found.
+    pub auto_correct: bool,
+}
+
+/// Result of a reconciliation run.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReconcileReport {
+    /// Differences found, positions first (sorted by symbol), then balances.
+    pub drifts: Vec<Drift>,
+    /// Whether the local state was overwritten with the server snapshot.
+    pub corrected: bool,
+}
+
+impl ReconcileReport {
+    /// Returns `true` when local state matched the server.
+    pub fn is_clean(&self) -> bool {
+        self.drifts.is_empty()
+    }
+}
+
+/// Compares local state with a REST snapshot without touching either.
+///
+/// Balance fields are only compared when both the local value and `balance` are known.
+pub fn diff(
+    state: &PortfolioState,
+    positions: &[FullPosition],
+    balance: Option<&Balance>,
+    balance_tolerance: Decimal,
+) -> Vec<Drift> {
+    let mut remote: BTreeMap<&Symbol, Decimal> = BTreeMap::new();
+    for position in positions {
+        let qty = signed_quantity(position.quantity, position.quantity_direction);
+        if !qty.is_zero() {
+            *remote.entry(&position.symbol).or_default() += qty;
+        }
+    }
+    remote.retain(|_, qty| !qty.is_zero());
+    let local: BTreeMap<&Symbol, Decimal> = state
+        .positions
+        .iter()
+        .filter(|(_, qty)| !qty.is_zero())
+        .map(|(symbol, qty)| (symbol, *qty))
+        .collect();
+
+    let mut drifts = Vec::new();
+    for (symbol, &remote_qty) in &remote {
+        match local.get(symbol) {
+            None => drifts.push(Drift::MissingPosition {
+                symbol: (*symbol).clone(),
+                remote: remote_qty,
+            }),
+            Some(&local_qty) if local_qty != remote_qty => drifts.push(Drift::QuantityMismatch {
+                symbol: (*symbol).clone(),
+                local: local_qty,
+                remote: remote_qty,
+            }),
+            Some(_) => {}
+        }
+    }
+    for (symbol, &local_qty) in &local {
+        if !remote.contains_key(symbol) {
+            drifts.push(Drift::StalePosition {
+                symbol: (*symbol).clone(),
+                local: local_qty,
+            });
+        }
+    }
+    drifts.sort_by(|a, b| drift_symbol(a).cmp(&drift_symbol(b)));
+
+    if let Some(balance) = balance {
+        let fields = [
+            ("cash_balance", state.cash_balance, balance.cash_balance),
+            (
+                "net_liquidating_value",
+                state.net_liquidating_value,
+                balance.net_liquidating_value,
+            ),
+        ];
+        for (field, local, remote) in fields {
+            match local {
+                Some(local) if (local - remote).abs() > balance_tolerance => {
+                    drifts.push(Drift::BalanceMismatch {
+                        field,
+                        local,
+                        remote,
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+    drifts
+}
+
+fn drift_symbol(drift: &Drift) -> Option<&Symbol> {
+    match drift {
+        Drift::MissingPosition { symbol, .. }
+        | Drift::StalePosition { symbol, .. }
+        | Drift::QuantityMismatch { symbol, .. } => Some(symbol),
+        Drift::BalanceMismatch { .. } => None,
+    }
+}
+
+impl Account<'_> {
+    /// Fetches positions and balances and compares them with `state`.
+    ///
+    /// With [`ReconcileOptions::auto_correct`] set, `state` is reset to the server
+    /// snapshot whenever drift is found.
+    pub async fn reconcile(
+        &self,
+        state: &mut PortfolioState,
+        options: &ReconcileOptions,
+    ) -> TastyResult<ReconcileReport> {
+        let positions = self.positions().await?;
+        let balance = self.balance().await?;
+
+        let drifts = diff(state, &positions, Some(&balance), options.balance_tolerance);
+        let corrected = options.auto_correct && !drifts.is_empty();
+        if corrected {
+            state.reset(&positions, Some(&balance));
+        }
+        Ok(ReconcileReport { drifts, corrected })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(symbol: &str, quantity: &str, direction: &str) -> FullPosition {
+        crate::test_fixtures::position(serde_json::json!({
+            "symbol": symbol,
+            "underlying-symbol": symbol,
+            "quantity": quantity,
+            "quantity-direction": direction,
+        }))
+    }
+
+    #[test]
+    fn test_diff_reports_position_drift() {
+        let mut state = PortfolioState::new();
+        state
+            .positions
+            .insert(Symbol("AAPL".into()), Decimal::from(100));
+        state
+            .positions
+            .insert(Symbol("MSFT".into()), Decimal::from(-10));
+        state
+            .positions
+            .insert(Symbol("TSLA".into()), Decimal::from(5));
+
+        let remote = vec![
+            position("AAPL", "100", "Long"),
+            position("MSFT", "20", "Short"),
+            position("SPY", "1", "Long"),
+        ];
+
+        let drifts = diff(&state, &remote, None, Decimal::ZERO);
+        assert_eq!(
+            drifts,
+            vec![
+                Drift::QuantityMismatch {
+                    symbol: Symbol("MSFT".into()),
+                    local: Decimal::from(-10),
+                    remote: Decimal::from(-20),
+                },
+                Drift::MissingPosition {
+                    symbol: Symbol("SPY".into()),
+                    remote: Decimal::from(1),
+                },
+                Drift::StalePosition {
+                    symbol: Symbol("TSLA".into()),
+                    local: Decimal::from(5),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reset_clears_drift() {
+        let remote = vec![position("AAPL", "100", "Long")];
+        let mut state = PortfolioState::new();
+        assert_eq!(diff(&state, &remote, None, Decimal::ZERO).len(), 1);
+
+        state.reset(&remote, None);
+        assert!(diff(&state, &remote, None, Decimal::ZERO).is_empty());
+    }
+}
@@ -0,0 +1,288 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Scenario stress tests of an account's positions.
+//!
+//! A [`Scenario`] moves every underlying by a percentage and implied volatility by a
+//! number of points. [`StressTest::run`] reprices the positions under each scenario from
+//! their greeks (delta, gamma and vega, times quantity and multiplier) and projects the
+//! account's net liquidating value and maintenance excess. Stocks and futures move
+//! one-for-one with their price. The maintenance requirement is held constant: the
+//! margin impact of a scenario is its P&L eating into the excess.
+//!
+//! Greeks come from a [`LiveGreeksPositions`](crate::streaming::greeks_positions::LiveGreeksPositions)
+//! snapshot; underlying prices from the caller, or the close price of a stock or future
+//! position in the same underlying.
+//!
+//! ```rust,ignore
+//! let mut streamer = tasty.create_quote_streamer().await?;
+//! let live = account.live_greeks_positions(&mut streamer).await?;
+//! // ... wait for greeks to arrive
+//! let spots = HashMap::from([(Symbol::from("SPY"), 580.0)]);
+//! let report = account.stress_test(&live.snapshot(), &spots, &Scenario::standard()).await?;
+//! println!("worst case {:.2}", report.value_at_risk());
+//! ```
+
+use crate::accounts::Account;
+use crate::streaming::greeks_positions::{GreeksPosition, is_option};
+use crate::streaming::portfolio_state::signed_quantity;
+use crate::types::balance::Balance;
+use crate::{Symbol, TastyResult};
+use rust_decimal::prelude::ToPrimitive;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A market shock applied to every position.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Scenario {
+    /// Label shown in reports, e.g. `"-10%"`.
+    pub name: String,
+    /// Relative move of every underlying, e.g. `-0.10` for a 10% drop.
+    pub price_change: f64,
+    /// Change of implied volatility in points, e.g. `5.0` for +5 vol.
+    pub vol_change: f64,
+}
+
+impl Scenario {
+    /// A scenario named `name` moving prices by `price_change` and volatility by
+    /// `vol_change` points.
+    pub fn new(name: impl Into<String>, price_change: f64, vol_change: f64) -> Self {
+        Self {
+            name: name.into(),
+            price_change,
+            vol_change,
+        }
+    }
+
+    /// Moves of ±5%, ±10% and a crash of -20% with volatility up 15 points.
+    pub fn standard() -> Vec<Self> {
+        vec![
+            Self::new("-10%", -0.10, 5.0),
+            Self::new("-5%", -0.05, 2.0),
+            Self::new("+5%", 0.05, -2.0),
+            Self::new("+10%", 0.10, -3.0),
+            Self::new("crash", -0.20, 15.0),
+        ]
+    }
+}
+
+/// P&L of one position under a scenario.
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionImpact {
+    /// The position's symbol.
+    pub symbol: Symbol,
+    /// Its underlying.
+    pub underlying_symbol: Symbol,
+    /// Projected P&L.
+    pub pnl: f64,
+}
+
+/// Outcome of one scenario.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScenarioResult {
+    /// The scenario applied.
+    pub scenario: Scenario,
+    /// Projected P&L over all priced positions.
+    pub pnl: f64,
+    /// Projected P&L per position.
+    pub positions: Vec<PositionImpact>,
+    /// Net liquidating value after the scenario.
+    pub net_liquidating_value: f64,
+    /// Net liquidating value left above the maintenance requirement after the scenario.
+    pub maintenance_excess: f64,
+}
+
+impl ScenarioResult {
+    /// Whether the scenario would leave the account below its maintenance requirement.
+    pub fn is_margin_call(&self) -> bool {
+        self.maintenance_excess < 0.0
+    }
+}
+
+/// Scenario results for a set of positions. See the module docs.
+#[derive(Debug, Clone, Serialize)]
+pub struct StressTest {
+    /// One result per scenario, in the order given.
+    pub results: Vec<ScenarioResult>,
+    /// Positions left out: options without greeks yet or with a missing (NaN) delta,
+    /// gamma or vega, and positions whose underlying price is unknown.
+    pub unpriced: Vec<Symbol>,
+}
+
+impl StressTest {
+    /// Applies `scenarios` to `positions` of the account whose balance is `balance`.
+    ///
+    /// `spots` gives underlying prices by underlying symbol; missing ones fall back to
+    /// the close price of a stock or future position in that underlying.
+    pub fn run(
+        positions: &[GreeksPosition],
+        spots: &HashMap<Symbol, f64>,
+        balance: &Balance,
+        scenarios: &[Scenario],
+    ) -> Self {
+        let mut spots = spots.clone();
+        for entry in positions {
+            let position = &entry.position;
+            if !is_option(&position.instrument_type) {
+                let close = position.close_price.to_f64().unwrap_or_default();
+                spots
+                    .entry(position.underlying_symbol.clone())
+                    .or_insert(close);
+            }
+        }
+
+        let mut priced = Vec::new();
+        let mut unpriced = Vec::new();
+        for entry in positions {
+            let spot = spots.get(&entry.position.underlying_symbol).copied();
+            let greeks = if is_option(&entry.position.instrument_type) {
+                entry
+                    .greeks
+                    .as_ref()
+                    .and_then(|g| Some((g.delta()?, g.gamma()?, g.vega()?)))
+            } else {
+                Some((1.0, 0.0, 0.0))
+            };
+            match (spot, greeks) {
+                (Some(spot), Some(greeks)) => priced.push((entry, spot, greeks)),
+                _ => unpriced.push(entry.position.symbol.clone()),
+            }
+        }
+
+        let net_liq = balance.net_liquidating_value.to_f64().unwrap_or_default();
+        let requirement = balance.maintenance_requirement.to_f64().unwrap_or_default();
+        let results = scenarios
+            .iter()
+            .map(|scenario| {
+                let positions: Vec<PositionImpact> = priced
+                    .iter()
+                    .map(|(entry, spot, (delta, gamma, vega))| {
+                        let move_ = spot * scenario.price_change;
+                        let per_contract = delta * move_
+                            + 0.5 * gamma * move_ * move_
+                            + vega * scenario.vol_change;
+                        PositionImpact {
+                            symbol: entry.position.symbol.clone(),
+                            underlying_symbol: entry.position.underlying_symbol.clone(),
+                            pnl: per_contract * exposure(entry),
+                        }
+                    })
+                    .collect();
+                let pnl = positions.iter().map(|p| p.pnl).sum::<f64>();
+                ScenarioResult {
+                    scenario: scenario.clone(),
+                    pnl,
+                    positions,
+                    net_liquidating_value: net_liq + pnl,
+                    maintenance_excess: net_liq + pnl - requirement,
+                }
+            })
+            .collect();
+        Self { results, unpriced }
+    }
+
+    /// The scenario with the largest loss.
+    pub fn worst(&self) -> Option<&ScenarioResult> {
+        self.results.iter().min_by(|a, b| a.pnl.total_cmp(&b.pnl))
+    }
+
+    /// Largest loss over the scenarios, as a positive amount; zero when none loses.
+    pub fn value_at_risk(&self) -> f64 {
+        self.worst().map_or(0.0, |worst| (-worst.pnl).max(0.0))
+    }
+}
+
+/// Signed quantity times multiplier.
+fn exposure(entry: &GreeksPosition) -> f64 {
+    let position = &entry.position;
+    (signed_quantity(position.quantity, position.quantity_direction) * position.multiplier)
+        .to_f64()
+        .unwrap_or_default()
+}
+
+impl Account<'_> {
+    /// Runs `scenarios` over `positions` against the account's current balance. See
+    /// [`StressTest::run`].
+    pub async fn stress_test(
+        &self,
+        positions: &[GreeksPosition],
+        spots: &HashMap<Symbol, f64>,
+        scenarios: &[Scenario],
+    ) -> TastyResult<StressTest> {
+        let balance = self.balance().await?;
+        Ok(StressTest::run(positions, spots, &balance, scenarios))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FullPosition;
+    use crate::api::quote_streaming::DxFeedSymbol;
+    use crate::dxfeed::DxfGreeksT;
+    use crate::types::instrument::InstrumentType;
+    use serde_json::Value;
+
+    fn data(fixture: &str) -> Value {
+        let body: Value = serde_json::from_str(fixture).unwrap();
+        body["data"].clone()
+    }
+
+    #[test]
+    fn test_stress_test_projects_pnl_and_excess() {
+        let balance: Balance =
+            serde_json::from_value(data(include_str!("../../tests/fixtures/balances.json")))
+                .unwrap();
+        let mut position: FullPosition = serde_json::from_value(
+            data(include_str!("../../tests/fixtures/positions.json"))["items"][0].clone(),
+        )
+        .unwrap();
+        position.instrument_type = InstrumentType::EquityOption;
+        position.quantity = 1.into();
+        position.quantity_direction = crate::QuantityDirection::Long;
+        position.multiplier = 100.into();
+        let underlying = position.underlying_symbol.clone();
+        let option = GreeksPosition {
+            position,
+            streamer_symbol: DxFeedSymbol("OPT".to_string()),
+            greeks: Some(DxfGreeksT {
+                event_flags: 0,
+                index: 0,
+                time: 0,
+                price: 2.0,
+                volatility: 0.2,
+                delta: 0.5,
+                gamma: 0.1,
+                theta: -0.02,
+                rho: 0.0,
+                vega: 0.1,
+            }),
+        };
+
+        let spots = HashMap::from([(underlying, 100.0)]);
+        let scenarios = [Scenario::new("-10%", -0.10, 5.0)];
+        let report = StressTest::run(&[option.clone()], &spots, &balance, &scenarios);
+        // (0.5 × -10 + 0.5 × 0.1 × 100 + 0.1 × 5) × 100
+        let result = &report.results[0];
+        assert!((result.pnl - 50.0).abs() < 1e-9);
+        let net_liq = balance.net_liquidating_value.to_f64().unwrap();
+        assert!((result.net_liquidating_value - net_liq - 50.0).abs() < 1e-9);
+        assert_eq!(report.value_at_risk(), 0.0);
+
+        let report = StressTest::run(&[option.clone()], &HashMap::new(), &balance, &scenarios);
+        assert!(report.results[0].positions.is_empty());
+        assert_eq!(report.unpriced.len(), 1);
+
+        // A NaN greek leaves the position out instead of poisoning every scenario
+        let mut illiquid = option;
+        illiquid.greeks.as_mut().unwrap().gamma = f64::NAN;
+        let report = StressTest::run(&[illiquid], &spots, &balance, &scenarios);
+        assert!(report.results[0].positions.is_empty());
+        assert_eq!(report.results[0].pnl, 0.0);
+        assert_eq!(report.unpriced.len(), 1);
+        assert_eq!(report.value_at_risk(), 0.0);
+    }
+}
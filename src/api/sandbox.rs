@@ -0,0 +1,158 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Known starting state for integration tests against the sandbox.
+//!
+//! [`TastyTrade::sandbox`] only succeeds on a client configured for the demo or
+//! certification environment, so seeding code cannot run against a live account by
+//! mistake. [`Sandbox::prepare_account`] then picks a tradable account and tops its
+//! cash up with a simulated deposit, where the sandbox supports one.
+//!
+//! ```rust,ignore
+//! let tasty = TastyTrade::login(&TastyTradeConfig::from_env()).await?;
+//! let sandbox = tasty.sandbox()?;
+//! let account = sandbox.prepare_account(dec!(10000)).await?;
+//! ```
+
+use crate::accounts::Account;
+use crate::api::base::TastyResult;
+use crate::types::balance::Balance;
+use crate::utils::config::{Environment, TastyTradeConfig};
+use crate::{TastyTrade, TastyTradeError};
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use serde_json::json;
+use tracing::{debug, info};
+
+/// Seeding helpers for a client connected to the sandbox. See the module docs.
+#[derive(Debug, Clone, Copy)]
+pub struct Sandbox<'t> {
+    tasty: &'t TastyTrade,
+}
+
+/// Returns `true` when `config` targets a non-production environment and its REST
+/// URL is not the production one.
+fn is_sandbox(config: &TastyTradeConfig) -> bool {
+    (config.use_demo || config.environment.is_sandbox())
+        && config.base_url != Environment::Production.base_url()
+}
+
+impl TastyTrade {
+    /// The [`Sandbox`] helpers, or a configuration error when this client does not
+    /// target the sandbox.
+    pub fn sandbox(&self) -> TastyResult<Sandbox<'_>> {
+        if !is_sandbox(&self.config) {
+            return Err(TastyTradeError::ConfigError(format!(
+                "sandbox helpers need the demo environment, not {} ({})",
+                self.config.environment, self.config.base_url
+            )));
+        }
+        Ok(Sandbox { tasty: self })
+    }
+}
+
+impl<'t> Sandbox<'t> {
+    /// Credits `amount` of simulated cash to `account`.
+    ///
+    /// Returns `false` without changing anything when the sandbox does not offer
+    /// simulated deposits (the endpoint answers `404 Not Found`); other failures are
+    /// errors.
+    pub async fn deposit(&self, account: &Account<'_>, amount: Decimal) -> TastyResult<bool> {
+        let url = format!(
+            "{}/accounts/{}/sandbox/deposits",
+            self.tasty.config.base_url,
+            account.number().0
+        );
+        let body = json!({ "amount": amount.round_dp(2).to_string() });
+        let response = self
+            .tasty
+            .send(self.tasty.client.post(&url).body(body.to_string()))
+            .await?;
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND {
+            debug!("Simulated deposits are not available on {url}");
+            return Ok(false);
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(TastyTradeError::Unknown(format!(
+                "HTTP {} for simulated deposit: {}",
+                status.as_u16(),
+                body
+            )));
+        }
+        info!(
+            "Deposited {amount} to sandbox account {}",
+            account.number().0
+        );
+        Ok(true)
+    }
+
+    /// Makes sure `account` holds at least `min_cash`, depositing the difference when
+    /// needed, and returns its balance afterwards.
+    ///
+    /// Fails when the account is short and the sandbox cannot deposit.
+    pub async fn ensure_cash(
+        &self,
+        account: &Account<'_>,
+        min_cash: Decimal,
+    ) -> TastyResult<Balance> {
+        let balance = account.balance().await?;
+        let missing = min_cash - balance.cash_balance;
+        if missing <= Decimal::ZERO {
+            return Ok(balance);
+        }
+        if !self.deposit(account, missing).await? {
+            return Err(TastyTradeError::Unknown(format!(
+                "sandbox account {} holds {} cash, {} needed, and simulated deposits are not available",
+                account.number().0,
+                balance.cash_balance,
+                min_cash
+            )));
+        }
+        account.balance().await
+    }
+
+    /// The first account orders can be placed on, holding at least `min_cash`.
+    pub async fn prepare_account(&self, min_cash: Decimal) -> TastyResult<Account<'t>> {
+        let account = self
+            .tasty
+            .tradable_accounts()
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                TastyTradeError::Unknown("no tradable account in the sandbox".to_string())
+            })?;
+        self.ensure_cash(&account, min_cash).await?;
+        Ok(account)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_requires_demo_environment() {
+        let production = TastyTradeConfig::default();
+        let tasty = TastyTrade::from_session_token("token", &production).unwrap();
+        assert!(matches!(
+            tasty.sandbox(),
+            Err(TastyTradeError::ConfigError(_))
+        ));
+
+        let demo = TastyTradeConfig {
+            use_demo: true,
+            environment: Environment::Demo,
+            base_url: Environment::Demo.base_url().to_string(),
+            websocket_url: Environment::Demo.websocket_url().to_string(),
+            ..TastyTradeConfig::default()
+        };
+        let tasty = TastyTrade::from_session_token("token", &demo).unwrap();
+        assert!(tasty.sandbox().is_ok());
+    }
+}
@@ -0,0 +1,287 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Lifecycle of the session token.
+//!
+//! A [`TastyTrade`](crate::TastyTrade) and its clones share one [`SessionManager`].
+//! Before each request the token is refreshed through the
+//! [`AuthProvider`] when it expires within [`SESSION_REFRESH_MARGIN`], and a request
+//! rejected with `401 Unauthorized` is retried once with a fresh token. Concurrent
+//! requests that hit an expired token trigger a single refresh between them.
+//!
+//! Every replacement of the session is reported to the callbacks registered with
+//! [`TastyTrade::on_session_refreshed`](crate::TastyTrade::on_session_refreshed), e.g.
+//! to persist the new token for the next process.
+//!
+//! Once [`TastyTrade::logout`](crate::TastyTrade::logout) closed the session, every
+//! request fails with [`TastyTradeError::LoggedOut`] instead of reaching the API.
+
+use crate::TastyTradeError;
+use crate::api::auth::{AuthProvider, AuthSession};
+use crate::api::base::TastyResult;
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use tracing::debug;
+
+/// How long before its expiration a session is refreshed.
+pub const SESSION_REFRESH_MARGIN: chrono::Duration = chrono::Duration::seconds(60);
+
+/// Called with each new session after a refresh.
+pub type SessionCallback = Arc<dyn Fn(&AuthSession) + Send + Sync>;
+
+#[derive(Default)]
+struct Listeners(RwLock<Vec<SessionCallback>>);
+
+impl fmt::Debug for Listeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} listeners", self.0.read().unwrap().len())
+    }
+}
+
+#[derive(Debug)]
+struct CurrentSession {
+    token: String,
+    expires_at: Option<DateTime<Utc>>,
+    remember_token: Option<String>,
+    streamer_url: Option<String>,
+}
+
+/// The session token in use, shared by a client and its clones.
+#[derive(Debug)]
+pub struct SessionManager {
+    current: RwLock<CurrentSession>,
+    refresh: tokio::sync::Mutex<()>,
+    closed: AtomicBool,
+    listeners: Listeners,
+}
+
+impl SessionManager {
+    /// Starts with `session`.
+    pub(crate) fn new(session: AuthSession) -> Self {
+        Self {
+            current: RwLock::new(CurrentSession {
+                token: session.session_token,
+                expires_at: session.expires_at,
+                remember_token: session.remember_token,
+                streamer_url: session.streamer_url,
+            }),
+            refresh: tokio::sync::Mutex::new(()),
+            closed: AtomicBool::new(false),
+            listeners: Listeners::default(),
+        }
+    }
+
+    /// Returns `true` once the session was logged out.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Refuses every later request and refresh.
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    /// Fails with [`TastyTradeError::LoggedOut`] once the session is closed.
+    pub(crate) fn ensure_open(&self) -> TastyResult<()> {
+        if self.is_closed() {
+            return Err(TastyTradeError::LoggedOut);
+        }
+        Ok(())
+    }
+
+    /// The token sent with the next request.
+    pub(crate) fn token(&self) -> String {
+        self.current.read().unwrap().token.clone()
+    }
+
+    /// When the current session expires, when known.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.current.read().unwrap().expires_at
+    }
+
+    /// The latest remember token issued with a session.
+    pub(crate) fn remember_token(&self) -> Option<String> {
+        self.current.read().unwrap().remember_token.clone()
+    }
+
+    /// The account streamer URL reported with the latest session that reported one.
+    pub fn streamer_url(&self) -> Option<String> {
+        self.current.read().unwrap().streamer_url.clone()
+    }
+
+    /// Returns `true` when the session expires within `margin` of `now`. Sessions of
+    /// unknown expiration are never considered expiring.
+    pub fn expires_within(&self, now: DateTime<Utc>, margin: chrono::Duration) -> bool {
+        self.expires_at()
+            .is_some_and(|expires_at| expires_at - margin <= now)
+    }
+
+    /// Calls `callback` with every later session.
+    pub(crate) fn on_refreshed(&self, callback: SessionCallback) {
+        self.listeners.0.write().unwrap().push(callback);
+    }
+
+    /// Uses `session` from now on, then reports it to the listeners. A remember token or
+    /// streamer URL received earlier is kept when `session` comes without one.
+    pub(crate) fn replace(&self, session: AuthSession) {
+        {
+            let mut current = self.current.write().unwrap();
+            current.token = session.session_token.clone();
+            current.expires_at = session.expires_at;
+            if session.remember_token.is_some() {
+                current.remember_token = session.remember_token.clone();
+            }
+            if session.streamer_url.is_some() {
+                current.streamer_url = session.streamer_url.clone();
+            }
+        }
+        let listeners = self.listeners.0.read().unwrap().clone();
+        for listener in listeners {
+            listener(&session);
+        }
+    }
+
+    /// Replaces the session `stale_token` with a new one from `auth`, returning the token
+    /// to use.
+    ///
+    /// When another task already replaced `stale_token` while this one waited, its token
+    /// is returned without asking `auth` again. A provider handing back `stale_token`
+    /// itself, such as [`StaticTokenAuth`](crate::api::auth::StaticTokenAuth), has no
+    /// fresh session to give: that fails with [`TastyTradeError::Auth`] and the listeners
+    /// are not called, so a rejected request is not replayed with the same token.
+    pub(crate) async fn refresh(
+        &self,
+        auth: &dyn AuthProvider,
+        http: &reqwest::Client,
+        base_url: &str,
+        stale_token: &str,
+    ) -> TastyResult<String> {
+        let _guard = self.refresh.lock().await;
+        self.ensure_open()?;
+        let token = self.token();
+        if token != stale_token {
+            return Ok(token);
+        }
+        debug!("Refreshing session");
+        let session = auth.acquire(http, base_url).await?;
+        if session.session_token == stale_token {
+            return Err(TastyTradeError::Auth(
+                "the auth provider returned the session being replaced".to_string(),
+            ));
+        }
+        let token = session.session_token.clone();
+        self.replace(session);
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::auth::StaticTokenAuth;
+    use futures_util::future::BoxFuture;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, Default)]
+    struct CountingAuth {
+        calls: AtomicU32,
+    }
+
+    impl AuthProvider for CountingAuth {
+        fn acquire<'a>(
+            &'a self,
+            _http: &'a reqwest::Client,
+            _base_url: &'a str,
+        ) -> BoxFuture<'a, TastyResult<AuthSession>> {
+            Box::pin(async move {
+                let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+                tokio::task::yield_now().await;
+                Ok(AuthSession::new(format!("token-{n}")))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_refreshes_acquire_once() {
+        let now = Utc::now();
+        let session = Arc::new(SessionManager::new(AuthSession {
+            session_token: "token-0".to_string(),
+            remember_token: None,
+            expires_at: Some(now + chrono::Duration::seconds(30)),
+            streamer_url: Some("wss://streamer.example.com".to_string()),
+        }));
+        assert!(session.expires_within(now, SESSION_REFRESH_MARGIN));
+        assert!(!session.expires_within(now, chrono::Duration::seconds(10)));
+
+        let refreshed = Arc::new(RwLock::new(Vec::new()));
+        let seen = refreshed.clone();
+        session.on_refreshed(Arc::new(move |s: &AuthSession| {
+            seen.write().unwrap().push(s.session_token.clone())
+        }));
+
+        let auth = Arc::new(CountingAuth::default());
+        let http = reqwest::Client::new();
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let (session, auth, http) = (session.clone(), auth.clone(), http.clone());
+                tokio::spawn(async move {
+                    session
+                        .refresh(auth.as_ref(), &http, "https://example.com", "token-0")
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), "token-1");
+        }
+        assert_eq!(auth.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(session.expires_at(), None);
+        assert_eq!(
+            session.streamer_url().as_deref(),
+            Some("wss://streamer.example.com")
+        );
+        assert_eq!(*refreshed.read().unwrap(), ["token-1"]);
+
+        session.close();
+        assert!(matches!(
+            session.ensure_open(),
+            Err(TastyTradeError::LoggedOut)
+        ));
+        assert!(
+            session
+                .refresh(auth.as_ref(), &http, "https://example.com", "token-1")
+                .await
+                .is_err()
+        );
+        assert_eq!(auth.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_refuses_the_stale_session() {
+        let session = SessionManager::new(AuthSession::new("session-xyz"));
+        let refreshed = Arc::new(RwLock::new(Vec::new()));
+        let seen = refreshed.clone();
+        session.on_refreshed(Arc::new(move |s: &AuthSession| {
+            seen.write().unwrap().push(s.session_token.clone())
+        }));
+
+        let auth = StaticTokenAuth::new("session-xyz");
+        let result = session
+            .refresh(
+                &auth,
+                &reqwest::Client::new(),
+                "https://example.com",
+                "session-xyz",
+            )
+            .await;
+        assert!(matches!(result, Err(TastyTradeError::Auth(_))));
+        assert_eq!(session.token(), "session-xyz");
+        assert!(refreshed.read().unwrap().is_empty());
+    }
+}
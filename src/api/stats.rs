@@ -0,0 +1,134 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Request statistics of a session.
+//!
+//! Every HTTP call made by [`TastyTrade`](crate::TastyTrade) updates its
+//! [`ClientStats`]: latency, status and, when the API sends them, the rate-limit
+//! headers. Schedulers can read them to slow down before the budget runs out.
+//!
+//! ```rust,ignore
+//! account.positions().await?;
+//! if tasty.stats().rate_limit_remaining().is_some_and(|left| left < 10) {
+//!     tokio::time::sleep(Duration::from_secs(1)).await;
+//! }
+//! ```
+
+use reqwest::header::HeaderMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Header names checked, in order, for each rate-limit value.
+const LIMIT_HEADERS: [&str; 2] = ["x-ratelimit-limit", "ratelimit-limit"];
+const REMAINING_HEADERS: [&str; 2] = ["x-ratelimit-remaining", "ratelimit-remaining"];
+const RESET_HEADERS: [&str; 2] = ["x-ratelimit-reset", "ratelimit-reset"];
+
+#[derive(Debug, Default)]
+struct StatsState {
+    requests: u64,
+    last_status: Option<u16>,
+    last_latency: Option<Duration>,
+    rate_limit: Option<u64>,
+    rate_limit_remaining: Option<u64>,
+    rate_limit_reset: Option<u64>,
+}
+
+/// Statistics shared by a session and its clones.
+#[derive(Debug, Default)]
+pub struct ClientStats {
+    state: Mutex<StatsState>,
+}
+
+impl ClientStats {
+    /// Number of HTTP requests that got a response.
+    pub fn request_count(&self) -> u64 {
+        self.state.lock().unwrap().requests
+    }
+
+    /// HTTP status of the last response.
+    pub fn last_status(&self) -> Option<u16> {
+        self.state.lock().unwrap().last_status
+    }
+
+    /// Time between sending the last request and receiving its response headers.
+    pub fn last_request_latency(&self) -> Option<Duration> {
+        self.state.lock().unwrap().last_latency
+    }
+
+    /// Requests allowed per rate-limit window, as last reported by the API.
+    pub fn rate_limit(&self) -> Option<u64> {
+        self.state.lock().unwrap().rate_limit
+    }
+
+    /// Requests left in the current rate-limit window, as last reported by the API.
+    ///
+    /// `None` until a response carries the header; the API does not send it everywhere.
+    pub fn rate_limit_remaining(&self) -> Option<u64> {
+        self.state.lock().unwrap().rate_limit_remaining
+    }
+
+    /// The reset value of the rate-limit window as last reported, unparsed beyond the
+    /// integer: depending on the endpoint it is seconds left or a Unix timestamp.
+    pub fn rate_limit_reset(&self) -> Option<u64> {
+        self.state.lock().unwrap().rate_limit_reset
+    }
+
+    /// Records one response. Rate-limit values are kept from earlier responses when
+    /// this one does not carry them.
+    pub(crate) fn record(&self, status: u16, headers: &HeaderMap, latency: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.requests += 1;
+        state.last_status = Some(status);
+        state.last_latency = Some(latency);
+        if let Some(limit) = header_u64(headers, &LIMIT_HEADERS) {
+            state.rate_limit = Some(limit);
+        }
+        if let Some(remaining) = header_u64(headers, &REMAINING_HEADERS) {
+            state.rate_limit_remaining = Some(remaining);
+        }
+        if let Some(reset) = header_u64(headers, &RESET_HEADERS) {
+            state.rate_limit_reset = Some(reset);
+        }
+    }
+}
+
+fn header_u64(headers: &HeaderMap, names: &[&str]) -> Option<u64> {
+    names
+        .iter()
+        .filter_map(|name| headers.get(*name))
+        .find_map(|value| value.to_str().ok()?.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_stats_track_rate_limit_headers() {
+        let stats = ClientStats::default();
+        assert_eq!(stats.rate_limit_remaining(), None);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("120"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("118"));
+        headers.insert("ratelimit-reset", HeaderValue::from_static("42"));
+        stats.record(200, &headers, Duration::from_millis(85));
+
+        assert_eq!(stats.rate_limit(), Some(120));
+        assert_eq!(stats.rate_limit_remaining(), Some(118));
+        assert_eq!(stats.rate_limit_reset(), Some(42));
+        assert_eq!(
+            stats.last_request_latency(),
+            Some(Duration::from_millis(85))
+        );
+
+        stats.record(404, &HeaderMap::new(), Duration::from_millis(30));
+        assert_eq!(stats.request_count(), 2);
+        assert_eq!(stats.last_status(), Some(404));
+        assert_eq!(stats.rate_limit_remaining(), Some(118));
+    }
+}
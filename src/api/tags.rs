@@ -0,0 +1,230 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Local tags for orders.
+//!
+//! The API has no field to label an order with the strategy or signal behind it. A
+//! [`TagLog`] keeps that link on disk instead: [`Account::place_tagged_order`] places
+//! the order and appends its id and [`OrderTags`] to an append-only JSON-lines file.
+//! Anything that carries an order id (live orders, fills, transactions) can later be
+//! joined back onto its tags to attribute P&L per strategy.
+//!
+//! ```rust,ignore
+//! let mut log = TagLog::open("order-tags.jsonl")?;
+//! let tags = OrderTags::strategy("wheel").with_signal("spy-put-2024-06-03");
+//! account.place_tagged_order(&order, tags, &mut log).await?;
+//!
+//! let orders = account.live_orders().await?;
+//! for (order, tags) in log.join(&orders, |o| Some(o.id.0)) {
+//!     println!("{} {:?}", order.id.0, tags.and_then(|t| t.strategy.as_deref()));
+//! }
+//! ```
+
+use crate::accounts::Account;
+use crate::api::base::{TastyResult, WriteResponse};
+use crate::types::order::{Order, OrderPlacedResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Free-form labels attached to an order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct OrderTags {
+    /// The strategy that placed the order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+    /// The signal that triggered the order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal_id: Option<String>,
+    /// Any other key-value metadata.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, String>,
+}
+
+impl OrderTags {
+    /// Tags naming only the strategy.
+    pub fn strategy(strategy: impl Into<String>) -> Self {
+        Self {
+            strategy: Some(strategy.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Adds the signal id.
+    pub fn with_signal(mut self, signal_id: impl Into<String>) -> Self {
+        self.signal_id = Some(signal_id.into());
+        self
+    }
+
+    /// Adds a key-value pair, replacing an earlier value of `key`.
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// One line of the tag log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TagRecord {
+    /// The id of the tagged order.
+    pub order_id: u64,
+    /// The account the order was placed on.
+    pub account_number: String,
+    /// The tags.
+    pub tags: OrderTags,
+    /// When the order was tagged.
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Append-only file of [`TagRecord`]s, indexed by order id.
+#[derive(Debug)]
+pub struct TagLog {
+    path: PathBuf,
+    by_order: HashMap<u64, OrderTags>,
+}
+
+impl TagLog {
+    /// Opens the log at `path`, loading every record already in it. The file is created
+    /// on the first [`record`](Self::record).
+    ///
+    /// Unreadable lines are skipped with a warning so one bad write does not lose the
+    /// rest of the history. A later record for the same order wins.
+    pub fn open(path: impl AsRef<Path>) -> TastyResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut by_order = HashMap::new();
+        for (n, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<TagRecord>(line) {
+                Ok(record) => {
+                    by_order.insert(record.order_id, record.tags);
+                }
+                Err(e) => warn!("Skipping line {} of {}: {}", n + 1, path.display(), e),
+            }
+        }
+        Ok(Self { path, by_order })
+    }
+
+    /// Appends `record` to the file and indexes it.
+    pub fn record(&mut self, record: TagRecord) -> TastyResult<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+        self.by_order.insert(record.order_id, record.tags);
+        Ok(())
+    }
+
+    /// The tags of order `order_id`, if it was tagged.
+    pub fn tags_for(&self, order_id: u64) -> Option<&OrderTags> {
+        self.by_order.get(&order_id)
+    }
+
+    /// Pairs each item with the tags of its order. `order_id` extracts the order id of
+    /// an item, e.g. from a fill or a transaction; items without one get `None`.
+    pub fn join<'a, T>(
+        &self,
+        items: &'a [T],
+        order_id: impl Fn(&T) -> Option<u64>,
+    ) -> Vec<(&'a T, Option<&OrderTags>)> {
+        items
+            .iter()
+            .map(|item| (item, order_id(item).and_then(|id| self.tags_for(id))))
+            .collect()
+    }
+
+    /// Groups items by the strategy of their order. Untagged items, and items whose
+    /// tags name no strategy, are grouped under `None`.
+    pub fn by_strategy<'a, T>(
+        &self,
+        items: &'a [T],
+        order_id: impl Fn(&T) -> Option<u64>,
+    ) -> BTreeMap<Option<String>, Vec<&'a T>> {
+        let mut groups: BTreeMap<Option<String>, Vec<&'a T>> = BTreeMap::new();
+        for (item, tags) in self.join(items, order_id) {
+            let strategy = tags.and_then(|t| t.strategy.clone());
+            groups.entry(strategy).or_default().push(item);
+        }
+        groups
+    }
+}
+
+impl Account<'_> {
+    /// Places `order` and records `tags` for it in `log`.
+    ///
+    /// The tags are written only once the API has accepted the order. A failure to write
+    /// them is returned as an error even though the order is live: check the account
+    /// before placing it again.
+    pub async fn place_tagged_order(
+        &self,
+        order: &Order,
+        tags: OrderTags,
+        log: &mut TagLog,
+    ) -> TastyResult<WriteResponse<OrderPlacedResult>> {
+        let placed = self.place_order(order).await?;
+        log.record(TagRecord {
+            order_id: placed.order.id.0,
+            account_number: self.inner.account.account_number.0.clone(),
+            tags,
+            recorded_at: self.tasty.clock.now(),
+        })?;
+        Ok(placed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_log_persists_and_joins() {
+        let path =
+            std::env::temp_dir().join(format!("tastytrade-tags-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let record = |order_id, tags| TagRecord {
+            order_id,
+            account_number: "5WT00001".to_string(),
+            tags,
+            recorded_at: Utc::now(),
+        };
+
+        let mut log = TagLog::open(&path).unwrap();
+        log.record(record(1, OrderTags::strategy("wheel").with_signal("s-1")))
+            .unwrap();
+        log.record(record(2, OrderTags::strategy("scalp").with("desk", "b")))
+            .unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{truncated\n")
+            .unwrap();
+
+        let log = TagLog::open(&path).unwrap();
+        assert_eq!(log.tags_for(1).unwrap().signal_id.as_deref(), Some("s-1"));
+        assert_eq!(log.tags_for(2).unwrap().extra["desk"], "b");
+
+        let fills = [(1, "fill-a"), (3, "fill-b"), (1, "fill-c")];
+        let groups = log.by_strategy(&fills, |(id, _)| Some(*id));
+        assert_eq!(groups[&Some("wheel".to_string())].len(), 2);
+        assert_eq!(groups[&None].len(), 1);
+        fs::remove_file(&path).unwrap();
+    }
+}
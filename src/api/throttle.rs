@@ -0,0 +1,389 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Burst protection for order placement and cancellation.
+//!
+//! Every [`TastyTrade`](crate::TastyTrade) session owns an [`OrderThrottle`] built from
+//! [`TastyTradeConfig::order_throttle`](crate::utils::config::TastyTradeConfig::order_throttle).
+//! [`Account::place_order`](crate::accounts::Account::place_order) and
+//! [`Account::cancel_order`](crate::accounts::Account::cancel_order) consult it before
+//! sending anything, so a strategy stuck in a loop gets a
+//! [`TastyTradeError::Throttled`] instead of flooding the account with orders.
+//!
+//! Every limit is opt-in: the default configuration is
+//! [`OrderThrottleConfig::unlimited`].
+//!
+//! The same throttle can also watch for duplicate submissions: an order with the same
+//! legs, price and time-in-force as one sent to the same endpoint a moment ago is
+//! either logged or rejected with [`TastyTradeError::PossibleDuplicateOrder`],
+//! depending on [`DuplicateOrderPolicy`]. Placements, complex orders and replacements
+//! are all checked; a replacement only duplicates one of the same order.
+//!
+//! An order is claimed before it is sent and its claim is only dropped when the API
+//! rejects it. A request that times out or whose response cannot be decoded may still
+//! have placed the order, so retrying it within the window counts as a duplicate.
+
+use crate::TastyTradeError;
+use crate::api::base::TastyResult;
+use crate::utils::clock::{SharedClock, system_clock};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// What to do when an order looks like a duplicate of a recent one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicateOrderPolicy {
+    /// No duplicate detection.
+    #[default]
+    Off,
+    /// Log a warning and send the order anyway.
+    Warn,
+    /// Reject the order with [`TastyTradeError::PossibleDuplicateOrder`].
+    Block,
+}
+
+/// Limits applied to mutating order calls. None apply by default; set the ones wanted,
+/// e.g. `OrderThrottleConfig { max_orders_per_minute: 60, ..Default::default() }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct OrderThrottleConfig {
+    /// Maximum number of orders placed in any rolling 60 second window. `0` disables the limit.
+    pub max_orders_per_minute: u32,
+    /// Minimum time, in milliseconds, between two cancel requests for the same order.
+    pub min_cancel_interval_ms: u64,
+    /// How identical orders placed in quick succession are handled.
+    pub duplicate_policy: DuplicateOrderPolicy,
+    /// Window, in milliseconds, within which an identical order counts as a duplicate.
+    pub duplicate_window_ms: u64,
+}
+
+impl Default for OrderThrottleConfig {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+impl OrderThrottleConfig {
+    /// A configuration that never throttles, the default. Only the duplicate window is
+    /// set, so turning on a [`DuplicateOrderPolicy`] alone is enough.
+    pub fn unlimited() -> Self {
+        Self {
+            max_orders_per_minute: 0,
+            min_cancel_interval_ms: 0,
+            duplicate_policy: DuplicateOrderPolicy::Off,
+            duplicate_window_ms: 10_000,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ThrottleState {
+    placements: VecDeque<Instant>,
+    cancels: HashMap<u64, Instant>,
+    recent_orders: HashMap<String, Instant>,
+}
+
+/// Shared limiter state for one session.
+#[derive(Debug)]
+pub struct OrderThrottle {
+    config: OrderThrottleConfig,
+    state: Mutex<ThrottleState>,
+    clock: SharedClock,
+}
+
+impl OrderThrottle {
+    /// Creates a limiter with the given limits.
+    pub fn new(config: OrderThrottleConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(ThrottleState::default()),
+            clock: system_clock(),
+        }
+    }
+
+    /// Measures the rolling windows with `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The limits this throttle enforces.
+    pub fn config(&self) -> &OrderThrottleConfig {
+        &self.config
+    }
+
+    /// Records a placement, or fails if the per-minute budget is exhausted.
+    pub fn check_placement(&self) -> TastyResult<()> {
+        self.check_placement_at(self.clock.instant())
+    }
+
+    /// Records a cancel for `order_id`, or fails if the same order was cancelled too recently.
+    pub fn check_cancel(&self, order_id: u64) -> TastyResult<()> {
+        self.check_cancel_at(order_id, self.clock.instant())
+    }
+
+    /// Claims `order` for a write to `path`, failing or warning when an identical order
+    /// was claimed for the same path within the window.
+    ///
+    /// The check and the claim happen under one lock, so of two identical orders sent
+    /// at once only the first gets through. Does nothing unless a
+    /// [`DuplicateOrderPolicy`] other than `Off` is configured.
+    pub fn claim_order(&self, path: &str, order: &impl Serialize) -> TastyResult<()> {
+        self.claim_at(&Self::fingerprint(path, order), self.clock.instant())
+    }
+
+    /// Drops the claim of `order` on `path`, e.g. when it was not sent after all.
+    pub fn release_order(&self, path: &str, order: &impl Serialize) {
+        if self.config.duplicate_policy == DuplicateOrderPolicy::Off {
+            return;
+        }
+        let key = Self::fingerprint(path, order);
+        self.state.lock().unwrap().recent_orders.remove(&key);
+    }
+
+    /// Drops the claim of `order` on `path` when `result` is a rejection by the API.
+    ///
+    /// Any other failure keeps the claim: after a timeout or an undecodable response
+    /// the order may well have been placed.
+    pub fn settle_order<T>(&self, path: &str, order: &impl Serialize, result: &TastyResult<T>) {
+        if let Err(TastyTradeError::Api(_)) = result {
+            self.release_order(path, order);
+        }
+    }
+
+    /// Orders are compared on their serialized form, which covers legs, price,
+    /// price effect, order type and time-in-force. The path tells placements on
+    /// different accounts, and replacements of different orders, apart.
+    fn fingerprint(path: &str, order: &impl Serialize) -> String {
+        format!(
+            "{}:{}",
+            path,
+            serde_json::to_string(order).unwrap_or_default()
+        )
+    }
+
+    fn claim_at(&self, key: &str, now: Instant) -> TastyResult<()> {
+        let policy = self.config.duplicate_policy;
+        if policy == DuplicateOrderPolicy::Off {
+            return Ok(());
+        }
+        let window = Duration::from_millis(self.config.duplicate_window_ms);
+        let mut state = self.state.lock().unwrap();
+        state
+            .recent_orders
+            .retain(|_, placed| now.duration_since(*placed) < window);
+        if let Some(placed) = state.recent_orders.get(key) {
+            let message = format!(
+                "an identical order was sent {} ms ago (window {} ms)",
+                now.duration_since(*placed).as_millis(),
+                window.as_millis()
+            );
+            if policy == DuplicateOrderPolicy::Block {
+                return Err(TastyTradeError::PossibleDuplicateOrder(message));
+            }
+            warn!("Possible duplicate order: {}", message);
+        }
+        state.recent_orders.insert(key.to_string(), now);
+        Ok(())
+    }
+
+    fn check_placement_at(&self, now: Instant) -> TastyResult<()> {
+        let limit = self.config.max_orders_per_minute as usize;
+        if limit == 0 {
+            return Ok(());
+        }
+        let mut state = self.state.lock().unwrap();
+        while let Some(&oldest) = state.placements.front() {
+            if now.duration_since(oldest) >= WINDOW {
+                state.placements.pop_front();
+            } else {
+                break;
+            }
+        }
+        if state.placements.len() >= limit {
+            let retry_in = WINDOW - now.duration_since(state.placements[0]);
+            return Err(TastyTradeError::Throttled(format!(
+                "order placement limit of {} per minute reached, retry in {} ms",
+                limit,
+                retry_in.as_millis()
+            )));
+        }
+        state.placements.push_back(now);
+        Ok(())
+    }
+
+    fn check_cancel_at(&self, order_id: u64, now: Instant) -> TastyResult<()> {
+        let min_interval = Duration::from_millis(self.config.min_cancel_interval_ms);
+        if min_interval.is_zero() {
+            return Ok(());
+        }
+        let mut state = self.state.lock().unwrap();
+        state
+            .cancels
+            .retain(|_, last| now.duration_since(*last) < min_interval);
+        if let Some(last) = state.cancels.get(&order_id) {
+            let retry_in = min_interval - now.duration_since(*last);
+            return Err(TastyTradeError::Throttled(format!(
+                "order {} was cancelled less than {} ms ago, retry in {} ms",
+                order_id,
+                min_interval.as_millis(),
+                retry_in.as_millis()
+            )));
+        }
+        state.cancels.insert(order_id, now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+    use crate::{ApiError, Order};
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_placement_limit_uses_rolling_window() {
+        let throttle = OrderThrottle::new(OrderThrottleConfig {
+            max_orders_per_minute: 2,
+            ..OrderThrottleConfig::unlimited()
+        });
+        let start = Instant::now();
+
+        assert!(throttle.check_placement_at(start).is_ok());
+        assert!(
+            throttle
+                .check_placement_at(start + Duration::from_secs(1))
+                .is_ok()
+        );
+        let err = throttle
+            .check_placement_at(start + Duration::from_secs(2))
+            .unwrap_err();
+        assert!(matches!(err, TastyTradeError::Throttled(_)));
+
+        assert!(throttle.check_placement_at(start + WINDOW).is_ok());
+    }
+
+    #[test]
+    fn test_cancel_interval_is_per_order() {
+        let throttle = OrderThrottle::new(OrderThrottleConfig {
+            min_cancel_interval_ms: 500,
+            ..OrderThrottleConfig::unlimited()
+        });
+        let start = Instant::now();
+
+        assert!(throttle.check_cancel_at(1, start).is_ok());
+        assert!(throttle.check_cancel_at(2, start).is_ok());
+        assert!(
+            throttle
+                .check_cancel_at(1, start + Duration::from_millis(100))
+                .is_err()
+        );
+        assert!(
+            throttle
+                .check_cancel_at(1, start + Duration::from_millis(600))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_duplicate_detection_within_window() {
+        let throttle = OrderThrottle::new(OrderThrottleConfig {
+            duplicate_policy: DuplicateOrderPolicy::Block,
+            duplicate_window_ms: 1_000,
+            ..OrderThrottleConfig::unlimited()
+        });
+        let start = Instant::now();
+        assert!(throttle.claim_at("ACC1:order", start).is_ok());
+
+        let err = throttle
+            .claim_at("ACC1:order", start + Duration::from_millis(200))
+            .unwrap_err();
+        assert!(matches!(err, TastyTradeError::PossibleDuplicateOrder(_)));
+        assert!(
+            throttle
+                .claim_at("ACC2:order", start + Duration::from_millis(200))
+                .is_ok()
+        );
+        assert!(
+            throttle
+                .claim_at("ACC1:order", start + Duration::from_millis(1_500))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_duplicate_claim_is_kept_unless_the_api_rejects() {
+        let throttle = OrderThrottle::new(OrderThrottleConfig {
+            duplicate_policy: DuplicateOrderPolicy::Block,
+            duplicate_window_ms: 10_000,
+            ..OrderThrottleConfig::unlimited()
+        });
+        let order = Order::limit_buy("AAPL", 10, Decimal::from(150)).unwrap();
+        let path = "/accounts/ACC1/orders";
+
+        throttle.claim_order(path, &order).unwrap();
+        let timed_out: TastyResult<()> = Err(TastyTradeError::Connection("timeout".into()));
+        throttle.settle_order(path, &order, &timed_out);
+        assert!(throttle.claim_order(path, &order).is_err());
+
+        let rejected: TastyResult<()> = Err(TastyTradeError::Api(ApiError {
+            code: Some("invalid_price".to_string()),
+            message: "rejected".to_string(),
+            errors: None,
+        }));
+        throttle.settle_order(path, &order, &rejected);
+        assert!(throttle.claim_order(path, &order).is_ok());
+        assert!(
+            throttle
+                .claim_order("/accounts/ACC2/orders", &order)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_placement_window_follows_injected_clock() {
+        let clock = MockClock::new(Utc::now());
+        let throttle = OrderThrottle::new(OrderThrottleConfig {
+            max_orders_per_minute: 1,
+            ..OrderThrottleConfig::unlimited()
+        })
+        .with_clock(Arc::new(clock.clone()));
+
+        assert!(throttle.check_placement().is_ok());
+        assert!(throttle.check_placement().is_err());
+        clock.advance(WINDOW);
+        assert!(throttle.check_placement().is_ok());
+    }
+
+    #[test]
+    fn test_default_is_unlimited() {
+        assert_eq!(
+            OrderThrottleConfig::default(),
+            OrderThrottleConfig::unlimited()
+        );
+        let config: OrderThrottleConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.max_orders_per_minute, 0);
+        assert_eq!(config.min_cancel_interval_ms, 0);
+    }
+
+    #[test]
+    fn test_unlimited_never_throttles() {
+        let throttle = OrderThrottle::new(OrderThrottleConfig::unlimited());
+        let now = Instant::now();
+        for _ in 0..1_000 {
+            assert!(throttle.check_placement_at(now).is_ok());
+            assert!(throttle.check_cancel_at(7, now).is_ok());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::types::validation::OrderValidationError;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
@@ -67,6 +68,24 @@ pub enum TastyTradeError {
     Unknown(String),
     /// Represents an error within the client configuration. This variant contains a `String` describing the configuration error.
     ConfigError(String),
+    /// Represents a mutating call rejected locally by the order throttle before reaching the API. This variant contains a `String` describing which limit was hit.
+    Throttled(String),
+    /// Represents an order rejected locally because an identical order was placed on the same account moments earlier. This variant contains a `String` describing the match.
+    PossibleDuplicateOrder(String),
+    /// Represents a quote streaming request refused because the customer has no market data entitlement. This variant contains a `String` with the API's explanation.
+    MarketDataNotEntitled(String),
+    /// Represents an order rejected locally because it is inconsistent, either in itself or with the account's positions and working orders. This variant contains a `String` describing the problem.
+    InvalidOrder(String),
+    /// Represents an order refused locally by an [`OrderValidator`](crate::types::validation::OrderValidator). This variant contains every problem found.
+    OrderValidation(Vec<OrderValidationError>),
+    /// Represents an order refused locally by the environment guard because its notional is above the limit configured for the sandbox, or because it has no notional to check, as a market order. This variant contains a `String` with the notional and the limit.
+    NotionalLimitExceeded(String),
+    /// Represents an order refused locally by the environment guard because production orders were not confirmed with `confirm_production`.
+    ProductionNotConfirmed,
+    /// Represents an order refused locally because the customer's authority over the account does not allow trading, e.g. a read-only account. This variant contains a `String` with the account and its authority level.
+    TradingNotPermitted(String),
+    /// Represents a request made after [`TastyTrade::logout`](crate::TastyTrade::logout) destroyed the session.
+    LoggedOut,
 }
 
 impl Display for TastyTradeError {
@@ -83,6 +102,29 @@ impl Display for TastyTradeError {
             TastyTradeError::Streaming(msg) => write!(f, "Streaming error: {}", msg),
             TastyTradeError::Unknown(msg) => write!(f, "Unknown error: {}", msg),
             TastyTradeError::ConfigError(msg) => write!(f, "Configuration error: {}", msg),
+            TastyTradeError::Throttled(msg) => write!(f, "Throttled: {}", msg),
+            TastyTradeError::PossibleDuplicateOrder(msg) => {
+                write!(f, "Possible duplicate order: {}", msg)
+            }
+            TastyTradeError::MarketDataNotEntitled(msg) => {
+                write!(f, "Market data not entitled: {}", msg)
+            }
+            TastyTradeError::InvalidOrder(msg) => write!(f, "Invalid order: {}", msg),
+            TastyTradeError::OrderValidation(errors) => {
+                let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                write!(f, "Order validation failed: {}", errors.join("; "))
+            }
+            TastyTradeError::NotionalLimitExceeded(msg) => {
+                write!(f, "Notional limit exceeded: {}", msg)
+            }
+            TastyTradeError::ProductionNotConfirmed => write!(
+                f,
+                "Production order refused: set confirm_production to place orders in production"
+            ),
+            TastyTradeError::TradingNotPermitted(msg) => {
+                write!(f, "Trading not permitted: {}", msg)
+            }
+            TastyTradeError::LoggedOut => write!(f, "Session logged out"),
         }
     }
 }
@@ -134,6 +176,15 @@ impl Error for TastyTradeError {
             Self::Streaming(_) => None,
             Self::Unknown(_) => None,
             Self::ConfigError(_) => None,
+            Self::Throttled(_) => None,
+            Self::PossibleDuplicateOrder(_) => None,
+            Self::MarketDataNotEntitled(_) => None,
+            Self::InvalidOrder(_) => None,
+            Self::OrderValidation(_) => None,
+            Self::NotionalLimitExceeded(_) => None,
+            Self::ProductionNotConfirmed => None,
+            Self::TradingNotPermitted(_) => None,
+            Self::LoggedOut => None,
         }
     }
 }
@@ -173,6 +224,16 @@ impl From<ApiError> for TastyTradeError {
     }
 }
 
+impl From<Vec<OrderValidationError>> for TastyTradeError {
+    /// Converts the problems found by an
+    /// [`OrderValidator`](crate::types::validation::OrderValidator) into a
+    /// `TastyTradeError::OrderValidation`, so `validator.validate(&order)?` works in
+    /// functions returning a `TastyResult`.
+    fn from(errors: Vec<OrderValidationError>) -> Self {
+        Self::OrderValidation(errors)
+    }
+}
+
 impl From<reqwest::Error> for TastyTradeError {
     /// Converts a `reqwest::Error` into a `TastyTradeError`.
     ///
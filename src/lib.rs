@@ -10,6 +10,10 @@
 //! - Account and positions information
 //! - Order management (placing, modifying, canceling)
 //! - Real-time account streaming for balance updates and order status changes
+//! - Data model (orders, positions, balances, instruments) usable on its own via the
+//!   `tastytrade-types` crate, without the HTTP and streaming dependencies
+//! - Optional `schemars` feature deriving JSON Schemas for the payload types
+//! - Optional `keyring` feature keeping credentials in the operating system keychain
 //!
 //! ## Usage
 //!
@@ -61,7 +65,7 @@
 //!     quote_sub.add_symbols(&[Symbol("AAPL".to_string())]);
 //!
 //!     // Listen for events
-//!     if let Ok(dxfeed::Event { sym, data }) = quote_sub.get_event().await {
+//!     if let Ok(dxfeed::Event { sym, data, .. }) = quote_sub.get_event().await {
 //!         match data {
 //!             dxfeed::EventData::Quote(quote) => {
 //!                 println!("Quote for {}: {}/{}", sym, quote.bid_price, quote.ask_price);
@@ -178,6 +182,9 @@ mod error;
 pub mod streaming;
 mod types;
 
+#[cfg(test)]
+mod test_fixtures;
+
 pub mod prelude;
 pub mod utils;
 
@@ -186,6 +193,7 @@ pub use api::base::TastyResult;
 pub use api::client::TastyTrade;
 
 pub use error::{ApiError, DxFeedError, TastyTradeError};
+pub use types::calendar;
 pub use types::dxfeed;
 pub use types::instrument::InstrumentType;
 pub use types::order::{
@@ -193,3 +201,4 @@ pub use types::order::{
 };
 pub use types::order::{AsSymbol, LiveOrderRecord, Symbol};
 pub use types::position::{BriefPosition, FullPosition, QuantityDirection};
+pub use types::sizing;
@@ -22,34 +22,74 @@
 pub use crate::api::client::TastyTrade;
 
 // Re-export result types
-pub use crate::api::base::TastyResult;
+pub use crate::api::base::{
+    Items, Paginated, Pagination, ResponseContext, TastyResult, WithContext, WriteResponse,
+};
 
 // Re-export error types
 pub use crate::error::{ApiError, DxFeedError, TastyTradeError};
 
 // Re-export account types
-pub use crate::api::accounts::{Account, AccountDetails, AccountInner, AccountNumber};
+pub use crate::api::accounts::{
+    Account, AccountDetails, AccountInner, AccountNumber, AuthorityLevel, CANCEL_ALL_CONCURRENCY,
+    LiveOrderQuery, OrderHistoryQuery,
+};
+
+// Re-export customer types
+pub use crate::types::customer::{Address, Customer, CustomerEntity, CustomerSuitability};
 
 // Re-export order types
 pub use crate::types::order::{
-    Action, AsSymbol, LiveOrderRecord, Order, OrderBuilder, OrderId, OrderLeg, OrderLegBuilder,
-    OrderPlacedResult, OrderStatus, OrderType, PriceEffect, Symbol, TimeInForce,
+    Action, AsSymbol, BuyingPowerEffect, BuyingPowerEffectDetail, ComplexOrder,
+    ComplexOrderPlacedResult, ComplexOrderType, DryRunResult, LiveComplexOrder, LiveOrderLeg,
+    LiveOrderRecord, Order, OrderBuilder, OrderId, OrderLeg, OrderLegBuilder, OrderPlacedResult,
+    OrderStatus, OrderType, PriceEffect, Symbol, TimeInForce,
 };
 
 // Re-export position types
-pub use crate::types::position::{BriefPosition, FullPosition, QuantityDirection};
+pub use crate::types::position::{
+    AdjustedPosition, BriefPosition, FullPosition, PositionsSummary, QuantityDirection,
+};
 
 // Re-export balance types
-pub use crate::types::balance::{Balance, BalanceSnapshot, SnapshotTimeOfDay};
+pub use crate::types::balance::{
+    Balance, BalanceSnapshot, NetLiqHistoryPoint, SnapshotTimeOfDay, TimeBack,
+};
 
 // Re-export instrument types
 pub use crate::types::instrument::{
-    Cryptocurrency, DestinationVenueSymbol, EquityInstrument, EquityInstrumentInfo, EquityOption,
-    Expiration, Future, FutureOption, FutureOptionProduct, FutureProduct, FutureRoll,
-    InstrumentType, NestedOptionChain, QuantityDecimalPrecision, Strike, SymbolEntry, TickSize,
-    Warrant,
+    ChainSummary, Cryptocurrency, Deliverable, DeliverableType, DestinationVenueSymbol,
+    EquityInstrument, EquityInstrumentInfo, EquityOption, Expiration, ExpirationSummary,
+    FUTURE_MONTH_CODES, Future, FutureOption, FutureOptionProduct, FutureProduct, FutureRoll,
+    FuturesCompactOptionChain, InstrumentType, NestedOptionChain, QuantityDecimalPrecision, Strike,
+    SymbolEntry, TickSize, Warrant, month_code, month_from_code, occ_root,
+};
+
+// Re-export query types
+pub use crate::api::query::{EquityOptionsQuery, FuturesQuery, PageQuery, SymbolsQuery};
+
+// Re-export risk types
+pub use crate::api::risk::{PositionImpact, Scenario, ScenarioResult, StressTest};
+
+// Re-export market metrics types
+pub use crate::types::market_metrics::{
+    ExpirationImpliedVolatility, MarketMetrics, TermStructure, TermStructurePoint,
+};
+
+// Re-export external transaction types
+pub use crate::types::transaction::{
+    ExternalTransaction, MaskedAccountNumber, TransferDirection, TransferState,
 };
 
+// Re-export market calendar types
+pub use crate::types::calendar::MarketSession;
+
+// Re-export option strategy types
+pub use crate::types::strategy::{Direction, OptionKind, StrategyBuilder, StrategyError};
+
+// Re-export order validation types
+pub use crate::types::validation::{OrderValidationError, OrderValidator};
+
 // Re-export DxFeed types
 pub use crate::types::dxfeed::*;
 
@@ -57,11 +97,55 @@ pub use crate::types::dxfeed::*;
 pub use crate::streaming::account_streaming::{
     AccountEvent, AccountMessage, AccountStreamer, ErrorMessage, StatusMessage,
 };
-pub use crate::streaming::quote_streamer::{QuoteStreamer, QuoteSubscription};
+pub use crate::streaming::bridge::{Bridge, Encoding, MessageSink};
+pub use crate::streaming::cancellation::CancellationToken;
+pub use crate::streaming::csv_logger::{CsvLogger, LogFormat, Rotation};
+pub use crate::streaming::futures_pnl::{FuturesPnl, LiveFuturesPnl};
+pub use crate::streaming::greeks_positions::{GreeksPosition, LiveGreeksPositions};
+pub use crate::streaming::offload::{EventExecutor, OffloadHandle, OffloadStats, ThreadPool};
+pub use crate::streaming::order_tracker::{OrderTracker, TrackedOrder};
+pub use crate::streaming::portfolio_state::PortfolioState;
+pub use crate::streaming::quote_streamer::{
+    QuoteStreamer, QuoteSubscription, SUBSCRIPTION_EVENT_CAPACITY,
+};
+pub use crate::streaming::streamer_config::StreamerConfig;
+pub use crate::streaming::subscription_builder::{EventKind, SubscriptionBuilder};
+pub use crate::streaming::subscription_group::SubscriptionGroup;
 
 // Re-export quote streaming types
 pub use crate::api::quote_streaming::{DxFeedSymbol, QuoteStreamerTokens};
 
+// Re-export diagnostics types
+pub use crate::api::diagnostics::{DiagnosticReport, DiagnosticStep};
+
+// Re-export reconciliation types
+pub use crate::api::await_fill::FillReport;
+pub use crate::api::chase::{ChaseEvent, ChaseReport};
+pub use crate::api::combined_positions::UnderlyingPositions;
+pub use crate::api::portfolio::Portfolio;
+pub use crate::api::reconcile::{Drift, ReconcileOptions, ReconcileReport};
+pub use crate::api::sandbox::Sandbox;
+
+// Re-export authentication types
+pub use crate::api::auth::{
+    AuthProvider, AuthSession, FallbackAuth, OAUTH_AUTHORIZE_URL, OAuth2Auth, OTP_HEADER,
+    OtpCallback, PasswordAuth, RememberTokenAuth, StaticTokenAuth,
+};
+
+// Re-export session lifecycle types
+pub use crate::api::keepalive::KEEPALIVE_INTERVAL;
+pub use crate::api::session::{SESSION_REFRESH_MARGIN, SessionCallback, SessionManager};
+
+// Re-export request statistics
+pub use crate::api::stats::ClientStats;
+
+// Re-export order tagging types
+pub use crate::api::tags::{OrderTags, TagLog, TagRecord};
+
+// Re-export order throttling types
+pub use crate::api::guard::EnvironmentGuardConfig;
+pub use crate::api::throttle::{DuplicateOrderPolicy, OrderThrottle, OrderThrottleConfig};
+
 // Re-export option chain types
 pub use crate::api::option_chain::{
     Expiration as OptionExpiration, NestedOptionChain as OptionNestedChain, OptionChain,
@@ -70,11 +154,20 @@ pub use crate::api::option_chain::{
 
 // Re-export utility types
 pub use crate::utils::{
-    config::TastyTradeConfig, download::*, file::*, logger::setup_logger, parse::*,
+    candle_cache::{Candle, CandleCache},
+    clock::{Clock, MockClock, SharedClock, SystemClock},
+    config::{ConfigProblem, Environment, TastyTradeConfig},
+    download::*,
+    file::*,
+    logger::setup_logger,
+    parse::*,
+    universe::{SymbolSetExt, SymbolUniverse, UniverseDiff},
 };
 
 // Re-export login types
-pub use crate::types::login::{LoginCredentials, LoginResponse, LoginResponseUser};
+pub use crate::types::login::{
+    LoginCredentials, LoginResponse, LoginResponseUser, SessionValidation,
+};
 
 // Re-export event types
 pub use crate::types::event::TastyEvent;
@@ -1,6 +1,9 @@
 use std::time::Duration;
 
+use crate::streaming::cancellation::CancellationToken;
+use crate::streaming::streamer_config::{StreamerConfig, connect_with_config};
 use crate::types::balance::Balance;
+use crate::types::transaction::ExternalTransaction;
 use crate::{
     BriefPosition, LiveOrderRecord, TastyResult, TastyTrade, TastyTradeError, accounts::Account,
 };
@@ -9,7 +12,7 @@ use futures_util::{SinkExt, StreamExt};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::tungstenite::Message;
 use tracing::{debug, error, warn};
 
 /**
@@ -85,9 +88,9 @@ pub struct HandlerAction {
 /// {"type": "account_balance", "data": { ... balance data ... }}
 /// {"type": "current_position", "data": { ... position data ... }}
 /// {"type": "order_chain", "data": null}
-/// {"type": "external_transaction", "data": null}
+/// {"type": "external_transaction", "data": { ... deposit or withdrawal ... }}
 /// ```
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(tag = "type", content = "data")]
 pub enum AccountMessage {
     /// Represents a live order record.  Contains a `LiveOrderRecord` struct.
@@ -98,8 +101,9 @@ pub enum AccountMessage {
     CurrentPosition(Box<BriefPosition>),
     /// Represents an order chain.  Currently has no associated data.
     OrderChain,
-    /// Represents an external transaction.  Currently has no associated data.
-    ExternalTransaction,
+    /// Represents a deposit or withdrawal being created or changing state.  Contains an
+    /// `ExternalTransaction` struct with the bank account number already masked.
+    ExternalTransaction(Box<ExternalTransaction>),
 }
 
 /// Represents a status message received from the API.
@@ -151,7 +155,7 @@ pub struct ErrorMessage {
 ///
 /// This enum uses `serde`'s untagged enum representation.  This means the
 /// deserialization will try each variant in order until one matches.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(untagged)]
 pub enum AccountEvent {
     /// Represents an error message received from the API.
@@ -201,14 +205,16 @@ pub struct AccountStreamer {
     channel_id: Option<u32>,
     /// Optional sender for DXLink commands.
     dxlink_command_tx: Option<mpsc::Sender<DXLinkCommand>>,
+    /// Stops the tasks of the streamer.
+    cancellation: CancellationToken,
 }
 
 impl AccountStreamer {
     /// Establishes a connection to the TastyTrade streaming API for account updates.
     ///
     /// This function initializes and manages two separate streaming connections:
-    /// 1. **DXLink:** A newer, more robust streaming solution.  It attempts to create and configure a DXLink channel for account updates, subscribing to `Order` and `Message` event types.  If successful, it uses this channel for streaming data.  If DXLink setup fails, it falls back to the legacy websocket implementation.
-    /// 2. **Legacy Websocket:**  A fallback mechanism used if DXLink connection or channel setup fails. It maintains a persistent websocket connection to receive account updates.
+    /// 1. **DXLink:** A newer, more robust streaming solution.  It connects to the DXLink URL returned by `/api-quote-tokens` and attempts to create and configure a channel for account updates, subscribing to `Order` and `Message` event types.  If DXLink is unavailable (e.g. no market data entitlement) the streamer continues with the account websocket alone.
+    /// 2. **Account Websocket:**  A persistent connection to [`TastyTrade::account_streamer_url`] that receives account updates.
     ///
     /// Both implementations handle incoming messages and send outgoing actions (e.g., heartbeats, subscriptions).  The DXLink implementation also includes a command channel for managing subscriptions and disconnections.
     ///
@@ -225,21 +231,166 @@ impl AccountStreamer {
     ///
     /// This function can return a variety of errors related to network communication, authentication, or streaming setup. See the `TastyTradeError` enum for more details.
     pub async fn connect(tasty: &TastyTrade) -> TastyResult<AccountStreamer> {
-        let token = &tasty.session_token;
+        Self::connect_with_cancellation(tasty, CancellationToken::new()).await
+    }
+
+    /// Like [`connect`](Self::connect), stopping every task of the streamer once
+    /// `cancellation` is cancelled. See [`cancellation`](crate::streaming::cancellation).
+    pub async fn connect_with_cancellation(
+        tasty: &TastyTrade,
+        cancellation: CancellationToken,
+    ) -> TastyResult<AccountStreamer> {
+        tasty.session.ensure_open()?;
+        let dxlink = Self::connect_dxlink(tasty, &cancellation).await;
+        let (channel_id, dxlink_command_tx) = match dxlink {
+            Ok((channel_id, command_tx)) => (channel_id, Some(command_tx)),
+            Err(e) => {
+                warn!(
+                    "DXLink unavailable for account updates, using the account websocket only: {}",
+                    e
+                );
+                (None, None)
+            }
+        };
+
+        // The account websocket carries the account messages themselves
+        let mut streamer = Self::open_websocket(
+            &tasty.account_streamer_url(),
+            &tasty.session.token(),
+            &tasty.config.streamer,
+            cancellation,
+        )
+        .await?;
+        streamer.channel_id = channel_id;
+        streamer.dxlink_command_tx = dxlink_command_tx;
+        Ok(streamer)
+    }
+
+    /// Connects only the account websocket at `url`, authenticating requests with `token`.
+    ///
+    /// No DXLink connection is made. Frames that are not valid account events are logged
+    /// and skipped; once the server closes or drops the connection, [`get_event`](Self::get_event)
+    /// returns an error after the events already received.
+    pub async fn connect_websocket(url: &str, token: &str) -> TastyResult<AccountStreamer> {
+        Self::connect_websocket_with(url, token, &StreamerConfig::default()).await
+    }
+
+    /// Like [`connect_websocket`](Self::connect_websocket), with the socket options of
+    /// `config`.
+    pub async fn connect_websocket_with(
+        url: &str,
+        token: &str,
+        config: &StreamerConfig,
+    ) -> TastyResult<AccountStreamer> {
+        Self::open_websocket(url, token, config, CancellationToken::new()).await
+    }
+
+    async fn open_websocket(
+        url: &str,
+        token: &str,
+        config: &StreamerConfig,
+        cancellation: CancellationToken,
+    ) -> TastyResult<AccountStreamer> {
         let (event_sender, event_receiver) = flume::unbounded();
         let (action_sender, action_receiver): (
             flume::Sender<HandlerAction>,
             flume::Receiver<HandlerAction>,
         ) = flume::unbounded();
 
-        // Initialize DXLink client for account updates
-        let mut client = DXLinkClient::new(&tasty.config.websocket_url, token);
+        let token_clone = token.to_string();
+
+        let ws_stream = connect_with_config(url, config).await?;
+
+        let (mut write, mut read) = ws_stream.split();
+
+        cancellation.spawn("Account websocket reader", async move {
+            while let Some(message) = read.next().await {
+                let message = match message {
+                    Ok(Message::Close(_)) => break,
+                    Ok(message @ (Message::Text(_) | Message::Binary(_))) => message,
+                    Ok(_) => continue, // Ping and pong frames
+                    Err(e) => {
+                        warn!("Account websocket connection lost: {}", e);
+                        break;
+                    }
+                };
+                match serde_json::from_slice::<AccountEvent>(&message.into_data()) {
+                    Ok(event) => {
+                        if event_sender.send_async(event).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => warn!("Skipping malformed account message: {}", e),
+                }
+            }
+            debug!("Account websocket reader terminated");
+        });
+
+        cancellation.spawn("Account websocket writer", async move {
+            while let Ok(action) = action_receiver.recv_async().await {
+                let message = SubRequest::<Box<dyn erased_serde::Serialize + Send + Sync>> {
+                    auth_token: token_clone.clone(),
+                    action: action.action,
+                    value: action.value,
+                };
+                let message = serde_json::to_string(&message).unwrap();
+                let message = Message::Text(message.into());
+
+                if write.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let sender_clone = action_sender.clone();
+        cancellation.spawn("Account websocket heartbeat", async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                if sender_clone
+                    .send_async(HandlerAction {
+                        action: SubRequestAction::Heartbeat,
+                        value: None,
+                    })
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            event_receiver,
+            action_sender,
+            channel_id: None,
+            dxlink_command_tx: None,
+            cancellation,
+        })
+    }
+
+    /// The token stopping the tasks of this streamer.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+
+    /// Opens the DXLink side of the streamer.
+    ///
+    /// DXLink is a separate server from the account websocket: its URL and token come
+    /// from [`TastyTrade::quote_streamer_tokens`], never from `websocket_url`.
+    async fn connect_dxlink(
+        tasty: &TastyTrade,
+        cancellation: &CancellationToken,
+    ) -> TastyResult<(Option<u32>, mpsc::Sender<DXLinkCommand>)> {
+        let tokens = tasty.quote_streamer_tokens().await?;
+        let mut client = DXLinkClient::new(&tokens.streamer_url, &tokens.token);
 
         // Connect to DXLink
         match client.connect().await {
-            Ok(_) => debug!("Connected to DXLink for account updates"),
+            Ok(_) => debug!(
+                "Connected to DXLink at {} for account updates",
+                tokens.streamer_url
+            ),
             Err(e) => {
-                warn!("Error connecting to DXLink for account updates: {}", e);
                 return Err(TastyTradeError::Streaming(format!(
                     "Error connecting to DXLink for account updates: {}",
                     e
@@ -277,7 +428,7 @@ impl AccountStreamer {
         let (command_tx, mut command_rx) = mpsc::channel::<DXLinkCommand>(100);
 
         // Spawn task to handle DXLink commands
-        tokio::spawn(async move {
+        cancellation.spawn("DXLink account command handler", async move {
             while let Some(cmd) = command_rx.recv().await {
                 match cmd {
                     DXLinkCommand::Subscribe(channel_id, subscriptions) => {
@@ -304,61 +455,7 @@ impl AccountStreamer {
             debug!("DXLink account command handler terminated");
         });
 
-        // Keep existing tokio-tungstenite implementation for compatibility
-        let url = tasty.config.websocket_url.clone();
-        let token_clone = token.clone();
-
-        let (ws_stream, _response) = connect_async(url).await?;
-
-        let (mut write, mut read) = ws_stream.split();
-
-        tokio::spawn(async move {
-            while let Some(message) = read.next().await {
-                let data = message.unwrap().into_data();
-                let data: AccountEvent = serde_json::from_slice(&data).unwrap();
-                event_sender.send_async(data).await.unwrap();
-            }
-        });
-
-        tokio::spawn(async move {
-            while let Ok(action) = action_receiver.recv_async().await {
-                let message = SubRequest::<Box<dyn erased_serde::Serialize + Send + Sync>> {
-                    auth_token: token_clone.clone(),
-                    action: action.action,
-                    value: action.value,
-                };
-                let message = serde_json::to_string(&message).unwrap();
-                let message = Message::Text(message.into());
-
-                if write.send(message).await.is_err() {
-                    break;
-                }
-            }
-        });
-
-        let sender_clone = action_sender.clone();
-        tokio::spawn(async move {
-            loop {
-                tokio::time::sleep(Duration::from_secs(30)).await;
-                if sender_clone
-                    .send_async(HandlerAction {
-                        action: SubRequestAction::Heartbeat,
-                        value: None,
-                    })
-                    .await
-                    .is_err()
-                {
-                    break;
-                }
-            }
-        });
-
-        Ok(Self {
-            event_receiver,
-            action_sender,
-            channel_id,
-            dxlink_command_tx: Some(command_tx),
-        })
+        Ok((channel_id, command_tx))
     }
 
     /// Subscribes to account updates.
@@ -460,10 +557,13 @@ impl Drop for AccountStreamer {
     /// to avoid blocking the drop function.  Any errors encountered while
     /// sending the disconnect command are logged as warnings.
     fn drop(&mut self) {
-        // Send disconnect command if we have a command channel
-        if let Some(tx) = &self.dxlink_command_tx {
+        // Send disconnect command if we have a command channel, unless the runtime is gone
+        if let (Some(tx), Ok(runtime)) = (
+            &self.dxlink_command_tx,
+            tokio::runtime::Handle::try_current(),
+        ) {
             let tx_clone = tx.clone();
-            tokio::spawn(async move {
+            runtime.spawn(async move {
                 if let Err(e) = tx_clone.send(DXLinkCommand::Disconnect).await {
                     warn!("Error sending disconnect command: {}", e);
                 }
@@ -488,4 +588,13 @@ impl TastyTrade {
     pub async fn create_account_streamer(&self) -> TastyResult<AccountStreamer> {
         AccountStreamer::connect(self).await
     }
+
+    /// Like [`create_account_streamer`](Self::create_account_streamer), stopping every
+    /// task of the streamer once `cancellation` is cancelled.
+    pub async fn create_account_streamer_with_cancellation(
+        &self,
+        cancellation: CancellationToken,
+    ) -> TastyResult<AccountStreamer> {
+        AccountStreamer::connect_with_cancellation(self, cancellation).await
+    }
 }
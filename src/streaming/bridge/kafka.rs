@@ -0,0 +1,53 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Kafka sink of the bridge, enabled with the `kafka` feature.
+
+use super::MessageSink;
+use crate::{TastyResult, TastyTradeError};
+use rdkafka::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use std::time::Duration;
+
+/// How long a message may wait in the producer queue before publishing fails.
+const QUEUE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Publishes to Kafka topics; the message key is the bridge's key.
+#[derive(Clone)]
+pub struct KafkaSink {
+    producer: FutureProducer,
+}
+
+impl KafkaSink {
+    /// A sink producing to the comma-separated `brokers`.
+    pub fn new(brokers: &str) -> TastyResult<Self> {
+        let mut config = ClientConfig::new();
+        config.set("bootstrap.servers", brokers);
+        Self::from_config(&config)
+    }
+
+    /// A sink producing with `config`, for security or batching settings.
+    pub fn from_config(config: &ClientConfig) -> TastyResult<Self> {
+        let producer = config
+            .create()
+            .map_err(|e| TastyTradeError::Streaming(format!("Kafka producer: {e}")))?;
+        Ok(Self { producer })
+    }
+}
+
+impl MessageSink for KafkaSink {
+    async fn publish(&self, topic: &str, key: Option<&str>, payload: Vec<u8>) -> TastyResult<()> {
+        let mut record = FutureRecord::<str, [u8]>::to(topic).payload(&payload);
+        if let Some(key) = key {
+            record = record.key(key);
+        }
+        self.producer
+            .send(record, QUEUE_TIMEOUT)
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| TastyTradeError::Streaming(format!("Kafka publish to {topic}: {e}")))
+    }
+}
@@ -0,0 +1,222 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Forwarding of streamed events to a message bus.
+//!
+//! A [`Bridge`] publishes account events and market events to a [`MessageSink`], one
+//! topic each, so other processes can react to them without their own session. Every
+//! message is keyed: account events by account number, market events by symbol.
+//! Sinks for Kafka ([`kafka`], feature `kafka`) and NATS ([`nats`], feature `nats`)
+//! are included; anything else implements [`MessageSink`].
+//!
+//! ```rust,ignore
+//! let sink = kafka::KafkaSink::new("localhost:9092")?;
+//! let bridge = Bridge::new(sink);
+//! let account_streamer = tasty.create_account_streamer().await?;
+//! tokio::spawn(async move { bridge.forward_account_events(&account_streamer).await });
+//! ```
+
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "nats")]
+pub mod nats;
+
+use crate::streaming::account_streaming::{AccountEvent, AccountMessage, AccountStreamer};
+use crate::streaming::quote_streamer::QuoteSubscription;
+use crate::types::dxfeed;
+use crate::{TastyResult, TastyTradeError};
+use serde::Serialize;
+use std::future::Future;
+use tracing::warn;
+
+/// Topic of account events unless configured otherwise.
+pub const DEFAULT_ACCOUNT_TOPIC: &str = "tastytrade.account";
+/// Topic of market events unless configured otherwise.
+pub const DEFAULT_MARKET_TOPIC: &str = "tastytrade.market";
+
+/// A message bus the bridge publishes to.
+pub trait MessageSink: Send + Sync {
+    /// Publishes `payload` to `topic`, keyed by `key` where the bus supports keys.
+    fn publish(
+        &self,
+        topic: &str,
+        key: Option<&str>,
+        payload: Vec<u8>,
+    ) -> impl Future<Output = TastyResult<()>> + Send;
+}
+
+/// How events are encoded on the bus.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// The serde JSON form of the event, as the API sends it.
+    #[default]
+    Json,
+}
+
+impl Encoding {
+    fn encode<T: Serialize>(self, value: &T) -> TastyResult<Vec<u8>> {
+        match self {
+            Encoding::Json => Ok(serde_json::to_vec(value)?),
+        }
+    }
+}
+
+/// Publishes streamed events to a [`MessageSink`]. See the module docs.
+#[derive(Debug, Clone)]
+pub struct Bridge<S> {
+    sink: S,
+    account_topic: String,
+    market_topic: String,
+    encoding: Encoding,
+}
+
+impl<S: MessageSink> Bridge<S> {
+    /// A bridge to `sink` on the default topics, encoding events as JSON.
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            account_topic: DEFAULT_ACCOUNT_TOPIC.to_string(),
+            market_topic: DEFAULT_MARKET_TOPIC.to_string(),
+            encoding: Encoding::default(),
+        }
+    }
+
+    /// Publishes account events to `topic`.
+    pub fn account_topic(mut self, topic: impl Into<String>) -> Self {
+        self.account_topic = topic.into();
+        self
+    }
+
+    /// Publishes market events to `topic`.
+    pub fn market_topic(mut self, topic: impl Into<String>) -> Self {
+        self.market_topic = topic.into();
+        self
+    }
+
+    /// Encodes events with `encoding`.
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Publishes one account event, keyed by its account number when it has one.
+    pub async fn publish_account_event(&self, event: &AccountEvent) -> TastyResult<()> {
+        let key = match event {
+            AccountEvent::AccountMessage(message) => account_key(message),
+            _ => None,
+        };
+        let payload = self.encoding.encode(event)?;
+        self.sink
+            .publish(&self.account_topic, key.as_deref(), payload)
+            .await
+    }
+
+    /// Publishes one market event, keyed by its symbol.
+    pub async fn publish_market_event(&self, event: &dxfeed::Event) -> TastyResult<()> {
+        let payload = self.encoding.encode(event)?;
+        self.sink
+            .publish(&self.market_topic, Some(&event.sym), payload)
+            .await
+    }
+
+    /// Publishes every event of `streamer` until it closes. Events the sink rejects
+    /// are logged and skipped.
+    pub async fn forward_account_events(&self, streamer: &AccountStreamer) -> TastyResult<()> {
+        while let Ok(event) = streamer.get_event().await {
+            if let Err(e) = self.publish_account_event(&event).await {
+                warn!("Could not bridge account event: {e}");
+            }
+        }
+        Err(TastyTradeError::Streaming(
+            "account stream closed".to_string(),
+        ))
+    }
+
+    /// Publishes every event of `subscription` until it closes. Events the sink
+    /// rejects are logged and skipped.
+    pub async fn forward_market_events(
+        &self,
+        subscription: &mut QuoteSubscription,
+    ) -> TastyResult<()> {
+        while let Ok(event) = subscription.get_event().await {
+            if let Err(e) = self.publish_market_event(&event).await {
+                warn!("Could not bridge market event: {e}");
+            }
+        }
+        Err(TastyTradeError::Streaming(
+            "quote subscription closed".to_string(),
+        ))
+    }
+}
+
+fn account_key(message: &AccountMessage) -> Option<String> {
+    let number = match message {
+        AccountMessage::Order(order) => &order.account_number,
+        AccountMessage::AccountBalance(balance) => &balance.account_number,
+        AccountMessage::CurrentPosition(position) => &position.account_number,
+        AccountMessage::OrderChain | AccountMessage::ExternalTransaction(_) => return None,
+    };
+    Some(number.0.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::dxfeed::DxfQuoteT;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemorySink(Mutex<Vec<(String, Option<String>, Vec<u8>)>>);
+
+    impl MessageSink for MemorySink {
+        async fn publish(
+            &self,
+            topic: &str,
+            key: Option<&str>,
+            payload: Vec<u8>,
+        ) -> TastyResult<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), key.map(str::to_string), payload));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bridge_publishes_keyed_json() {
+        let bridge = Bridge::new(MemorySink::default()).market_topic("quotes");
+        let quote = DxfQuoteT {
+            bid_price: 100.0,
+            ask_price: 100.5,
+            ..Default::default()
+        };
+        bridge
+            .publish_market_event(&dxfeed::Event::new_quote("SPY".to_string(), quote))
+            .await
+            .unwrap();
+
+        let body = include_str!("../../../tests/fixtures/live_orders.json");
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        let message = serde_json::json!({ "type": "Order", "data": body["data"]["items"][0] });
+        let event =
+            AccountEvent::AccountMessage(Box::new(serde_json::from_value(message).unwrap()));
+        bridge.publish_account_event(&event).await.unwrap();
+
+        let published = bridge.sink.0.lock().unwrap();
+        let (topic, key, payload) = &published[0];
+        assert_eq!(topic, "quotes");
+        assert_eq!(key.as_deref(), Some("SPY"));
+        let json: serde_json::Value = serde_json::from_slice(payload).unwrap();
+        assert_eq!(json["sym"], "SPY");
+
+        let (topic, key, payload) = &published[1];
+        assert_eq!(topic, DEFAULT_ACCOUNT_TOPIC);
+        assert_eq!(key.as_deref(), Some("5WT00001"));
+        let json: serde_json::Value = serde_json::from_slice(payload).unwrap();
+        assert_eq!(json["type"], "Order");
+    }
+}
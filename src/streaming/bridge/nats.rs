@@ -0,0 +1,52 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! NATS sink of the bridge, enabled with the `nats` feature.
+
+use super::MessageSink;
+use crate::{TastyResult, TastyTradeError};
+use async_nats::HeaderMap;
+
+/// Header carrying the bridge's key, since NATS subjects have none.
+pub const KEY_HEADER: &str = "Tastytrade-Key";
+
+/// Publishes to NATS subjects named after the bridge's topics.
+#[derive(Clone)]
+pub struct NatsSink {
+    client: async_nats::Client,
+}
+
+impl NatsSink {
+    /// A sink connected to the server at `url`, e.g. `nats://localhost:4222`.
+    pub async fn connect(url: &str) -> TastyResult<Self> {
+        let client = async_nats::connect(url)
+            .await
+            .map_err(|e| TastyTradeError::Streaming(format!("NATS connect: {e}")))?;
+        Ok(Self { client })
+    }
+
+    /// A sink publishing through an already connected `client`.
+    pub fn from_client(client: async_nats::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl MessageSink for NatsSink {
+    async fn publish(&self, topic: &str, key: Option<&str>, payload: Vec<u8>) -> TastyResult<()> {
+        let subject = topic.to_string();
+        let result = match key {
+            Some(key) => {
+                let mut headers = HeaderMap::new();
+                headers.insert(KEY_HEADER, key);
+                self.client
+                    .publish_with_headers(subject, headers, payload.into())
+                    .await
+            }
+            None => self.client.publish(subject, payload.into()).await,
+        };
+        result.map_err(|e| TastyTradeError::Streaming(format!("NATS publish to {topic}: {e}")))
+    }
+}
@@ -0,0 +1,104 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Application-wide shutdown of the streaming tasks.
+//!
+//! Streamers run their socket readers, writers and heartbeats on spawned tasks. By
+//! default those end when the streamer is dropped or the server closes the connection;
+//! a task still running when the runtime shuts down is cancelled mid-way. Connect with a
+//! [`CancellationToken`] instead, e.g.
+//! [`TastyTrade::create_account_streamer_with_cancellation`](crate::TastyTrade::create_account_streamer_with_cancellation),
+//! and cancel it before shutting down: every task of every streamer sharing the token
+//! stops.
+//!
+//! ```rust,ignore
+//! let shutdown = CancellationToken::new();
+//! let account = tasty.create_account_streamer_with_cancellation(shutdown.clone()).await?;
+//! let quotes = tasty.create_quote_streamer_with_cancellation(shutdown.clone()).await?;
+//! tokio::signal::ctrl_c().await?;
+//! shutdown.cancel();
+//! ```
+
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+/// Signal shared by the tasks to stop. Clones share the same state; cancelling is
+/// permanent.
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<watch::Sender<bool>>,
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CancellationToken {
+    /// A token not cancelled yet.
+    pub fn new() -> Self {
+        Self {
+            cancelled: Arc::new(watch::Sender::new(false)),
+        }
+    }
+
+    /// Cancels the token, waking every task waiting on it.
+    pub fn cancel(&self) {
+        self.cancelled.send_replace(true);
+    }
+
+    /// Returns `true` once [`cancel`](Self::cancel) was called on this token or a clone.
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    /// Completes once the token is cancelled, immediately if it already is.
+    pub async fn cancelled(&self) {
+        let mut receiver = self.cancelled.subscribe();
+        // The sender lives as long as `self`, so waiting cannot fail
+        let _ = receiver.wait_for(|cancelled| *cancelled).await;
+    }
+
+    /// Spawns `task`, dropping it as soon as the token is cancelled.
+    pub(crate) fn spawn<F>(&self, name: &'static str, task: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let token = self.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = token.cancelled() => debug!("{name} cancelled"),
+                _ = task => {}
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_cancel_stops_spawned_tasks() {
+        let token = CancellationToken::new();
+        let task = token.spawn("test task", std::future::pending());
+        assert!(!token.is_cancelled());
+
+        token.clone().cancel();
+        assert!(token.is_cancelled());
+        tokio::time::timeout(Duration::from_secs(1), task)
+            .await
+            .unwrap()
+            .unwrap();
+        // Already cancelled: returns at once
+        token.cancelled().await;
+    }
+}
@@ -0,0 +1,326 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Recording quote events to disk.
+//!
+//! [`CsvLogger`] writes every event it receives as one timestamped row, either as CSV
+//! with a fixed header shared by all event types or as JSON lines. Each row is written
+//! and flushed in one go, so a file read while the logger is running never ends in a
+//! partial record. Files can rotate daily or once they reach a size limit.
+//!
+//! ```rust,ignore
+//! let logger = CsvLogger::new("ticks", "spy").rotation(Rotation::Daily);
+//! let task = logger.attach(*quote_sub);
+//! ```
+
+use crate::api::base::TastyResult;
+use crate::dxfeed::{Event, EventData};
+use crate::streaming::quote_streamer::QuoteSubscription;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+const CSV_HEADER: &str = "received_at,symbol,event_type,time,bid_price,ask_price,bid_size,ask_size,price,size,day_volume,volatility,delta,gamma,theta,vega,rho";
+
+/// Output file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Comma separated values with a header row; fields not carried by an event are empty.
+    #[default]
+    Csv,
+    /// One JSON object per line, holding the receive time and the full event.
+    JsonLines,
+}
+
+impl LogFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            LogFormat::Csv => "csv",
+            LogFormat::JsonLines => "jsonl",
+        }
+    }
+}
+
+/// When to start a new file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    /// Keep writing to the same file.
+    #[default]
+    Never,
+    /// Start a new file on the first event of each UTC day.
+    Daily,
+    /// Start a new file once the current one reaches this many bytes.
+    MaxBytes(u64),
+}
+
+#[derive(Serialize)]
+struct JsonRow<'a> {
+    received_at: DateTime<Utc>,
+    event: &'a Event,
+}
+
+struct OpenFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    bytes: u64,
+    day: NaiveDate,
+}
+
+/// Writes timestamped quote events to rotating CSV or JSON lines files.
+///
+/// Files are named `<prefix>-<YYYYMMDD>-<n>.<csv|jsonl>` inside the configured directory.
+pub struct CsvLogger {
+    dir: PathBuf,
+    prefix: String,
+    format: LogFormat,
+    rotation: Rotation,
+    sequence: u32,
+    current: Option<OpenFile>,
+}
+
+impl CsvLogger {
+    /// Creates a CSV logger writing to `dir`, which is created on the first write.
+    pub fn new(dir: impl Into<PathBuf>, prefix: impl Into<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            prefix: prefix.into(),
+            format: LogFormat::default(),
+            rotation: Rotation::default(),
+            sequence: 0,
+            current: None,
+        }
+    }
+
+    /// Sets the output format.
+    pub fn format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the rotation policy.
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Path of the file currently being written, if any.
+    pub fn current_path(&self) -> Option<&Path> {
+        self.current.as_ref().map(|file| file.path.as_path())
+    }
+
+    /// Appends one event, stamped with the current time.
+    pub fn write(&mut self, event: &Event) -> TastyResult<()> {
+        self.write_at(event, Utc::now())
+    }
+
+    /// Appends one event with an explicit receive time.
+    pub fn write_at(&mut self, event: &Event, received_at: DateTime<Utc>) -> TastyResult<()> {
+        let line = self.format_row(event, received_at)?;
+        let file = self.file_for(received_at)?;
+        file.writer.write_all(line.as_bytes())?;
+        file.writer.flush()?;
+        file.bytes += line.len() as u64;
+        Ok(())
+    }
+
+    /// Consumes the subscription and logs every event until it ends.
+    ///
+    /// Write errors are logged and the offending event skipped.
+    pub fn attach(mut self, mut subscription: QuoteSubscription) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            while let Ok(event) = subscription.get_event().await {
+                if let Err(e) = self.write(&event) {
+                    warn!("Failed to log event for {}: {}", event.sym, e);
+                }
+            }
+        })
+    }
+
+    fn format_row(&self, event: &Event, received_at: DateTime<Utc>) -> TastyResult<String> {
+        match self.format {
+            LogFormat::JsonLines => {
+                let mut line = serde_json::to_string(&JsonRow { received_at, event })?;
+                line.push('\n');
+                Ok(line)
+            }
+            LogFormat::Csv => Ok(csv_row(event, received_at)),
+        }
+    }
+
+    fn file_for(&mut self, now: DateTime<Utc>) -> TastyResult<&mut OpenFile> {
+        let rotate = match (&self.current, self.rotation) {
+            (None, _) => true,
+            (Some(_), Rotation::Never) => false,
+            (Some(file), Rotation::Daily) => file.day != now.date_naive(),
+            (Some(file), Rotation::MaxBytes(limit)) => file.bytes >= limit,
+        };
+        if rotate {
+            self.current = Some(self.open(now)?);
+        }
+        Ok(self.current.as_mut().expect("file opened above"))
+    }
+
+    fn open(&mut self, now: DateTime<Utc>) -> TastyResult<OpenFile> {
+        fs::create_dir_all(&self.dir)?;
+        let path = loop {
+            self.sequence += 1;
+            let path = self.dir.join(format!(
+                "{}-{}-{}.{}",
+                self.prefix,
+                now.format("%Y%m%d"),
+                self.sequence,
+                self.format.extension()
+            ));
+            if !path.exists() {
+                break path;
+            }
+        };
+
+        let mut writer = BufWriter::new(File::create(&path)?);
+        let mut bytes = 0;
+        if self.format == LogFormat::Csv {
+            writeln!(writer, "{CSV_HEADER}")?;
+            writer.flush()?;
+            bytes = CSV_HEADER.len() as u64 + 1;
+        }
+        Ok(OpenFile {
+            path,
+            writer,
+            bytes,
+            day: now.date_naive(),
+        })
+    }
+}
+
+fn csv_row(event: &Event, received_at: DateTime<Utc>) -> String {
+    // time, bid, ask, bid_size, ask_size, price, size, day_volume, vol, delta, gamma, theta, vega, rho
+    let (kind, fields): (&str, [String; 14]) = match &event.data {
+        EventData::Quote(q) => (
+            "Quote",
+            [
+                q.time.to_string(),
+                q.bid_price.to_string(),
+                q.ask_price.to_string(),
+                q.bid_size.to_string(),
+                q.ask_size.to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ],
+        ),
+        EventData::Trade(t) => (
+            "Trade",
+            [
+                t.time.to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                t.price.to_string(),
+                t.size.to_string(),
+                t.day_volume.to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ],
+        ),
+        EventData::Greeks(g) => (
+            "Greeks",
+            [
+                g.time.to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                g.price.to_string(),
+                String::new(),
+                String::new(),
+                g.volatility.to_string(),
+                g.delta.to_string(),
+                g.gamma.to_string(),
+                g.theta.to_string(),
+                g.vega.to_string(),
+                g.rho.to_string(),
+            ],
+        ),
+    };
+    format!(
+        "{},{},{},{}\n",
+        received_at.to_rfc3339(),
+        csv_field(&event.sym),
+        kind,
+        fields.join(",")
+    )
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dxfeed::{DxfQuoteT, DxfTradeT};
+
+    #[test]
+    fn test_csv_logger_writes_rows_and_rotates_by_size() {
+        let dir =
+            std::env::temp_dir().join(format!("tastytrade-csv-logger-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let quote = Event::new_quote(
+            "SPY".to_string(),
+            DxfQuoteT {
+                bid_price: 500.25,
+                ask_price: 500.5,
+                ..Default::default()
+            },
+        );
+        let trade = Event::new_trade(
+            "SPY".to_string(),
+            DxfTradeT {
+                price: 500.3,
+                size: 10,
+                ..Default::default()
+            },
+        );
+        let at = "2024-06-03T14:30:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let mut logger = CsvLogger::new(&dir, "spy").rotation(Rotation::MaxBytes(1));
+        logger.write_at(&quote, at).unwrap();
+        let first = logger.current_path().unwrap().to_path_buf();
+        logger.write_at(&trade, at).unwrap();
+        let second = logger.current_path().unwrap().to_path_buf();
+        assert_ne!(first, second);
+
+        let contents = fs::read_to_string(&first).unwrap();
+        let mut lines = contents.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("2024-06-03T14:30:00+00:00,SPY,Quote,0,500.25,500.5,"));
+        assert_eq!(row.split(',').count(), CSV_HEADER.split(',').count());
+        assert!(fs::read_to_string(&second).unwrap().contains(",Trade,"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Recent events of a subscription, kept per symbol.
+//!
+//! [`QuoteSubscription::keep_last`](crate::streaming::quote_streamer::QuoteSubscription::keep_last)
+//! records the last `n` events of every symbol in a ring buffer, so a UI can draw a
+//! short trade or quote tape with
+//! [`QuoteSubscription::history`](crate::streaming::quote_streamer::QuoteSubscription::history)
+//! instead of storing events itself. Memory stays bounded at `n` events per symbol.
+//!
+//! ```rust,ignore
+//! let sub = streamer.create_sub(dxfeed::DXF_ET_TRADE);
+//! sub.add_symbols(&["SPY"]);
+//! sub.keep_last(20);
+//! // ... later, from the render loop
+//! for trade in sub.history("SPY") { /* ... */ }
+//! ```
+
+use crate::types::dxfeed;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::broadcast;
+
+#[derive(Debug, Default)]
+struct Buffers {
+    capacity: usize,
+    by_symbol: HashMap<String, VecDeque<dxfeed::Event>>,
+}
+
+/// Per-symbol ring buffers shared by a subscription and its clones.
+#[derive(Debug, Default)]
+pub(crate) struct EventHistory {
+    buffers: Mutex<Buffers>,
+    recording: AtomicBool,
+}
+
+impl EventHistory {
+    /// Keeps the last `capacity` events per symbol, dropping older ones already kept.
+    /// Zero stops recording and clears the history.
+    pub(crate) fn set_capacity(&self, capacity: usize) {
+        let mut buffers = self.buffers.lock().unwrap();
+        buffers.capacity = capacity;
+        if capacity == 0 {
+            buffers.by_symbol.clear();
+        }
+        for events in buffers.by_symbol.values_mut() {
+            while events.len() > capacity {
+                events.pop_front();
+            }
+        }
+    }
+
+    /// Marks the recorder as started; `false` when it already was.
+    pub(crate) fn start_recording(&self) -> bool {
+        !self.recording.swap(true, Ordering::SeqCst)
+    }
+
+    pub(crate) fn record(&self, event: dxfeed::Event) {
+        let mut buffers = self.buffers.lock().unwrap();
+        let capacity = buffers.capacity;
+        if capacity == 0 {
+            return;
+        }
+        let events = buffers.by_symbol.entry(event.sym.clone()).or_default();
+        if events.len() == capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// Events kept for `symbol`, oldest first.
+    pub(crate) fn get(&self, symbol: &str) -> Vec<dxfeed::Event> {
+        self.buffers
+            .lock()
+            .unwrap()
+            .by_symbol
+            .get(symbol)
+            .map(|events| events.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Records the events of `receiver` until the subscription closes.
+    pub(crate) async fn record_from(&self, mut receiver: broadcast::Receiver<dxfeed::Event>) {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => self.record(event),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::dxfeed::{DxfQuoteT, EventData};
+
+    fn quote(sym: &str, bid_price: f64) -> dxfeed::Event {
+        dxfeed::Event::new_quote(
+            sym.to_string(),
+            DxfQuoteT {
+                bid_price,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn bids(history: &EventHistory, sym: &str) -> Vec<f64> {
+        history
+            .get(sym)
+            .into_iter()
+            .map(|event| match event.data {
+                EventData::Quote(quote) => quote.bid_price,
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_history_keeps_last_events_per_symbol() {
+        let history = EventHistory::default();
+        history.record(quote("SPY", 1.0));
+        assert!(history.get("SPY").is_empty());
+
+        history.set_capacity(2);
+        for price in [1.0, 2.0, 3.0] {
+            history.record(quote("SPY", price));
+        }
+        history.record(quote("QQQ", 9.0));
+        assert_eq!(bids(&history, "SPY"), [2.0, 3.0]);
+        assert_eq!(bids(&history, "QQQ"), [9.0]);
+
+        history.set_capacity(1);
+        assert_eq!(bids(&history, "SPY"), [3.0]);
+        history.set_capacity(0);
+        assert!(history.get("QQQ").is_empty());
+    }
+}
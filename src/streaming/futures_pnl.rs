@@ -0,0 +1,208 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Live mark-to-market of futures positions.
+//!
+//! The positions endpoint only carries the previous close, which is stale for
+//! instruments trading almost around the clock. [`Account::live_futures_pnl`]
+//! subscribes every futures position to `Quote` events and emits a
+//! [`FuturesPnl`] on each update, valuing the position at the quote mid:
+//! price change × multiplier × signed quantity.
+
+use crate::accounts::Account;
+use crate::api::quote_streaming::DxFeedSymbol;
+use crate::dxfeed::{self, DxfQuoteT, EventData};
+use crate::streaming::portfolio_state::signed_quantity;
+use crate::types::instrument::InstrumentType;
+use crate::{FullPosition, TastyResult};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// A futures position marked at the latest quote.
+#[derive(Debug, Clone, Serialize)]
+pub struct FuturesPnl {
+    /// The position as returned by the positions endpoint.
+    pub position: FullPosition,
+    /// DXLink symbol used for the quote subscription.
+    pub streamer_symbol: DxFeedSymbol,
+    /// Mid of the latest quote, `None` until one with both sides arrives.
+    pub mark: Option<Decimal>,
+}
+
+impl FuturesPnl {
+    fn pnl_from(&self, reference: Decimal) -> Option<Decimal> {
+        let qty = signed_quantity(self.position.quantity, self.position.quantity_direction);
+        self.mark
+            .map(|mark| (mark - reference) * self.position.multiplier * qty)
+    }
+
+    /// P&L since the previous close.
+    pub fn day_pnl(&self) -> Option<Decimal> {
+        self.pnl_from(self.position.close_price)
+    }
+
+    /// P&L since the position was opened.
+    pub fn open_pnl(&self) -> Option<Decimal> {
+        self.pnl_from(self.position.average_open_price)
+    }
+}
+
+/// Mid price of a quote, `None` if either side is missing (NaN) or not positive.
+fn quote_mid(quote: &DxfQuoteT) -> Option<Decimal> {
+    let (bid, ask) = (quote.bid()?, quote.ask()?);
+    if bid <= 0.0 || ask <= 0.0 {
+        return None;
+    }
+    let bid = Decimal::try_from(bid).ok()?;
+    let ask = Decimal::try_from(ask).ok()?;
+    Some((bid + ask) / Decimal::TWO)
+}
+
+/// Handle returned by [`Account::live_futures_pnl`].
+///
+/// The underlying quote streamer lives in a background task and shuts down once
+/// this handle (and every stream created from it) is dropped.
+pub struct LiveFuturesPnl {
+    receiver: flume::Receiver<FuturesPnl>,
+    latest: Arc<Mutex<HashMap<DxFeedSymbol, FuturesPnl>>>,
+}
+
+impl LiveFuturesPnl {
+    /// Waits for the next mark update. Returns `None` once the stream has ended.
+    pub async fn recv(&self) -> Option<FuturesPnl> {
+        self.receiver.recv_async().await.ok()
+    }
+
+    /// Current view of every futures position, including those still waiting for a quote.
+    pub fn snapshot(&self) -> Vec<FuturesPnl> {
+        self.latest.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Day P&L summed over the positions that have a mark.
+    pub fn total_day_pnl(&self) -> Decimal {
+        self.latest
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(FuturesPnl::day_pnl)
+            .sum()
+    }
+
+    /// Converts the handle into a `Stream` of updates.
+    pub fn into_stream(self) -> flume::r#async::RecvStream<'static, FuturesPnl> {
+        self.receiver.into_stream()
+    }
+}
+
+impl Account<'_> {
+    /// Streams this account's futures positions marked to market on every quote.
+    ///
+    /// Positions are fetched once; call again after the position set changes.
+    pub async fn live_futures_pnl(&self) -> TastyResult<LiveFuturesPnl> {
+        let positions = self.positions().await?;
+
+        let mut latest = HashMap::new();
+        for position in positions {
+            if !matches!(position.instrument_type, InstrumentType::Future) {
+                continue;
+            }
+            let streamer_symbol = self
+                .tasty
+                .get_streamer_symbol(&position.instrument_type, &position.symbol)
+                .await?;
+            latest.insert(
+                streamer_symbol.clone(),
+                FuturesPnl {
+                    position,
+                    streamer_symbol,
+                    mark: None,
+                },
+            );
+        }
+        let symbols: Vec<DxFeedSymbol> = latest.keys().cloned().collect();
+        let latest = Arc::new(Mutex::new(latest));
+
+        let (sender, receiver) = flume::unbounded();
+        if !symbols.is_empty() {
+            let mut streamer = self.tasty.create_quote_streamer().await?;
+            let mut sub = streamer.create_sub(dxfeed::DXF_ET_QUOTE);
+            sub.add_symbols(&symbols);
+
+            let latest = latest.clone();
+            tokio::spawn(async move {
+                let _streamer = streamer;
+                while let Ok(event) = sub.get_event().await {
+                    let EventData::Quote(quote) = event.data else {
+                        continue;
+                    };
+                    let Some(mark) = quote_mid(&quote) else {
+                        continue;
+                    };
+                    let update = {
+                        let mut latest = latest.lock().unwrap();
+                        let Some(entry) = latest.get_mut(&DxFeedSymbol(event.sym)) else {
+                            continue;
+                        };
+                        entry.mark = Some(mark);
+                        entry.clone()
+                    };
+                    if sender.send_async(update).await.is_err() {
+                        break;
+                    }
+                }
+                debug!("Live futures P&L stream terminated");
+            });
+        }
+
+        Ok(LiveFuturesPnl { receiver, latest })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_futures_pnl_uses_mid_multiplier_and_direction() {
+        let position = crate::test_fixtures::position(serde_json::json!({
+            "symbol": "/ESZ4",
+            "instrument-type": "Future",
+            "underlying-symbol": "/ES",
+            "quantity": "2",
+            "quantity-direction": "Short",
+            "close-price": "5000.00",
+            "average-open-price": "5010.00",
+            "multiplier": 50.0,
+        }));
+        let mut entry = FuturesPnl {
+            position,
+            streamer_symbol: DxFeedSymbol("/ESZ24:XCME".to_string()),
+            mark: None,
+        };
+        assert_eq!(entry.day_pnl(), None);
+
+        entry.mark = quote_mid(&DxfQuoteT {
+            bid_price: 4995.0,
+            ask_price: 4995.5,
+            ..Default::default()
+        });
+        assert_eq!(entry.mark, Some(Decimal::from_str("4995.25").unwrap()));
+        // Short 2 × 50: (4995.25 - 5000) × 50 × -2
+        assert_eq!(entry.day_pnl(), Some(Decimal::from_str("475").unwrap()));
+        assert_eq!(entry.open_pnl(), Some(Decimal::from_str("1475").unwrap()));
+        assert_eq!(quote_mid(&DxfQuoteT::default()), None);
+        let one_sided = DxfQuoteT {
+            bid_price: 4995.0,
+            ask_price: f64::NAN,
+            ..Default::default()
+        };
+        assert_eq!(quote_mid(&one_sided), None);
+    }
+}
@@ -0,0 +1,227 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Positions joined with live option greeks.
+//!
+//! [`Account::live_greeks_positions`] resolves the DXLink streamer symbol of every
+//! option position, subscribes to its `Greeks` events and emits a
+//! [`GreeksPosition`] each time one of them updates. Position-level figures
+//! (greek × quantity × multiplier, signed by direction) are available through
+//! [`GreeksPosition::position_theta`] and friends, which is what portfolio views
+//! usually display.
+//!
+//! The greeks subscription is created on a [`QuoteStreamer`] passed in by the caller,
+//! so several accounts share one DXLink connection and options held in more than one
+//! account are only subscribed once.
+
+use crate::accounts::Account;
+use crate::api::quote_streaming::DxFeedSymbol;
+use crate::dxfeed::{self, DxfGreeksT, EventData};
+use crate::streaming::portfolio_state::signed_quantity;
+use crate::streaming::quote_streamer::QuoteStreamer;
+use crate::types::instrument::InstrumentType;
+use crate::{FullPosition, TastyResult};
+use rust_decimal::prelude::ToPrimitive;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// A position with the latest greeks of its option leg.
+#[derive(Debug, Clone, Serialize)]
+pub struct GreeksPosition {
+    /// The position as returned by the positions endpoint.
+    pub position: FullPosition,
+    /// DXLink symbol used for the greeks subscription, or the position's own symbol for
+    /// non-options, which are not subscribed.
+    pub streamer_symbol: DxFeedSymbol,
+    /// Latest per-contract greeks, `None` until the first event arrives or for non-options.
+    pub greeks: Option<DxfGreeksT>,
+}
+
+impl GreeksPosition {
+    /// Signed quantity times multiplier, the factor that turns per-contract greeks into position greeks.
+    fn exposure(&self) -> f64 {
+        let qty = signed_quantity(self.position.quantity, self.position.quantity_direction);
+        (qty * self.position.multiplier)
+            .to_f64()
+            .unwrap_or_default()
+    }
+
+    /// `greek` of the latest greeks times the exposure, `None` until greeks arrive or
+    /// while that greek is missing (NaN).
+    fn scaled(&self, greek: fn(&DxfGreeksT) -> Option<f64>) -> Option<f64> {
+        self.greeks
+            .as_ref()
+            .and_then(greek)
+            .map(|g| g * self.exposure())
+    }
+
+    /// Position delta.
+    pub fn position_delta(&self) -> Option<f64> {
+        self.scaled(DxfGreeksT::delta)
+    }
+
+    /// Position gamma.
+    pub fn position_gamma(&self) -> Option<f64> {
+        self.scaled(DxfGreeksT::gamma)
+    }
+
+    /// Position theta: the P&L change per day from time decay.
+    pub fn position_theta(&self) -> Option<f64> {
+        self.scaled(DxfGreeksT::theta)
+    }
+
+    /// Position vega: the P&L change per point of implied volatility.
+    pub fn position_vega(&self) -> Option<f64> {
+        self.scaled(DxfGreeksT::vega)
+    }
+}
+
+pub(crate) fn is_option(instrument_type: &InstrumentType) -> bool {
+    matches!(
+        instrument_type,
+        InstrumentType::EquityOption | InstrumentType::FutureOption
+    )
+}
+
+/// Handle returned by [`Account::live_greeks_positions`].
+///
+/// Updates are read from the streamer's subscription by a background task, which stops
+/// and closes the subscription once this handle (and every stream created from it) is
+/// dropped. The streamer must outlive the handle for updates to keep coming.
+pub struct LiveGreeksPositions {
+    receiver: flume::Receiver<GreeksPosition>,
+    latest: Arc<Mutex<HashMap<DxFeedSymbol, GreeksPosition>>>,
+}
+
+impl LiveGreeksPositions {
+    /// Waits for the next greeks update. Returns `None` once the stream has ended.
+    pub async fn recv(&self) -> Option<GreeksPosition> {
+        self.receiver.recv_async().await.ok()
+    }
+
+    /// Current view of every position, including those still waiting for greeks.
+    pub fn snapshot(&self) -> Vec<GreeksPosition> {
+        self.latest.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Converts the handle into a `Stream` of updates.
+    pub fn into_stream(self) -> flume::r#async::RecvStream<'static, GreeksPosition> {
+        self.receiver.into_stream()
+    }
+}
+
+impl Account<'_> {
+    /// Streams this account's positions enriched with live greeks for every option leg.
+    ///
+    /// Positions are fetched once; call again after the position set changes. The greeks
+    /// are subscribed on `streamer`, which can be shared between accounts.
+    pub async fn live_greeks_positions(
+        &self,
+        streamer: &mut QuoteStreamer,
+    ) -> TastyResult<LiveGreeksPositions> {
+        let positions = self.positions().await?;
+
+        let mut latest = HashMap::new();
+        let mut option_symbols = Vec::new();
+        for position in positions {
+            let streamer_symbol = if is_option(&position.instrument_type) {
+                let symbol = self
+                    .tasty
+                    .get_streamer_symbol(&position.instrument_type, &position.symbol)
+                    .await?;
+                option_symbols.push(symbol.clone());
+                symbol
+            } else {
+                DxFeedSymbol(position.symbol.0.clone())
+            };
+            latest.insert(
+                streamer_symbol.clone(),
+                GreeksPosition {
+                    position,
+                    streamer_symbol,
+                    greeks: None,
+                },
+            );
+        }
+        let latest = Arc::new(Mutex::new(latest));
+
+        let (sender, receiver) = flume::unbounded();
+        if !option_symbols.is_empty() {
+            let mut sub = streamer.create_sub(dxfeed::DXF_ET_GREEKS);
+            sub.add_symbols(&option_symbols);
+
+            let latest = latest.clone();
+            tokio::spawn(async move {
+                while let Ok(event) = sub.get_event().await {
+                    let EventData::Greeks(greeks) = event.data else {
+                        continue;
+                    };
+                    let update = {
+                        let mut latest = latest.lock().unwrap();
+                        let Some(entry) = latest.get_mut(&DxFeedSymbol(event.sym)) else {
+                            continue;
+                        };
+                        entry.greeks = Some(greeks);
+                        entry.clone()
+                    };
+                    if sender.send_async(update).await.is_err() {
+                        break;
+                    }
+                }
+                sub.close();
+                debug!("Live greeks positions stream terminated");
+            });
+        }
+
+        Ok(LiveGreeksPositions { receiver, latest })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_greeks_are_scaled_and_signed() {
+        let position = crate::test_fixtures::position(serde_json::json!({
+            "symbol": "AAPL  250117C00150000",
+            "instrument-type": "Equity Option",
+            "quantity": "2",
+            "quantity-direction": "Short",
+            "multiplier": 100.0,
+        }));
+        let mut entry = GreeksPosition {
+            position,
+            streamer_symbol: DxFeedSymbol(".AAPL250117C150".to_string()),
+            greeks: None,
+        };
+        assert!(is_option(&entry.position.instrument_type));
+        assert_eq!(entry.position_theta(), None);
+
+        entry.greeks = Some(DxfGreeksT {
+            event_flags: 0,
+            index: 0,
+            time: 0,
+            price: 3.5,
+            volatility: 0.25,
+            delta: 0.4,
+            gamma: 0.02,
+            theta: -0.05,
+            rho: 0.01,
+            vega: 0.12,
+        });
+        assert!((entry.position_delta().unwrap() + 80.0).abs() < 1e-9);
+        assert!((entry.position_theta().unwrap() - 10.0).abs() < 1e-9);
+        assert!((entry.position_vega().unwrap() + 24.0).abs() < 1e-9);
+
+        // A missing greek stays missing instead of scaling to NaN
+        entry.greeks.as_mut().unwrap().gamma = f64::NAN;
+        assert_eq!(entry.position_gamma(), None);
+        assert!(entry.position_delta().is_some());
+    }
+}
@@ -4,6 +4,35 @@
    Date: 5/3/25
 ******************************************************************************/
 
+pub mod cancellation;
+pub use cancellation::CancellationToken;
 pub mod quote_streamer;
 
+pub mod event_history;
+
+pub(crate) mod streamer_core;
+
+pub mod streamer_config;
+pub use streamer_config::StreamerConfig;
+
+pub mod subscription_group;
+
+pub mod subscription_builder;
+pub use subscription_builder::{EventKind, SubscriptionBuilder};
+
 pub mod account_streaming;
+
+pub mod greeks_positions;
+
+pub mod futures_pnl;
+
+pub mod offload;
+
+pub mod csv_logger;
+pub use csv_logger::{CsvLogger, LogFormat, Rotation};
+
+pub mod portfolio_state;
+
+pub mod order_tracker;
+
+pub mod bridge;
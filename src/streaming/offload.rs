@@ -0,0 +1,224 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Running per-event work off the streaming task.
+//!
+//! Quote events are received on a tokio task; a callback that prices a model or
+//! writes to disk for every tick would stall it and let the DXLink buffers fill up.
+//! [`QuoteSubscription::for_each_offloaded`] hands each event to an [`EventExecutor`]
+//! instead: the bundled [`ThreadPool`], or any pool of your own (rayon, a custom
+//! runtime, ...) through a closure.
+//!
+//! At most [`EventExecutor::queue_capacity`] events are queued or running at once.
+//! When the executor falls behind, the subscription stops reading until a slot frees
+//! up, so memory stays bounded. [`OffloadStats`] reports how long callbacks run and how
+//! long events wait in the queue, which is usually enough to size the pool.
+//!
+//! ```rust,ignore
+//! let pool = Arc::new(ThreadPool::new(4, 1_000));
+//! let handle = quote_sub.for_each_offloaded(|event| heavy_model(event), pool);
+//! // ...
+//! println!("mean callback time: {:?}", handle.stats().mean_busy_time());
+//! ```
+
+use crate::dxfeed;
+use crate::streaming::quote_streamer::QuoteSubscription;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+/// A unit of work submitted to an [`EventExecutor`].
+pub type Job = Box<dyn FnOnce() + Send + 'static>;
+
+const DEFAULT_QUEUE_CAPACITY: usize = 1_024;
+
+/// Runs jobs somewhere other than the streaming task.
+///
+/// Implemented for any `Fn(Job)` closure, e.g. `|job| rayon::spawn(job)`.
+pub trait EventExecutor: Send + Sync {
+    /// Runs `job`, typically on another thread. Must not block for long.
+    fn execute(&self, job: Job);
+
+    /// Maximum number of events queued or running at once.
+    fn queue_capacity(&self) -> usize {
+        DEFAULT_QUEUE_CAPACITY
+    }
+}
+
+impl<F: Fn(Job) + Send + Sync> EventExecutor for F {
+    fn execute(&self, job: Job) {
+        self(job)
+    }
+}
+
+/// A fixed set of dedicated worker threads.
+///
+/// Workers exit once the pool and every subscription using it are dropped.
+pub struct ThreadPool {
+    sender: flume::Sender<Job>,
+    queue_capacity: usize,
+}
+
+impl ThreadPool {
+    /// Starts `threads` workers (at least one) accepting up to `queue_capacity` pending events.
+    pub fn new(threads: usize, queue_capacity: usize) -> Self {
+        let (sender, receiver) = flume::unbounded::<Job>();
+        for i in 0..threads.max(1) {
+            let receiver = receiver.clone();
+            std::thread::Builder::new()
+                .name(format!("tastytrade-offload-{i}"))
+                .spawn(move || {
+                    while let Ok(job) = receiver.recv() {
+                        job();
+                    }
+                })
+                .expect("failed to spawn offload worker thread");
+        }
+        Self {
+            sender,
+            queue_capacity: queue_capacity.max(1),
+        }
+    }
+}
+
+impl EventExecutor for ThreadPool {
+    fn execute(&self, job: Job) {
+        // Workers only stop once the sender is dropped, so this cannot fail while `self` lives.
+        let _ = self.sender.send(job);
+    }
+
+    fn queue_capacity(&self) -> usize {
+        self.queue_capacity
+    }
+}
+
+/// Timing collected while events are processed off the streaming task.
+#[derive(Debug, Default)]
+pub struct OffloadStats {
+    events: AtomicU64,
+    busy_nanos: AtomicU64,
+    wait_nanos: AtomicU64,
+}
+
+impl OffloadStats {
+    fn record(&self, queue_wait: Duration, busy: Duration) {
+        self.events.fetch_add(1, Ordering::Relaxed);
+        self.wait_nanos
+            .fetch_add(queue_wait.as_nanos() as u64, Ordering::Relaxed);
+        self.busy_nanos
+            .fetch_add(busy.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Number of events whose callback has completed.
+    pub fn events(&self) -> u64 {
+        self.events.load(Ordering::Relaxed)
+    }
+
+    /// Total time spent inside the callback.
+    pub fn busy_time(&self) -> Duration {
+        Duration::from_nanos(self.busy_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Total time events waited between being received and the callback starting.
+    pub fn queue_wait_time(&self) -> Duration {
+        Duration::from_nanos(self.wait_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Average callback duration, `None` before the first event.
+    pub fn mean_busy_time(&self) -> Option<Duration> {
+        let events = self.events();
+        (events > 0).then(|| self.busy_time() / events as u32)
+    }
+}
+
+/// Handle to an offloaded subscription loop.
+pub struct OffloadHandle {
+    stats: Arc<OffloadStats>,
+    task: JoinHandle<()>,
+}
+
+impl OffloadHandle {
+    /// Processing statistics, updated live.
+    pub fn stats(&self) -> &OffloadStats {
+        &self.stats
+    }
+
+    /// Stops reading events. Jobs already submitted still run.
+    pub fn abort(&self) {
+        self.task.abort();
+    }
+
+    /// Waits until the subscription ends.
+    pub async fn join(self) {
+        let _ = self.task.await;
+    }
+}
+
+impl QuoteSubscription {
+    /// Consumes the subscription, running `callback` on `executor` for every event.
+    ///
+    /// See the [module documentation](crate::streaming::offload) for queuing behaviour.
+    pub fn for_each_offloaded<F, E>(mut self, callback: F, executor: Arc<E>) -> OffloadHandle
+    where
+        F: Fn(dxfeed::Event) + Send + Sync + 'static,
+        E: EventExecutor + ?Sized + 'static,
+    {
+        let callback = Arc::new(callback);
+        let stats = Arc::new(OffloadStats::default());
+        let slots = Arc::new(Semaphore::new(executor.queue_capacity().max(1)));
+
+        let task_stats = stats.clone();
+        let task = tokio::spawn(async move {
+            while let Ok(event) = self.get_event().await {
+                let Ok(slot) = slots.clone().acquire_owned().await else {
+                    break;
+                };
+                let callback = callback.clone();
+                let stats = task_stats.clone();
+                let received = Instant::now();
+                executor.execute(Box::new(move || {
+                    let started = Instant::now();
+                    callback(event);
+                    stats.record(started - received, started.elapsed());
+                    drop(slot);
+                }));
+            }
+        });
+
+        OffloadHandle { stats, task }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thread_pool_runs_jobs_and_records_stats() {
+        let pool = ThreadPool::new(2, 8);
+        assert_eq!(pool.queue_capacity(), 8);
+
+        let stats = Arc::new(OffloadStats::default());
+        let (done_tx, done_rx) = flume::unbounded();
+        for i in 0..4 {
+            let stats = stats.clone();
+            let done_tx = done_tx.clone();
+            pool.execute(Box::new(move || {
+                stats.record(Duration::from_millis(1), Duration::from_millis(2));
+                done_tx.send(i).unwrap();
+            }));
+        }
+
+        let mut seen: Vec<i32> = (0..4).map(|_| done_rx.recv().unwrap()).collect();
+        seen.sort();
+        assert_eq!(seen, vec![0, 1, 2, 3]);
+        assert_eq!(stats.events(), 4);
+        assert_eq!(stats.mean_busy_time(), Some(Duration::from_millis(2)));
+        assert_eq!(stats.queue_wait_time(), Duration::from_millis(4));
+    }
+}
@@ -0,0 +1,177 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Working orders kept up to date from account streaming.
+//!
+//! An [`OrderTracker`] is seeded with [`Account::live_orders`](crate::accounts::Account::live_orders)
+//! and then fed every [`AccountEvent`]. Orders leave the tracker as soon as they reach
+//! a final status (filled, cancelled, rejected, ...), so it always reflects what is
+//! still working on the account.
+
+use crate::streaming::account_streaming::{AccountEvent, AccountMessage};
+use crate::types::order::LiveOrderRecord;
+use crate::utils::clock::{SharedClock, system_clock};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A working order and when it was first seen.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrackedOrder {
+    /// Latest record received for the order.
+    pub record: LiveOrderRecord,
+    /// When the tracker first saw the order.
+    pub first_seen: DateTime<Utc>,
+    /// When the record was last updated.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TrackedOrder {
+    /// Time the order has been working, measured from `received-at` when the API
+    /// reports it and from the first time it was seen otherwise.
+    pub fn age(&self, now: DateTime<Utc>) -> Duration {
+        now - self.record.received_at.unwrap_or(self.first_seen)
+    }
+}
+
+/// Working orders by order id.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderTracker {
+    orders: BTreeMap<u64, TrackedOrder>,
+    #[serde(skip)]
+    clock: SharedClock,
+}
+
+impl Default for OrderTracker {
+    fn default() -> Self {
+        Self {
+            orders: BTreeMap::new(),
+            clock: system_clock(),
+        }
+    }
+}
+
+impl OrderTracker {
+    /// Creates an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Timestamps orders with `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Builds a tracker from a `live_orders` snapshot, keeping only working orders.
+    pub fn from_live_orders(records: Vec<LiveOrderRecord>) -> Self {
+        let mut tracker = Self::new();
+        for record in records {
+            tracker.update(record);
+        }
+        tracker
+    }
+
+    /// Inserts or refreshes an order, dropping it once its status is final.
+    pub fn update(&mut self, record: LiveOrderRecord) {
+        let id = record.id.0;
+        if !record.status.is_working() {
+            self.orders.remove(&id);
+            return;
+        }
+        let now = self.clock.now();
+        match self.orders.get_mut(&id) {
+            Some(tracked) => {
+                tracked.record = record;
+                tracked.updated_at = now;
+            }
+            None => {
+                self.orders.insert(
+                    id,
+                    TrackedOrder {
+                        record,
+                        first_seen: now,
+                        updated_at: now,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Applies a streamed account event. Only order messages are relevant.
+    pub fn apply_event(&mut self, event: &AccountEvent) {
+        if let AccountEvent::AccountMessage(message) = event {
+            self.apply(message);
+        }
+    }
+
+    /// Applies a streamed account message.
+    pub fn apply(&mut self, message: &AccountMessage) {
+        if let AccountMessage::Order(record) = message {
+            self.update(record.clone());
+        }
+    }
+
+    /// Working orders, oldest order id first.
+    pub fn working_orders(&self) -> impl Iterator<Item = &TrackedOrder> {
+        self.orders.values()
+    }
+
+    /// The working order with `id`, if any.
+    pub fn get(&self, id: u64) -> Option<&TrackedOrder> {
+        self.orders.get(&id)
+    }
+
+    /// Number of working orders.
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Returns `true` when no order is working.
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order_message(id: u64, status: &str) -> AccountMessage {
+        let json = format!(
+            r#"{{"type": "Order", "data": {{
+                "id": {id},
+                "account-number": "TEST123",
+                "time-in-force": "Day",
+                "order-type": "Limit",
+                "size": 1,
+                "underlying-symbol": "AAPL",
+                "price": "150.00",
+                "price-effect": "Debit",
+                "status": "{status}",
+                "cancellable": true,
+                "editable": true,
+                "edited": false
+            }}}}"#
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_final_status_removes_order() {
+        let mut tracker = OrderTracker::new();
+        tracker.apply(&order_message(1, "Received"));
+        tracker.apply(&order_message(2, "Live"));
+        tracker.apply(&order_message(1, "Live"));
+        assert_eq!(tracker.len(), 2);
+        assert!(tracker.get(1).unwrap().record.status.is_working());
+
+        tracker.apply(&order_message(1, "Filled"));
+        tracker.apply(&order_message(3, "Rejected"));
+        let ids: Vec<u64> = tracker.working_orders().map(|o| o.record.id.0).collect();
+        assert_eq!(ids, vec![2]);
+    }
+}
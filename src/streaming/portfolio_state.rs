@@ -0,0 +1,216 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Locally tracked positions and balances, kept up to date from account streaming.
+//!
+//! A [`PortfolioState`] is seeded from a REST snapshot and then fed every
+//! [`AccountEvent`] received from the [`AccountStreamer`](super::account_streaming::AccountStreamer).
+//! Long-running bots can read it without polling, and periodically check it against
+//! the server with [`Account::reconcile`](crate::accounts::Account::reconcile).
+
+use crate::streaming::account_streaming::{AccountEvent, AccountMessage};
+use crate::types::balance::Balance;
+use crate::types::order::Symbol;
+use crate::utils::clock::{SharedClock, system_clock};
+use crate::{FullPosition, QuantityDirection};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Signed quantity: positive for long positions, negative for short ones.
+pub(crate) fn signed_quantity(quantity: Decimal, direction: QuantityDirection) -> Decimal {
+    match direction {
+        QuantityDirection::Short => -quantity.abs(),
+        QuantityDirection::Long => quantity.abs(),
+        QuantityDirection::Zero => Decimal::ZERO,
+    }
+}
+
+/// Positions and balance figures as seen through the account stream.
+#[derive(Debug, Clone, Serialize)]
+pub struct PortfolioState {
+    /// Signed quantity per symbol. Closed positions are removed.
+    pub positions: HashMap<Symbol, Decimal>,
+    /// Last known cash balance.
+    pub cash_balance: Option<Decimal>,
+    /// Last known net liquidating value.
+    pub net_liquidating_value: Option<Decimal>,
+    /// Time of the last applied update.
+    pub last_update: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    clock: SharedClock,
+}
+
+impl Default for PortfolioState {
+    fn default() -> Self {
+        Self {
+            positions: HashMap::new(),
+            cash_balance: None,
+            net_liquidating_value: None,
+            last_update: None,
+            clock: system_clock(),
+        }
+    }
+}
+
+impl PortfolioState {
+    /// Creates an empty state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Timestamps updates with `clock` instead of the system clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns `true` when nothing was applied within `max_age`, or ever.
+    ///
+    /// A quiet account stream is normal, but a state that stays stale for long is
+    /// worth a [`reconcile`](crate::accounts::Account::reconcile).
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.last_update
+            .is_none_or(|last| self.clock.now() - last > max_age)
+    }
+
+    /// Builds a state from REST positions and, optionally, the account balance.
+    pub fn from_snapshot(positions: &[FullPosition], balance: Option<&Balance>) -> Self {
+        let mut state = Self::new();
+        state.reset(positions, balance);
+        state
+    }
+
+    /// Replaces the tracked positions (and balance, when given) with a REST snapshot.
+    ///
+    /// Entries of the same symbol, e.g. a long and a short lot, are summed as in
+    /// [`reconcile::diff`](crate::api::reconcile::diff).
+    pub fn reset(&mut self, positions: &[FullPosition], balance: Option<&Balance>) {
+        self.positions.clear();
+        for position in positions {
+            let qty = signed_quantity(position.quantity, position.quantity_direction);
+            if !qty.is_zero() {
+                *self.positions.entry(position.symbol.clone()).or_default() += qty;
+            }
+        }
+        self.positions.retain(|_, qty| !qty.is_zero());
+        if let Some(balance) = balance {
+            self.apply_balance(balance);
+        }
+        self.last_update = Some(self.clock.now());
+    }
+
+    /// Signed quantity held in `symbol`, zero when flat.
+    pub fn quantity(&self, symbol: &Symbol) -> Decimal {
+        self.positions.get(symbol).copied().unwrap_or_default()
+    }
+
+    /// Applies a streamed account event. Status and error messages are ignored.
+    pub fn apply_event(&mut self, event: &AccountEvent) {
+        if let AccountEvent::AccountMessage(message) = event {
+            self.apply(message);
+        }
+    }
+
+    /// Applies a streamed account message.
+    pub fn apply(&mut self, message: &AccountMessage) {
+        match message {
+            AccountMessage::CurrentPosition(position) => {
+                let qty = signed_quantity(position.quantity, position.quantity_direction);
+                if qty.is_zero() {
+                    self.positions.remove(&position.symbol);
+                } else {
+                    self.positions.insert(position.symbol.clone(), qty);
+                }
+            }
+            AccountMessage::AccountBalance(balance) => self.apply_balance(balance),
+            _ => return,
+        }
+        self.last_update = Some(self.clock.now());
+    }
+
+    fn apply_balance(&mut self, balance: &Balance) {
+        self.cash_balance = Some(balance.cash_balance);
+        self.net_liquidating_value = Some(balance.net_liquidating_value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::{Clock, MockClock};
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    fn position_message(symbol: &str, quantity: &str, direction: &str) -> AccountMessage {
+        let position = crate::test_fixtures::position_json(serde_json::json!({
+            "symbol": symbol,
+            "underlying-symbol": symbol,
+            "quantity": quantity,
+            "quantity-direction": direction,
+            "restricted-quantity": 0.0,
+        }));
+        let message = serde_json::json!({ "type": "CurrentPosition", "data": position });
+        serde_json::from_str(&message.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_apply_position_updates() {
+        let mut state = PortfolioState::new();
+        state.apply(&position_message("AAPL", "100", "Long"));
+        state.apply(&position_message("MSFT", "20", "Short"));
+
+        let aapl = Symbol("AAPL".to_string());
+        let msft = Symbol("MSFT".to_string());
+        assert_eq!(state.quantity(&aapl), Decimal::from(100));
+        assert_eq!(state.quantity(&msft), Decimal::from_str("-20").unwrap());
+        assert!(state.last_update.is_some());
+
+        state.apply(&position_message("AAPL", "0", "Zero"));
+        assert!(!state.positions.contains_key(&aapl));
+        assert_eq!(state.quantity(&aapl), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_reset_sums_duplicate_symbols() {
+        let lot = |symbol: &str, quantity: &str, direction: &str| {
+            crate::test_fixtures::position(serde_json::json!({
+                "symbol": symbol,
+                "quantity": quantity,
+                "quantity-direction": direction,
+            }))
+        };
+        let positions = [
+            lot("AAPL", "100", "Long"),
+            lot("AAPL", "30", "Short"),
+            lot("MSFT", "10", "Long"),
+            lot("MSFT", "10", "Short"),
+        ];
+
+        let state = PortfolioState::from_snapshot(&positions, None);
+        assert_eq!(
+            state.quantity(&Symbol("AAPL".to_string())),
+            Decimal::from(70)
+        );
+        assert!(!state.positions.contains_key(&Symbol("MSFT".to_string())));
+        assert!(crate::api::reconcile::diff(&state, &positions, None, Decimal::ZERO).is_empty());
+    }
+
+    #[test]
+    fn test_staleness_uses_injected_clock() {
+        let clock = MockClock::new("2024-06-03T14:30:00Z".parse().unwrap());
+        let mut state = PortfolioState::new().with_clock(Arc::new(clock.clone()));
+        assert!(state.is_stale(Duration::seconds(60)));
+
+        state.apply(&position_message("AAPL", "100", "Long"));
+        assert_eq!(state.last_update, Some(clock.now()));
+        clock.advance(std::time::Duration::from_secs(30));
+        assert!(!state.is_stale(Duration::seconds(60)));
+        clock.advance(std::time::Duration::from_secs(31));
+        assert!(state.is_stale(Duration::seconds(60)));
+    }
+}
@@ -1,181 +1,179 @@
 // For quote_streamer.rs
 use crate::TastyTrade;
+use crate::streaming::cancellation::CancellationToken;
+use crate::streaming::event_history::EventHistory;
+use crate::streaming::streamer_core::{StreamerAction, StreamerCore};
+use crate::streaming::subscription_builder::EventKind;
 use crate::types::dxfeed;
+use crate::types::dxfeed::DataQuality;
 use crate::{AsSymbol, Symbol, TastyResult, TastyTradeError};
 use dxlink::{DXLinkClient, EventType, FeedSubscription, MarketEvent};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use serde::Serialize;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, info, warn};
 
+/// Events buffered per subscription before its slowest receiver starts losing them.
+pub const SUBSCRIPTION_EVENT_CAPACITY: usize = 1024;
+
 #[derive(DebugPretty, DisplaySimple, Serialize, PartialEq, Eq, Hash, Clone, Copy)]
-pub struct SubscriptionId(usize);
+pub struct SubscriptionId(pub(crate) usize);
 
 pub struct QuoteSubscription {
     pub id: SubscriptionId,
-    streamer: Arc<Mutex<QuoteStreamer>>,
+    feed: SubscriptionFeed,
     event_types: i32, // Keep for compatibility with existing code
     event_receiver: flume::Receiver<dxfeed::Event>, // Keep for compatibility
-    dxlink_receiver: mpsc::Receiver<MarketEvent>, // New DXLink event receiver
-    symbols: Vec<Symbol>, // To track subscribed symbols
+    dxlink_receiver: broadcast::Receiver<dxfeed::Event>, // DXLink events, see `subscribe_events`
+    symbols: Arc<Mutex<Vec<Symbol>>>, // To track subscribed symbols, shared with clones
+    paused: Arc<AtomicBool>,
+    history: Arc<EventHistory>,
 }
 
 impl QuoteSubscription {
     /// Add symbols to subscription. See the "Note on symbology" section in [`QuoteSubscription`]
+    ///
+    /// While the subscription is paused the symbols are only recorded and get
+    /// subscribed upstream on [`resume`](Self::resume).
     pub fn add_symbols<S: AsSymbol>(&self, symbols: &[S]) {
         let symbols: Vec<Symbol> = symbols.iter().map(|sym| sym.as_symbol()).collect();
 
         // Update subscribed symbols internally
-        let mut my_symbols = Vec::new();
-        for sym in &symbols {
-            my_symbols.push(sym.clone());
+        if let Ok(mut my_symbols) = self.symbols.lock() {
+            for sym in &symbols {
+                if !my_symbols.contains(sym) {
+                    my_symbols.push(sym.clone());
+                }
+            }
+        }
+
+        if self.is_paused() {
+            return;
         }
 
         // Prepare subscription requests for DXLink
-        let subscriptions = symbols
-            .iter()
-            .flat_map(|sym| {
-                let mut requests = Vec::new();
-
-                // Transform dxfeed flags to DXLink event types
-                let event_flags = self.event_types;
-
-                if (event_flags & dxfeed::DXF_ET_QUOTE) != 0 {
-                    requests.push(FeedSubscription {
-                        event_type: "Quote".to_string(),
-                        symbol: sym.0.clone(),
-                        from_time: None,
-                        source: None,
-                    });
-                }
+        let subscriptions = feed_requests(self.event_types, &symbols);
+        self.feed.add(subscriptions);
+    }
 
-                if (event_flags & dxfeed::DXF_ET_TRADE) != 0 {
-                    requests.push(FeedSubscription {
-                        event_type: "Trade".to_string(),
-                        symbol: sym.0.clone(),
-                        from_time: None,
-                        source: None,
-                    });
-                }
+    /// Removes symbols from the subscription. Their feeds are unsubscribed upstream
+    /// unless another subscription of the streamer still wants them.
+    pub fn remove_symbols<S: AsSymbol>(&self, symbols: &[S]) {
+        let symbols: Vec<Symbol> = symbols.iter().map(|sym| sym.as_symbol()).collect();
+        if let Ok(mut my_symbols) = self.symbols.lock() {
+            my_symbols.retain(|sym| !symbols.contains(sym));
+        }
+        if !self.is_paused() {
+            self.feed.remove(feed_requests(self.event_types, &symbols));
+        }
+    }
 
-                if (event_flags & dxfeed::DXF_ET_GREEKS) != 0 {
-                    requests.push(FeedSubscription {
-                        event_type: "Greeks".to_string(),
-                        symbol: sym.0.clone(),
-                        from_time: None,
-                        source: None,
-                    });
-                }
+    /// Closes the subscription, like [`QuoteStreamer::close_sub`], for code that does
+    /// not hold the streamer, such as a task reading the subscription's events.
+    ///
+    /// Its feeds are unsubscribed upstream unless another subscription still wants them,
+    /// and every receiver of its events ends.
+    pub fn close(&self) {
+        self.feed.close();
+    }
 
-                requests
-            })
-            .collect::<Vec<FeedSubscription>>();
-
-        // Execute the subscription in a new async task
-        let streamer_clone = self.streamer.clone();
-        let subscriptions_clone = subscriptions.clone();
-
-        tokio::spawn(async move {
-            // Get the data we need from the mutex before awaiting
-            let (channel_id, tx) = {
-                if let Ok(streamer_guard) = streamer_clone.lock() {
-                    // Extract what we need from the guard
-                    let channel_id = streamer_guard.channel_id;
-                    let tx = streamer_guard.dxlink_command_tx.clone();
-                    (channel_id, tx)
-                } else {
-                    // If we can't lock the mutex, just return early
-                    return;
-                }
-            }; // MutexGuard is dropped here
-
-            // Now we're safe to await since we no longer hold the MutexGuard
-            if let (Some(channel_id), Some(tx)) = (channel_id, tx) {
-                // Send subscribe command through the channel
-                if !subscriptions_clone.is_empty()
-                    && let Err(e) = tx
-                        .send(DXLinkCommand::Subscribe(channel_id, subscriptions_clone))
-                        .await
-                {
-                    error!("Failed to send subscription command: {}", e);
-                }
-            }
-        });
+    /// Event kinds this subscription receives.
+    pub fn event_kinds(&self) -> Vec<EventKind> {
+        EventKind::from_flags(self.event_types)
+    }
+
+    /// Symbols added to this subscription so far.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        self.symbols
+            .lock()
+            .map(|symbols| symbols.clone())
+            .unwrap_or_default()
+    }
+
+    /// Stops the events of every symbol while keeping the symbol set, until
+    /// [`resume`](Self::resume) is called. Useful when a strategy is flat.
+    ///
+    /// Feeds are unsubscribed upstream unless another subscription of the streamer
+    /// still wants them; either way this subscription receives nothing while paused.
+    pub fn pause(&self) {
+        if self.paused.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.feed
+            .remove(feed_requests(self.event_types, &self.symbols()));
+    }
+
+    /// Subscribes again to every symbol dropped by [`pause`](Self::pause).
+    pub fn resume(&self) {
+        if !self.paused.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        self.feed
+            .add(feed_requests(self.event_types, &self.symbols()));
+    }
+
+    /// Returns `true` while the subscription is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Keeps the last `n` events of every symbol of this subscription, readable with
+    /// [`history`](Self::history). Calling it again resizes the buffers; zero stops
+    /// recording and forgets the events kept. See
+    /// [`event_history`](crate::streaming::event_history).
+    ///
+    /// Recording starts with the events received after the first call.
+    pub fn keep_last(&self, n: usize) {
+        self.history.set_capacity(n);
+        if n > 0 && self.history.start_recording() {
+            let history = self.history.clone();
+            let receiver = self.subscribe_events();
+            tokio::spawn(async move { history.record_from(receiver).await });
+        }
+    }
+
+    /// The last events of `symbol` kept since [`keep_last`](Self::keep_last), oldest
+    /// first. Empty when recording is off.
+    pub fn history(&self, symbol: impl AsSymbol) -> Vec<dxfeed::Event> {
+        self.history.get(&symbol.as_symbol().0)
+    }
+
+    /// Returns an additional receiver of this subscription's events.
+    ///
+    /// Every receiver gets every event, independently of the others and of
+    /// [`get_event`](Self::get_event). Each one starts with the events sent after it
+    /// was created. Events are buffered up to [`SUBSCRIPTION_EVENT_CAPACITY`] per
+    /// subscription: a receiver that falls further behind loses the oldest events and
+    /// its next `recv` returns `RecvError::Lagged` with the number skipped, then carries
+    /// on with the oldest event still buffered. A slow receiver never blocks the feed
+    /// or the other receivers. `recv` returns `RecvError::Closed` once the subscription
+    /// is closed and the buffer is drained.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<dxfeed::Event> {
+        self.dxlink_receiver.resubscribe()
     }
 
     /// Receive one event from feed. Yields if there are no events.
     /// Compatible with previous interface
+    ///
+    /// Events dropped because this subscription fell behind are logged and skipped;
+    /// see [`subscribe_events`](Self::subscribe_events).
     pub async fn get_event(&mut self) -> Result<dxfeed::Event, flume::RecvError> {
-        // Try to receive event from DXLink
-        match self.dxlink_receiver.recv().await {
-            Some(market_event) => {
-                // Convert from DXLink MarketEvent to dxfeed Event
-                match market_event {
-                    MarketEvent::Quote(quote) => {
-                        let symbol = quote.event_symbol;
-                        let data = dxfeed::EventData::Quote(dxfeed::DxfQuoteT {
-                            time: 0,
-                            sequence: 0,
-                            time_nanos: 0,
-                            bid_time: 0,
-                            bid_exchange_code: 0,
-                            bid_price: quote.bid_price,
-                            ask_price: quote.ask_price,
-                            bid_size: quote.bid_size as i64,
-                            ask_time: 0,
-                            ask_size: quote.ask_size as i64,
-                            ask_exchange_code: 0,
-                            scope: 0,
-                        });
-                        Ok(dxfeed::Event { sym: symbol, data })
-                    }
-                    MarketEvent::Trade(trade) => {
-                        // Convert Trade to dxfeed format
-                        let symbol = trade.event_symbol;
-                        let data = dxfeed::EventData::Trade(dxfeed::DxfTradeT {
-                            time: 0,
-                            sequence: 0,
-                            time_nanos: 0,
-                            exchange_code: 0,
-                            price: trade.price,
-                            size: trade.size as i64,
-
-                            tick: 0,
-                            change: 0.0,
-                            day_id: 0,
-                            day_volume: 0.0,
-                            day_turnover: 0.0,
-                            raw_flags: 0,
-                            direction: 0,
-                            is_eth: 0,
-                            scope: 0,
-                        });
-                        Ok(dxfeed::Event { sym: symbol, data })
-                    }
-                    MarketEvent::Greeks(greeks) => {
-                        // Convert Greeks to dxfeed format
-                        let symbol = greeks.event_symbol;
-                        let data = dxfeed::EventData::Greeks(dxfeed::DxfGreeksT {
-                            event_flags: 0,
-                            index: 0,
-                            time: 0,
-                            price: 0.0,
-                            volatility: 0.0,
-                            delta: greeks.delta,
-                            gamma: greeks.gamma,
-                            theta: greeks.theta,
-                            vega: greeks.vega,
-                            rho: greeks.rho,
-                        });
-                        Ok(dxfeed::Event { sym: symbol, data })
-                    }
+        loop {
+            match self.dxlink_receiver.recv().await {
+                Ok(event) => return Ok(event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(
+                        "Subscription {} fell behind, skipped {} events",
+                        self.id.0, skipped
+                    );
                 }
-            }
-            None => {
                 // Fallback to previous implementation
-                self.event_receiver.recv_async().await
+                Err(broadcast::error::RecvError::Closed) => {
+                    return self.event_receiver.recv_async().await;
+                }
             }
         }
     }
@@ -183,43 +181,193 @@ impl QuoteSubscription {
 
 impl Clone for QuoteSubscription {
     fn clone(&self) -> Self {
-        // Create a new channel for DXLink events
-        let (tx, rx) = mpsc::channel(100);
-
-        // Register this new channel with the streamer
-        if let Ok(streamer) = self.streamer.lock()
-            && let Some(cmd_tx) = &streamer.dxlink_command_tx
-        {
-            let cmd_tx_clone = cmd_tx.clone();
-            let sub_id = self.id.0;
-
-            tokio::spawn(async move {
-                if let Err(e) = cmd_tx_clone
-                    .send(DXLinkCommand::AddEventSender(sub_id as u32, tx))
-                    .await
-                {
-                    error!("Failed to register cloned event sender: {}", e);
-                }
-            });
-        }
-
         Self {
             id: self.id,
-            streamer: self.streamer.clone(),
+            feed: self.feed.clone(),
             event_types: self.event_types,
             event_receiver: self.event_receiver.clone(), // This requires flume::Receiver to implement Clone
-            dxlink_receiver: rx,
+            dxlink_receiver: self.subscribe_events(),
             symbols: self.symbols.clone(),
+            paused: self.paused.clone(),
+            history: self.history.clone(),
+        }
+    }
+}
+
+/// Converts a DXLink event into the `dxfeed` form handed to subscribers, tagged with the
+/// `quality` of the feed.
+fn to_dxfeed_event(market_event: MarketEvent, quality: DataQuality) -> dxfeed::Event {
+    match market_event {
+        MarketEvent::Quote(quote) => {
+            let symbol = quote.event_symbol;
+            let data = dxfeed::EventData::Quote(dxfeed::DxfQuoteT {
+                time: 0,
+                sequence: 0,
+                time_nanos: 0,
+                bid_time: 0,
+                bid_exchange_code: 0,
+                bid_price: quote.bid_price,
+                ask_price: quote.ask_price,
+                bid_size: quote.bid_size as i64,
+                ask_time: 0,
+                ask_size: quote.ask_size as i64,
+                ask_exchange_code: 0,
+                scope: 0,
+            });
+            dxfeed::Event {
+                sym: symbol,
+                data,
+                quality,
+            }
+        }
+        MarketEvent::Trade(trade) => {
+            // Convert Trade to dxfeed format
+            let symbol = trade.event_symbol;
+            let data = dxfeed::EventData::Trade(dxfeed::DxfTradeT {
+                time: 0,
+                sequence: 0,
+                time_nanos: 0,
+                exchange_code: 0,
+                price: trade.price,
+                size: trade.size as i64,
+
+                tick: 0,
+                change: 0.0,
+                day_id: 0,
+                day_volume: 0.0,
+                day_turnover: 0.0,
+                raw_flags: 0,
+                direction: 0,
+                is_eth: 0,
+                scope: 0,
+            });
+            dxfeed::Event {
+                sym: symbol,
+                data,
+                quality,
+            }
         }
+        MarketEvent::Greeks(greeks) => {
+            // Convert Greeks to dxfeed format. `price` is the theoretical option
+            // price and `volatility` its implied volatility; `time` is in ms since epoch.
+            let symbol = greeks.event_symbol;
+            let data = dxfeed::EventData::Greeks(dxfeed::DxfGreeksT {
+                event_flags: 0,
+                index: 0,
+                time: greeks.time,
+                price: greeks.price,
+                volatility: greeks.volatility,
+                delta: greeks.delta,
+                gamma: greeks.gamma,
+                theta: greeks.theta,
+                vega: greeks.vega,
+                rho: greeks.rho,
+            });
+            dxfeed::Event {
+                sym: symbol,
+                data,
+                quality,
+            }
+        }
+    }
+}
+
+/// DXLink event type name of `data`.
+pub(crate) fn event_type_name(data: &dxfeed::EventData) -> &'static str {
+    match data {
+        dxfeed::EventData::Quote(_) => "Quote",
+        dxfeed::EventData::Trade(_) => "Trade",
+        dxfeed::EventData::Greeks(_) => "Greeks",
+    }
+}
+
+/// DXLink event type names selected by a `dxfeed::DXF_ET_*` bit mask.
+pub(crate) fn event_type_names(flags: i32) -> Vec<&'static str> {
+    [
+        (dxfeed::DXF_ET_QUOTE, "Quote"),
+        (dxfeed::DXF_ET_TRADE, "Trade"),
+        (dxfeed::DXF_ET_GREEKS, "Greeks"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| flags & flag != 0)
+    .map(|(_, name)| name)
+    .collect()
+}
+
+/// DXLink feed request for one event type and symbol.
+pub(crate) fn feed_request(event_type: &str, symbol: &Symbol) -> FeedSubscription {
+    FeedSubscription {
+        event_type: event_type.to_string(),
+        symbol: symbol.0.clone(),
+        from_time: None,
+        source: None,
     }
 }
 
-// Commands for DXLink client to execute
-enum DXLinkCommand {
-    Subscribe(u32, Vec<FeedSubscription>),
-    Unsubscribe(u32, Vec<FeedSubscription>),
+/// Builds one DXLink feed request per event type in `flags` and symbol.
+pub(crate) fn feed_requests(flags: i32, symbols: &[Symbol]) -> Vec<FeedSubscription> {
+    let event_types = event_type_names(flags);
+    symbols
+        .iter()
+        .flat_map(|sym| {
+            event_types
+                .iter()
+                .map(move |event_type| feed_request(event_type, sym))
+        })
+        .collect()
+}
+
+/// Sends the feed requests of one subscription to the DXLink task, which counts them
+/// against those of the other subscriptions. See [`StreamerCore`].
+///
+/// The default handle is not connected to any streamer and ignores every request.
+#[derive(Clone, Default)]
+struct SubscriptionFeed {
+    subscription_id: u32,
+    command_tx: Option<mpsc::UnboundedSender<DXLinkCommand>>,
+}
+
+impl SubscriptionFeed {
+    fn add(&self, requests: Vec<FeedSubscription>) {
+        self.send(DXLinkCommand::AddFeeds, requests);
+    }
+
+    fn remove(&self, requests: Vec<FeedSubscription>) {
+        self.send(DXLinkCommand::RemoveFeeds, requests);
+    }
+
+    fn close(&self) {
+        if let Some(tx) = &self.command_tx
+            && let Err(e) = tx.send(DXLinkCommand::RemoveEventSender(self.subscription_id))
+        {
+            error!("Error unregistering event sender: {}", e);
+        }
+    }
+
+    fn send(
+        &self,
+        command: fn(u32, Vec<FeedSubscription>) -> DXLinkCommand,
+        requests: Vec<FeedSubscription>,
+    ) {
+        if requests.is_empty() {
+            return;
+        }
+        if let Some(tx) = &self.command_tx
+            && let Err(e) = tx.send(command(self.subscription_id, requests))
+        {
+            error!("Failed to send DXLink command: {}", e);
+        }
+    }
+}
+
+// Commands for DXLink client to execute, applied by `StreamerCore`
+pub(crate) enum DXLinkCommand {
+    /// Feeds wanted by a subscription, by subscription id
+    AddFeeds(u32, Vec<FeedSubscription>),
+    /// Feeds a subscription no longer wants, by subscription id
+    RemoveFeeds(u32, Vec<FeedSubscription>),
     CreateEventStream,
-    AddEventSender(u32, mpsc::Sender<MarketEvent>),
+    AddEventSender(u32, broadcast::Sender<dxfeed::Event>),
     RemoveEventSender(u32),
     Disconnect,
 }
@@ -229,15 +377,32 @@ pub struct QuoteStreamer {
     dxlink_client: Option<DXLinkClient>,
     channel_id: Option<u32>,
     subscriptions: Arc<Mutex<HashMap<Symbol, Vec<String>>>>,
-    next_sub_id: usize,
+    /// Source of subscription ids, shared with clones: the DXLink task routes events and
+    /// counts feeds by id, so ids must be unique per connection.
+    next_sub_id: Arc<AtomicUsize>,
     subscription_map: HashMap<SubscriptionId, QuoteSubscription>,
-    dxlink_command_tx: Option<mpsc::Sender<DXLinkCommand>>,
+    dxlink_command_tx: Option<mpsc::UnboundedSender<DXLinkCommand>>,
+    quality: DataQuality,
+    cancellation: CancellationToken,
 }
 
 impl QuoteStreamer {
     pub async fn connect(tasty: &TastyTrade) -> TastyResult<Self> {
+        Self::connect_with_cancellation(tasty, CancellationToken::new()).await
+    }
+
+    /// Like [`connect`](Self::connect), stopping the DXLink tasks once `cancellation`
+    /// is cancelled. See [`cancellation`](crate::streaming::cancellation).
+    pub async fn connect_with_cancellation(
+        tasty: &TastyTrade,
+        cancellation: CancellationToken,
+    ) -> TastyResult<Self> {
         let tokens = tasty.quote_streamer_tokens().await?;
         debug!("Obtained tokens for DXLink: {}", tokens.token);
+        let quality = tokens.data_quality();
+        if quality.is_delayed() {
+            warn!("No real-time market data entitlement, streaming delayed quotes");
+        }
 
         // Create DXLink client
         let mut client = DXLinkClient::new(&tokens.streamer_url, &tokens.token);
@@ -277,75 +442,69 @@ impl QuoteStreamer {
             )));
         }
 
-        // Create command channel
-        let (command_tx, mut command_rx) = mpsc::channel::<DXLinkCommand>(100);
-
-        // Spawn task to handle DXLink commands
-        // Spawn task to handle DXLink commands
-        tokio::spawn(async move {
-            // Map to store event forwarding channels by subscription ID
-            let mut event_senders: HashMap<u32, Vec<mpsc::Sender<MarketEvent>>> = HashMap::new();
-            let _event_stream: Option<mpsc::Receiver<MarketEvent>> = None;
-
-            while let Some(cmd) = command_rx.recv().await {
-                match cmd {
-                    DXLinkCommand::Subscribe(channel_id, subscriptions) => {
-                        if let Err(e) = client.subscribe(channel_id, subscriptions).await {
-                            error!("Error subscribing to symbols: {}", e);
-                        }
-                    }
-                    DXLinkCommand::Unsubscribe(channel_id, subscriptions) => {
-                        if let Err(e) = client.unsubscribe(channel_id, subscriptions).await {
-                            error!("Error unsubscribing from symbols: {}", e);
-                        }
-                    }
-                    DXLinkCommand::CreateEventStream => {
-                        match client.event_stream() {
-                            Ok(mut rx) => {
-                                debug!("Successfully created event stream");
-                                // Clone the map of senders for use in the task
-                                let senders = event_senders.clone();
-
-                                // Move rx directly into the spawned task
-                                tokio::spawn(async move {
-                                    // Use rx directly, don't try to borrow from event_stream
-                                    while let Some(event) = rx.recv().await {
-                                        // Determine which symbol this event is for
-                                        let _symbol = match &event {
-                                            MarketEvent::Quote(quote) => &quote.event_symbol,
-                                            MarketEvent::Trade(trade) => &trade.event_symbol,
-                                            MarketEvent::Greeks(greeks) => &greeks.event_symbol,
-                                        };
-
-                                        // Forward to all interested subscriptions
-                                        for sender_list in senders.values() {
-                                            for sender in sender_list {
-                                                // Try to send, but don't block if receiver is full
-                                                let _ = sender.try_send(event.clone());
+        // Create command channel. Unbounded so commands are queued in the order they
+        // are issued, without a runtime or an await.
+        let (command_tx, mut command_rx) = mpsc::unbounded_channel::<DXLinkCommand>();
+        let non_finite = tasty.config.streamer.non_finite;
+
+        // Spawn task to drive the DXLink client from the streamer core
+        let task_cancellation = cancellation.clone();
+        cancellation.spawn("DXLink command handler", async move {
+            let mut core = StreamerCore::new(channel_id);
+            // Converted events of the DXLink stream, forwarded by a reader task
+            let (event_tx, mut event_rx) = mpsc::unbounded_channel::<dxfeed::Event>();
+
+            loop {
+                tokio::select! {
+                    command = command_rx.recv() => {
+                        let Some(command) = command else { break };
+                        let Some(action) = core.handle_command(command) else { continue };
+                        match action {
+                            StreamerAction::Subscribe(channel_id, subscriptions) => {
+                                if let Err(e) = client.subscribe(channel_id, subscriptions).await {
+                                    error!("Error subscribing to symbols: {}", e);
+                                }
+                            }
+                            StreamerAction::Unsubscribe(channel_id, subscriptions) => {
+                                if let Err(e) = client.unsubscribe(channel_id, subscriptions).await {
+                                    error!("Error unsubscribing from symbols: {}", e);
+                                }
+                            }
+                            StreamerAction::OpenEventStream => match client.event_stream() {
+                                Ok(mut rx) => {
+                                    debug!("Successfully created event stream");
+                                    let event_tx = event_tx.clone();
+                                    task_cancellation.spawn("DXLink event reader", async move {
+                                        while let Some(event) = rx.recv().await {
+                                            let event = to_dxfeed_event(event, quality).sanitize(non_finite);
+                                            let Some(event) = event else {
+                                                debug!("Dropping event with a non-finite value");
+                                                continue;
+                                            };
+                                            if event_tx.send(event).is_err() {
+                                                break;
                                             }
                                         }
-                                    }
-                                });
-                            }
-                            Err(e) => {
-                                error!("Failed to create event stream: {}", e);
+                                        debug!("DXLink event stream ended");
+                                    });
+                                }
+                                Err(e) => {
+                                    error!("Failed to create event stream: {}", e);
+                                    core.event_stream_closed();
+                                }
+                            },
+                            StreamerAction::Disconnect => {
+                                if let Err(e) = client.disconnect().await {
+                                    warn!("Error disconnecting from DXLink: {}", e);
+                                }
                             }
                         }
-                    }
-                    DXLinkCommand::Disconnect => {
-                        if let Err(e) = client.disconnect().await {
-                            warn!("Error disconnecting from DXLink: {}", e);
+                        if core.is_disconnected() {
+                            break;
                         }
-                        break; // Exit the loop after disconnecting
                     }
-                    DXLinkCommand::AddEventSender(subscription_id, sender) => {
-                        let senders = event_senders.entry(subscription_id).or_default();
-                        senders.push(sender);
-                        debug!("Added event sender for subscription {}", subscription_id);
-                    }
-                    DXLinkCommand::RemoveEventSender(subscription_id) => {
-                        event_senders.remove(&subscription_id);
-                        debug!("Removed event senders for subscription {}", subscription_id);
+                    Some(event) = event_rx.recv() => {
+                        core.handle_event(event);
                     }
                 }
             }
@@ -356,62 +515,64 @@ impl QuoteStreamer {
             dxlink_client: None, // We moved client into the command handler task
             channel_id: Some(channel_id),
             subscriptions: Arc::new(Mutex::new(HashMap::new())),
-            next_sub_id: 0,
+            next_sub_id: Arc::default(),
             subscription_map: HashMap::new(),
             dxlink_command_tx: Some(command_tx),
+            quality,
+            cancellation,
         })
     }
 
+    /// The token stopping the DXLink tasks of this streamer.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+
+    /// Whether the quote token grants live or delayed data. Every event of this streamer
+    /// carries the same [`quality`](dxfeed::Event::quality).
+    pub fn data_quality(&self) -> DataQuality {
+        self.quality
+    }
+
     /// Create a subscription to market data. See `dxfeed::DXF_ET_*` for possible event types.
+    ///
+    /// [`subscription`](Self::subscription) offers the same with named event kinds.
     pub fn create_sub(&mut self, flags: i32) -> Box<QuoteSubscription> {
-        let id = SubscriptionId(self.next_sub_id);
-        self.next_sub_id += 1;
+        let id = SubscriptionId(self.next_sub_id.fetch_add(1, Ordering::SeqCst));
 
         // Set up channels for events
-        let (dxlink_tx, dxlink_rx) = mpsc::channel(100);
+        let (dxlink_tx, dxlink_rx) = broadcast::channel(SUBSCRIPTION_EVENT_CAPACITY);
         let (_event_sender, event_receiver) = flume::unbounded();
 
         // Register event sender if we have a command channel
+        let sub_id = id.0 as u32;
         if let Some(client_tx) = &self.dxlink_command_tx {
-            let client_tx_clone = client_tx.clone();
-            let sub_id = self.next_sub_id - 1; // Use the ID we just assigned
-
-            // Register the sender
-            let send_task = async move {
-                if let Err(e) = client_tx_clone
-                    .send(DXLinkCommand::AddEventSender(sub_id as u32, dxlink_tx))
-                    .await
-                {
-                    error!("Failed to register event sender: {}", e);
-                }
-            };
-
-            // Use tokio::task::spawn_local or equivalent if available, or handle differently
-            tokio::spawn(send_task);
+            if let Err(e) = client_tx.send(DXLinkCommand::AddEventSender(sub_id, dxlink_tx)) {
+                error!("Failed to register event sender: {}", e);
+            }
 
             // Create a separate event stream from the DXLink client if this is the first subscription
             if self.subscription_map.is_empty() && self.channel_id.is_some() {
-                let stream_tx_clone = client_tx.clone();
-                let stream_task = async move {
-                    // Send command to set up event stream
-                    match stream_tx_clone.send(DXLinkCommand::CreateEventStream).await {
-                        Ok(_) => debug!("Successfully requested event stream"),
-                        Err(e) => error!("Failed to request event stream: {}", e),
-                    }
-                };
-
-                tokio::spawn(stream_task);
+                match client_tx.send(DXLinkCommand::CreateEventStream) {
+                    Ok(_) => debug!("Successfully requested event stream"),
+                    Err(e) => error!("Failed to request event stream: {}", e),
+                }
             }
         }
 
         // Create subscription
         let subscription = QuoteSubscription {
             id,
-            streamer: Arc::new(Mutex::new(self.clone())), // Clone self
+            feed: SubscriptionFeed {
+                subscription_id: sub_id,
+                command_tx: self.dxlink_command_tx.clone(),
+            },
             event_types: flags,
             event_receiver,
             dxlink_receiver: dxlink_rx,
-            symbols: Vec::new(),
+            symbols: Arc::new(Mutex::new(Vec::new())),
+            paused: Arc::new(AtomicBool::new(false)),
+            history: Arc::default(),
         };
 
         // Store subscription in map and return a boxed clone
@@ -427,80 +588,16 @@ impl QuoteStreamer {
     }
 
     /// Close and remove subscription by id.
-    /// Close and remove subscription by id.
+    ///
+    /// Its feeds are unsubscribed upstream unless another subscription still wants them.
     pub fn close_sub(&mut self, id: SubscriptionId) {
-        // Get symbols from subscription to close
-        if let Some(subscription) = self.subscription_map.get(&id) {
-            let symbols = subscription.symbols.clone();
-
-            // Prepare unsubscribe requests
-            let unsubscribe_requests = symbols
-                .iter()
-                .flat_map(|sym| {
-                    let mut requests = Vec::new();
-                    let event_flags = subscription.event_types;
-
-                    if (event_flags & dxfeed::DXF_ET_QUOTE) != 0 {
-                        requests.push(FeedSubscription {
-                            event_type: "Quote".to_string(),
-                            symbol: sym.0.clone(),
-                            from_time: None,
-                            source: None,
-                        });
-                    }
-
-                    if (event_flags & dxfeed::DXF_ET_TRADE) != 0 {
-                        requests.push(FeedSubscription {
-                            event_type: "Trade".to_string(),
-                            symbol: sym.0.clone(),
-                            from_time: None,
-                            source: None,
-                        });
-                    }
-
-                    if (event_flags & dxfeed::DXF_ET_GREEKS) != 0 {
-                        requests.push(FeedSubscription {
-                            event_type: "Greeks".to_string(),
-                            symbol: sym.0.clone(),
-                            from_time: None,
-                            source: None,
-                        });
-                    }
-
-                    requests
-                })
-                .collect::<Vec<FeedSubscription>>();
-
-            // Execute unsubscribe via command channel
-            if let (Some(tx), Some(channel_id)) = (&self.dxlink_command_tx, self.channel_id) {
-                let tx_clone = tx.clone();
-                let channel = channel_id;
-                let requests = unsubscribe_requests.clone();
-                let sub_id = id.0;
-
-                tokio::spawn(async move {
-                    // Unregister the event sender
-                    if let Err(e) = tx_clone
-                        .send(DXLinkCommand::RemoveEventSender(sub_id as u32))
-                        .await
-                    {
-                        error!("Error unregistering event sender: {}", e);
-                    }
-
-                    // Unsubscribe from symbols
-                    if !requests.is_empty()
-                        && let Err(e) = tx_clone
-                            .send(DXLinkCommand::Unsubscribe(channel, requests))
-                            .await
-                    {
-                        error!("Error sending unsubscribe command: {}", e);
-                    }
-                });
-            }
+        // Unregistering the event sender releases the subscription's feeds
+        if self.subscription_map.remove(&id).is_some()
+            && let Some(tx) = &self.dxlink_command_tx
+            && let Err(e) = tx.send(DXLinkCommand::RemoveEventSender(id.0 as u32))
+        {
+            error!("Error unregistering event sender: {}", e);
         }
-
-        // Remove subscription from map
-        self.subscription_map.remove(&id);
     }
 
     pub fn subscribe(&self, _symbol: &[&str]) {
@@ -524,9 +621,11 @@ impl Clone for QuoteStreamer {
             dxlink_client: None, // Don't clone the client
             channel_id: self.channel_id,
             subscriptions: self.subscriptions.clone(),
-            next_sub_id: self.next_sub_id,
+            next_sub_id: self.next_sub_id.clone(),
             subscription_map: HashMap::new(), // Create a new empty map
             dxlink_command_tx: self.dxlink_command_tx.clone(),
+            quality: self.quality,
+            cancellation: self.cancellation.clone(),
         }
     }
 }
@@ -539,15 +638,180 @@ impl Drop for QuoteStreamer {
             self.close_sub(id);
         }
 
-        // Signal disconnection
-        if let Some(tx) = &self.dxlink_command_tx {
-            let tx_clone = tx.clone();
+        // Signal disconnection, unless the DXLink task is already gone
+        if let Some(tx) = &self.dxlink_command_tx
+            && let Err(e) = tx.send(DXLinkCommand::Disconnect)
+        {
+            debug!("Error sending disconnect command: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+impl QuoteStreamer {
+    /// A streamer without a DXLink connection, queueing its commands for channel 3 on
+    /// `command_tx` when given.
+    pub(crate) fn with_commands(command_tx: Option<mpsc::UnboundedSender<DXLinkCommand>>) -> Self {
+        Self {
+            dxlink_client: None,
+            channel_id: command_tx.as_ref().map(|_| 3),
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            next_sub_id: Arc::default(),
+            subscription_map: HashMap::new(),
+            dxlink_command_tx: command_tx,
+            quality: DataQuality::Realtime,
+            cancellation: CancellationToken::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            tokio::spawn(async move {
-                if let Err(e) = tx_clone.send(DXLinkCommand::Disconnect).await {
-                    warn!("Error sending disconnect command: {}", e);
+    fn disconnected_streamer() -> QuoteStreamer {
+        QuoteStreamer::with_commands(None)
+    }
+
+    #[test]
+    fn test_pause_resume_preserves_symbols() {
+        let mut streamer = disconnected_streamer();
+        let sub = streamer.create_sub(dxfeed::DXF_ET_QUOTE);
+        sub.add_symbols(&["SPY", "QQQ", "SPY"]);
+        assert_eq!(sub.symbols().len(), 2);
+
+        sub.pause();
+        assert!(sub.is_paused());
+        assert!(streamer.get_sub(sub.id).unwrap().is_paused());
+        sub.add_symbols(&["IWM"]);
+
+        sub.resume();
+        assert!(!sub.is_paused());
+        assert_eq!(
+            sub.symbols(),
+            vec![
+                Symbol("SPY".into()),
+                Symbol("QQQ".into()),
+                Symbol("IWM".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paused_subscription_keeps_shared_feeds() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut streamer = QuoteStreamer::with_commands(Some(tx));
+        let a = streamer.create_sub(dxfeed::DXF_ET_QUOTE);
+        let b = streamer.create_sub(dxfeed::DXF_ET_QUOTE);
+        a.add_symbols(&["SPY"]);
+        b.add_symbols(&["SPY", "QQQ"]);
+        a.pause();
+
+        // Replays the commands sent so far, returning the requests subscribed and
+        // unsubscribed upstream
+        let mut core = StreamerCore::new(3);
+        let mut replay = |core: &mut StreamerCore| {
+            let (mut subscribed, mut unsubscribed) = (Vec::new(), Vec::new());
+            while let Ok(command) = rx.try_recv() {
+                match core.handle_command(command) {
+                    Some(StreamerAction::Subscribe(_, requests)) => subscribed.extend(requests),
+                    Some(StreamerAction::Unsubscribe(_, requests)) => unsubscribed.extend(requests),
+                    _ => {}
                 }
-            });
+            }
+            (subscribed.len(), unsubscribed.len())
+        };
+        // `b` still wants Quote/SPY, so pausing `a` unsubscribes nothing
+        assert_eq!(replay(&mut core), (2, 0));
+
+        let mut a_events = a.subscribe_events();
+        let mut b_events = b.subscribe_events();
+        assert_eq!(
+            core.handle_event(dxfeed::Event::new_quote("SPY".into(), Default::default())),
+            1
+        );
+        assert!(a_events.try_recv().is_err());
+        assert_eq!(b_events.try_recv().unwrap().sym, "SPY");
+
+        // Resuming `a` before `b` closes keeps Quote/SPY subscribed throughout
+        b.remove_symbols(&["QQQ"]);
+        a.resume();
+        streamer.close_sub(b.id);
+        assert_eq!(replay(&mut core), (0, 1));
+    }
+
+    #[test]
+    fn test_clones_hand_out_distinct_subscription_ids() {
+        let mut streamer = disconnected_streamer();
+        let mut clone = streamer.clone();
+        let a = streamer.create_sub(dxfeed::DXF_ET_QUOTE);
+        let b = clone.create_sub(dxfeed::DXF_ET_QUOTE);
+        let c = streamer.create_sub(dxfeed::DXF_ET_QUOTE);
+        assert_ne!(a.id, b.id);
+        assert_ne!(b.id, c.id);
+        assert_ne!(a.id, c.id);
+    }
+
+    #[test]
+    fn test_subscription_builder_selects_event_kinds() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mut streamer = disconnected_streamer();
+
+        let sub = runtime
+            .block_on(
+                streamer
+                    .subscription()
+                    .quotes()
+                    .greeks()
+                    .quotes()
+                    .symbols(["AAPL", "SPY"])
+                    .start(),
+            )
+            .unwrap();
+        assert_eq!(sub.event_kinds(), vec![EventKind::Quote, EventKind::Greeks]);
+        assert_eq!(sub.symbols().len(), 2);
+        assert_eq!(
+            event_type_names(EventKind::Trade.flag() | EventKind::Greeks.flag()),
+            vec!["Trade", "Greeks"]
+        );
+
+        let err = runtime.block_on(streamer.subscription().symbols(["AAPL"]).start());
+        assert!(matches!(err, Err(TastyTradeError::Streaming(_))));
+    }
+
+    #[test]
+    fn test_subscribe_events_fans_out_and_reports_lag() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (tx, rx) = broadcast::channel(2);
+        let (flume_tx, flume_rx) = flume::unbounded();
+        let mut sub = QuoteSubscription {
+            id: SubscriptionId(0),
+            feed: SubscriptionFeed::default(),
+            event_types: dxfeed::DXF_ET_QUOTE,
+            event_receiver: flume_rx,
+            dxlink_receiver: rx,
+            symbols: Arc::new(Mutex::new(Vec::new())),
+            paused: Arc::new(AtomicBool::new(false)),
+            history: Arc::default(),
+        };
+        let mut extra = sub.subscribe_events();
+        let quote = |sym: &str| dxfeed::Event::new_quote(sym.to_string(), Default::default());
+
+        for sym in ["SPY", "QQQ", "IWM"] {
+            tx.send(quote(sym)).unwrap();
         }
+        runtime.block_on(async {
+            assert_eq!(sub.get_event().await.unwrap().sym, "QQQ");
+            assert_eq!(sub.get_event().await.unwrap().sym, "IWM");
+            assert!(matches!(
+                extra.recv().await,
+                Err(broadcast::error::RecvError::Lagged(1))
+            ));
+            assert_eq!(extra.recv().await.unwrap().sym, "QQQ");
+
+            drop(tx);
+            drop(flume_tx);
+            assert!(sub.get_event().await.is_err());
+        });
     }
 }
@@ -0,0 +1,131 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Options of the streaming connections.
+//!
+//! [`StreamerConfig`] lives in [`TastyTradeConfig::streamer`](crate::utils::config::TastyTradeConfig::streamer).
+//! Its socket options only cover the account websocket, which applies them when it connects:
+//! latency-sensitive deployments usually turn on `tcp_nodelay`; links where idle
+//! connections are dropped by a NAT or a firewall benefit from `tcp_keepalive`.
+//! `non_finite` is applied by the quote streamer and decides what happens to events
+//! carrying NaN prices.
+//!
+//! ```rust,ignore
+//! config.streamer = StreamerConfig::default().tcp_nodelay(true).tcp_keepalive(true);
+//! let tasty = TastyTrade::login(&config).await?;
+//! let streamer = tasty.create_account_streamer().await?;
+//! ```
+//!
+//! The DXLink connection of the quote streamer is opened by the `dxlink` client, which
+//! keeps its own socket settings: `tcp_nodelay` and `tcp_keepalive` do not reach it.
+
+use crate::types::dxfeed::NonFinitePolicy;
+use crate::{TastyResult, TastyTradeError};
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpSocket, TcpStream, lookup_host};
+use tokio_tungstenite::tungstenite::http::Uri;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, client_async_tls};
+
+/// Options applied to the streaming connections.
+///
+/// `tcp_nodelay` and `tcp_keepalive` are only applied to the account websocket; the
+/// DXLink connection of the quote streamer ignores them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct StreamerConfig {
+    /// Disable Nagle's algorithm on the account websocket, sending small frames
+    /// immediately.
+    pub tcp_nodelay: bool,
+    /// Enable `SO_KEEPALIVE` on the account websocket, so the OS probes idle
+    /// connections.
+    pub tcp_keepalive: bool,
+    /// What the quote streamer does with NaN and infinite values, which DXLink sends for
+    /// missing prices.
+    pub non_finite: NonFinitePolicy,
+}
+
+impl StreamerConfig {
+    /// Sets [`tcp_nodelay`](Self::tcp_nodelay).
+    pub fn tcp_nodelay(mut self, tcp_nodelay: bool) -> Self {
+        self.tcp_nodelay = tcp_nodelay;
+        self
+    }
+
+    /// Sets [`tcp_keepalive`](Self::tcp_keepalive).
+    pub fn tcp_keepalive(mut self, tcp_keepalive: bool) -> Self {
+        self.tcp_keepalive = tcp_keepalive;
+        self
+    }
+
+    /// Sets [`non_finite`](Self::non_finite).
+    pub fn non_finite(mut self, policy: NonFinitePolicy) -> Self {
+        self.non_finite = policy;
+        self
+    }
+}
+
+/// Host and port to open a TCP connection to for a `ws://` or `wss://` URL.
+fn socket_address(url: &str) -> TastyResult<(String, u16)> {
+    let uri: Uri = url
+        .parse()
+        .map_err(|e| TastyTradeError::Connection(format!("invalid websocket URL {url}: {e}")))?;
+    let host = uri
+        .host()
+        .ok_or_else(|| TastyTradeError::Connection(format!("websocket URL {url} has no host")))?;
+    let port = match (uri.port_u16(), uri.scheme_str()) {
+        (Some(port), _) => port,
+        (None, Some("ws")) => 80,
+        (None, Some("wss")) => 443,
+        (None, scheme) => {
+            return Err(TastyTradeError::Connection(format!(
+                "unsupported websocket scheme {scheme:?} in {url}"
+            )));
+        }
+    };
+    Ok((host.trim_matches(['[', ']']).to_string(), port))
+}
+
+/// Opens a websocket to `url` over a socket set up according to `config`.
+pub(crate) async fn connect_with_config(
+    url: &str,
+    config: &StreamerConfig,
+) -> TastyResult<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+    let (host, port) = socket_address(url)?;
+    let addr = lookup_host((host.as_str(), port))
+        .await?
+        .next()
+        .ok_or_else(|| TastyTradeError::Connection(format!("no address found for {host}")))?;
+
+    let socket = if addr.is_ipv4() {
+        TcpSocket::new_v4()?
+    } else {
+        TcpSocket::new_v6()?
+    };
+    socket.set_keepalive(config.tcp_keepalive)?;
+    socket.set_nodelay(config.tcp_nodelay)?;
+    let stream = socket.connect(addr).await?;
+
+    let (ws_stream, _response) = client_async_tls(url, stream).await?;
+    Ok(ws_stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_socket_address_defaults_port_by_scheme() {
+        assert_eq!(
+            socket_address("wss://streamer.tastyworks.com").unwrap(),
+            ("streamer.tastyworks.com".to_string(), 443)
+        );
+        assert_eq!(
+            socket_address("ws://127.0.0.1:9000/stream").unwrap(),
+            ("127.0.0.1".to_string(), 9000)
+        );
+        assert!(socket_address("https://api.tastyworks.com").is_err());
+    }
+}
@@ -0,0 +1,352 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! State of the DXLink task behind a [`QuoteStreamer`](crate::streaming::quote_streamer::QuoteStreamer).
+//!
+//! [`StreamerCore`] decides what each [`DXLinkCommand`] and each market event does
+//! without touching the network: commands update the routing table or turn into a
+//! [`StreamerAction`] for the IO driver to carry out, events are fanned out to the
+//! subscriptions' channels. The driver in `quote_streamer.rs` only moves messages
+//! between the DXLink client and the core.
+//!
+//! Feeds, one event type for one symbol, are reference counted across the
+//! subscriptions of the streamer: DXLink is asked for a feed when the first
+//! subscription wants it and told to drop it when the last one no longer does. Each
+//! subscription only receives the events of its own feeds, so pausing or closing one
+//! never cuts off, or leaks into, another.
+
+use crate::streaming::quote_streamer::{DXLinkCommand, event_type_name};
+use crate::types::dxfeed;
+use dxlink::FeedSubscription;
+use std::collections::{HashMap, HashSet};
+use tokio::sync::broadcast;
+use tracing::debug;
+
+/// Network work requested by [`StreamerCore::handle_command`].
+pub(crate) enum StreamerAction {
+    Subscribe(u32, Vec<FeedSubscription>),
+    Unsubscribe(u32, Vec<FeedSubscription>),
+    OpenEventStream,
+    Disconnect,
+}
+
+/// An event type and a symbol, the unit DXLink subscribes.
+type FeedKey = (String, String);
+
+fn feed_key(request: &FeedSubscription) -> FeedKey {
+    (request.event_type.clone(), request.symbol.clone())
+}
+
+fn key_request((event_type, symbol): &FeedKey) -> FeedSubscription {
+    FeedSubscription {
+        event_type: event_type.clone(),
+        symbol: symbol.clone(),
+        from_time: None,
+        source: None,
+    }
+}
+
+/// Routing table of the DXLink task.
+#[derive(Debug, Default)]
+pub(crate) struct StreamerCore {
+    channel_id: u32,
+    senders: HashMap<u32, broadcast::Sender<dxfeed::Event>>,
+    /// Feeds wanted by each subscription
+    interests: HashMap<u32, HashSet<FeedKey>>,
+    /// Number of subscriptions wanting each feed; a feed is subscribed upstream while
+    /// it is counted here
+    upstream: HashMap<FeedKey, usize>,
+    event_stream_requested: bool,
+    disconnected: bool,
+}
+
+impl StreamerCore {
+    /// A core subscribing feeds on the DXLink channel `channel_id`.
+    pub(crate) fn new(channel_id: u32) -> Self {
+        Self {
+            channel_id,
+            ..Self::default()
+        }
+    }
+
+    /// Applies `command`, returning the network action it needs, if any.
+    ///
+    /// Feeds are only subscribed and unsubscribed upstream when the first subscription
+    /// asks for them and when the last one lets them go. The event stream is opened
+    /// once however many subscriptions ask for it, and nothing is requested after a
+    /// disconnect.
+    pub(crate) fn handle_command(&mut self, command: DXLinkCommand) -> Option<StreamerAction> {
+        if self.disconnected {
+            return None;
+        }
+        match command {
+            DXLinkCommand::AddFeeds(subscription_id, requests) => {
+                let added = self.acquire(subscription_id, requests);
+                self.subscribe(added)
+            }
+            DXLinkCommand::RemoveFeeds(subscription_id, requests) => {
+                let removed = self.release(subscription_id, requests.iter().map(feed_key));
+                self.unsubscribe(removed)
+            }
+            DXLinkCommand::CreateEventStream => {
+                if self.event_stream_requested {
+                    return None;
+                }
+                self.event_stream_requested = true;
+                Some(StreamerAction::OpenEventStream)
+            }
+            DXLinkCommand::AddEventSender(subscription_id, sender) => {
+                self.senders.insert(subscription_id, sender);
+                debug!("Added event sender for subscription {}", subscription_id);
+                None
+            }
+            DXLinkCommand::RemoveEventSender(subscription_id) => {
+                self.senders.remove(&subscription_id);
+                debug!("Removed event senders for subscription {}", subscription_id);
+                let feeds: Vec<FeedKey> = self
+                    .interests
+                    .get(&subscription_id)
+                    .map(|feeds| feeds.iter().cloned().collect())
+                    .unwrap_or_default();
+                let removed = self.release(subscription_id, feeds);
+                self.interests.remove(&subscription_id);
+                self.unsubscribe(removed)
+            }
+            DXLinkCommand::Disconnect => {
+                self.disconnected = true;
+                self.senders.clear();
+                self.interests.clear();
+                self.upstream.clear();
+                Some(StreamerAction::Disconnect)
+            }
+        }
+    }
+
+    /// Records that the event stream could not be opened, so the next
+    /// [`DXLinkCommand::CreateEventStream`] tries again.
+    pub(crate) fn event_stream_closed(&mut self) {
+        self.event_stream_requested = false;
+    }
+
+    /// Forwards `event` to the subscriptions that want its feed, returning how many
+    /// received it.
+    ///
+    /// Broadcasting never blocks: lagging receivers lose their oldest events.
+    /// Subscriptions whose receivers are all gone are dropped from the table; their
+    /// feeds stay counted until the subscription is closed.
+    pub(crate) fn handle_event(&mut self, event: dxfeed::Event) -> usize {
+        let key = (event_type_name(&event.data).to_string(), event.sym.clone());
+        let interests = &self.interests;
+        let mut received = 0;
+        self.senders.retain(|subscription_id, sender| {
+            if !interests
+                .get(subscription_id)
+                .is_some_and(|feeds| feeds.contains(&key))
+            {
+                return true;
+            }
+            let sent = sender.send(event.clone()).is_ok();
+            received += usize::from(sent);
+            sent
+        });
+        received
+    }
+
+    /// Returns `true` once a [`DXLinkCommand::Disconnect`] was handled.
+    pub(crate) fn is_disconnected(&self) -> bool {
+        self.disconnected
+    }
+
+    /// Records `requests` for `subscription_id`, returning those no subscription
+    /// wanted before.
+    fn acquire(
+        &mut self,
+        subscription_id: u32,
+        requests: Vec<FeedSubscription>,
+    ) -> Vec<FeedSubscription> {
+        let feeds = self.interests.entry(subscription_id).or_default();
+        let mut added = Vec::new();
+        for request in requests {
+            let key = feed_key(&request);
+            if !feeds.insert(key.clone()) {
+                continue;
+            }
+            let count = self.upstream.entry(key).or_default();
+            *count += 1;
+            if *count == 1 {
+                added.push(request);
+            }
+        }
+        added
+    }
+
+    /// Forgets `feeds` for `subscription_id`, returning requests for those no
+    /// subscription wants anymore.
+    fn release(
+        &mut self,
+        subscription_id: u32,
+        feeds: impl IntoIterator<Item = FeedKey>,
+    ) -> Vec<FeedSubscription> {
+        let Some(wanted) = self.interests.get_mut(&subscription_id) else {
+            return Vec::new();
+        };
+        let mut removed = Vec::new();
+        for key in feeds {
+            if !wanted.remove(&key) {
+                continue;
+            }
+            if let Some(count) = self.upstream.get_mut(&key) {
+                *count -= 1;
+                if *count == 0 {
+                    self.upstream.remove(&key);
+                    removed.push(key_request(&key));
+                }
+            }
+        }
+        removed
+    }
+
+    fn subscribe(&self, requests: Vec<FeedSubscription>) -> Option<StreamerAction> {
+        (!requests.is_empty()).then(|| StreamerAction::Subscribe(self.channel_id, requests))
+    }
+
+    fn unsubscribe(&self, requests: Vec<FeedSubscription>) -> Option<StreamerAction> {
+        (!requests.is_empty()).then(|| StreamerAction::Unsubscribe(self.channel_id, requests))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Symbol;
+    use crate::streaming::quote_streamer::feed_request;
+
+    fn quote(sym: &str) -> dxfeed::Event {
+        dxfeed::Event::new_quote(sym.to_string(), Default::default())
+    }
+
+    fn request(event_type: &str, sym: &str) -> FeedSubscription {
+        feed_request(event_type, &Symbol(sym.into()))
+    }
+
+    #[test]
+    fn test_core_routes_commands_and_events() {
+        let mut core = StreamerCore::new(3);
+        let (tx_a, mut rx_a) = broadcast::channel(8);
+        let (tx_b, rx_b) = broadcast::channel(8);
+        assert!(
+            core.handle_command(DXLinkCommand::AddEventSender(0, tx_a))
+                .is_none()
+        );
+        assert!(
+            core.handle_command(DXLinkCommand::AddEventSender(1, tx_b))
+                .is_none()
+        );
+
+        assert!(matches!(
+            core.handle_command(DXLinkCommand::CreateEventStream),
+            Some(StreamerAction::OpenEventStream)
+        ));
+        assert!(
+            core.handle_command(DXLinkCommand::CreateEventStream)
+                .is_none()
+        );
+        core.event_stream_closed();
+        assert!(
+            core.handle_command(DXLinkCommand::CreateEventStream)
+                .is_some()
+        );
+
+        let feeds = || vec![request("Quote", "SPY"), request("Quote", "QQQ")];
+        assert!(matches!(
+            core.handle_command(DXLinkCommand::AddFeeds(0, feeds())),
+            Some(StreamerAction::Subscribe(3, requests)) if requests.len() == 2
+        ));
+        assert!(
+            core.handle_command(DXLinkCommand::AddFeeds(1, feeds()))
+                .is_none()
+        );
+        assert_eq!(core.handle_event(quote("SPY")), 2);
+        assert_eq!(core.handle_event(quote("IWM")), 0);
+        assert_eq!(rx_a.try_recv().unwrap().sym, "SPY");
+
+        // Dropped receivers and removed senders stop receiving
+        drop(rx_b);
+        assert_eq!(core.handle_event(quote("QQQ")), 1);
+        assert!(
+            core.handle_command(DXLinkCommand::RemoveEventSender(0))
+                .is_none()
+        );
+        assert_eq!(core.handle_event(quote("QQQ")), 0);
+        assert_eq!(rx_a.try_recv().unwrap().sym, "QQQ");
+        assert!(rx_a.try_recv().is_err());
+
+        assert!(matches!(
+            core.handle_command(DXLinkCommand::Disconnect),
+            Some(StreamerAction::Disconnect)
+        ));
+        assert!(core.is_disconnected());
+        assert!(
+            core.handle_command(DXLinkCommand::AddFeeds(2, feeds()))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_feeds_are_counted_across_subscriptions() {
+        let mut core = StreamerCore::new(3);
+        let (tx_a, mut rx_a) = broadcast::channel(8);
+        let (tx_b, mut rx_b) = broadcast::channel(8);
+        core.handle_command(DXLinkCommand::AddEventSender(0, tx_a));
+        core.handle_command(DXLinkCommand::AddEventSender(1, tx_b));
+
+        let a_feeds = || vec![request("Quote", "SPY"), request("Greeks", "SPY")];
+        assert!(matches!(
+            core.handle_command(DXLinkCommand::AddFeeds(0, a_feeds())),
+            Some(StreamerAction::Subscribe(3, requests)) if requests.len() == 2
+        ));
+        // Quote/SPY is already subscribed for `a`
+        assert!(matches!(
+            core.handle_command(DXLinkCommand::AddFeeds(
+                1,
+                vec![request("Quote", "SPY"), request("Quote", "QQQ")]
+            )),
+            Some(StreamerAction::Subscribe(3, requests))
+                if requests.len() == 1 && requests[0].symbol == "QQQ"
+        ));
+
+        // Each subscription only receives its own feeds
+        let greeks = dxfeed::Event::new_greeks("SPY".to_string(), Default::default());
+        assert_eq!(core.handle_event(greeks), 1);
+        assert_eq!(core.handle_event(quote("QQQ")), 1);
+        assert!(matches!(
+            rx_a.try_recv().unwrap().data,
+            dxfeed::EventData::Greeks(_)
+        ));
+        assert!(rx_a.try_recv().is_err());
+        assert_eq!(rx_b.try_recv().unwrap().sym, "QQQ");
+
+        // Pausing `a` only drops the feed `b` does not want
+        assert!(matches!(
+            core.handle_command(DXLinkCommand::RemoveFeeds(0, a_feeds())),
+            Some(StreamerAction::Unsubscribe(3, requests))
+                if requests.len() == 1 && requests[0].event_type == "Greeks"
+        ));
+        assert_eq!(core.handle_event(quote("SPY")), 1);
+        assert!(rx_a.try_recv().is_err());
+        assert_eq!(rx_b.try_recv().unwrap().sym, "SPY");
+
+        // Closing `b` drops the rest
+        assert!(matches!(
+            core.handle_command(DXLinkCommand::RemoveEventSender(1)),
+            Some(StreamerAction::Unsubscribe(3, requests)) if requests.len() == 2
+        ));
+        assert!(
+            core.handle_command(DXLinkCommand::AddFeeds(0, a_feeds()))
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,149 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Builder for quote subscriptions.
+//!
+//! [`QuoteStreamer::create_sub`] takes a `dxfeed::DXF_ET_*` bit mask, where a wrong
+//! constant or a forgotten `|` silently subscribes to the wrong events. The builder
+//! names each event kind instead:
+//!
+//! ```rust,ignore
+//! let sub = streamer
+//!     .subscription()
+//!     .quotes()
+//!     .greeks()
+//!     .symbols(["AAPL", ".AAPL250117C150"])
+//!     .start()
+//!     .await?;
+//! ```
+
+use crate::streaming::quote_streamer::{QuoteStreamer, QuoteSubscription};
+use crate::types::dxfeed;
+use crate::{AsSymbol, Symbol, TastyResult, TastyTradeError};
+use serde::Serialize;
+use std::fmt;
+
+/// A kind of market event a subscription can receive.
+///
+/// New kinds may be added in minor releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[non_exhaustive]
+pub enum EventKind {
+    /// Top of book bid and ask.
+    Quote,
+    /// Last trade.
+    Trade,
+    /// Option greeks and implied volatility.
+    Greeks,
+}
+
+impl EventKind {
+    /// Every kind, in flag order.
+    pub const ALL: [EventKind; 3] = [EventKind::Quote, EventKind::Trade, EventKind::Greeks];
+
+    /// The `dxfeed::DXF_ET_*` flag of this kind.
+    pub fn flag(self) -> i32 {
+        match self {
+            EventKind::Quote => dxfeed::DXF_ET_QUOTE,
+            EventKind::Trade => dxfeed::DXF_ET_TRADE,
+            EventKind::Greeks => dxfeed::DXF_ET_GREEKS,
+        }
+    }
+
+    /// The kinds selected by a `dxfeed::DXF_ET_*` bit mask.
+    pub fn from_flags(flags: i32) -> Vec<EventKind> {
+        Self::ALL
+            .into_iter()
+            .filter(|kind| flags & kind.flag() != 0)
+            .collect()
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventKind::Quote => write!(f, "Quote"),
+            EventKind::Trade => write!(f, "Trade"),
+            EventKind::Greeks => write!(f, "Greeks"),
+        }
+    }
+}
+
+/// Collects event kinds and symbols, then creates the subscription. See the module docs.
+#[must_use = "the subscription is only created by `start`"]
+pub struct SubscriptionBuilder<'s> {
+    streamer: &'s mut QuoteStreamer,
+    kinds: Vec<EventKind>,
+    symbols: Vec<Symbol>,
+}
+
+impl<'s> SubscriptionBuilder<'s> {
+    pub(crate) fn new(streamer: &'s mut QuoteStreamer) -> Self {
+        Self {
+            streamer,
+            kinds: Vec::new(),
+            symbols: Vec::new(),
+        }
+    }
+
+    /// Receives events of `kind`.
+    pub fn event(mut self, kind: EventKind) -> Self {
+        if !self.kinds.contains(&kind) {
+            self.kinds.push(kind);
+        }
+        self
+    }
+
+    /// Receives `Quote` events.
+    pub fn quotes(self) -> Self {
+        self.event(EventKind::Quote)
+    }
+
+    /// Receives `Trade` events.
+    pub fn trades(self) -> Self {
+        self.event(EventKind::Trade)
+    }
+
+    /// Receives `Greeks` events.
+    pub fn greeks(self) -> Self {
+        self.event(EventKind::Greeks)
+    }
+
+    /// Subscribes to `symbols` once started. Can be called several times.
+    pub fn symbols<I, S>(mut self, symbols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsSymbol,
+    {
+        self.symbols
+            .extend(symbols.into_iter().map(|sym| sym.as_symbol()));
+        self
+    }
+
+    /// Creates the subscription and subscribes to the collected symbols.
+    ///
+    /// Fails with [`TastyTradeError::Streaming`] when no event kind was selected.
+    pub async fn start(self) -> TastyResult<Box<QuoteSubscription>> {
+        if self.kinds.is_empty() {
+            return Err(TastyTradeError::Streaming(
+                "subscription started without any event kind".to_string(),
+            ));
+        }
+        let flags = self.kinds.iter().fold(0, |flags, kind| flags | kind.flag());
+        let subscription = self.streamer.create_sub(flags);
+        if !self.symbols.is_empty() {
+            subscription.add_symbols(&self.symbols);
+        }
+        Ok(subscription)
+    }
+}
+
+impl QuoteStreamer {
+    /// Starts building a subscription with typed event selection.
+    pub fn subscription(&mut self) -> SubscriptionBuilder<'_> {
+        SubscriptionBuilder::new(self)
+    }
+}
@@ -0,0 +1,168 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Quote subscriptions managed as a unit.
+//!
+//! A [`SubscriptionGroup`] holds several [`QuoteSubscription`]s of one streamer,
+//! typically one per strategy, so they can be paused, resumed and closed together.
+//! Feeds are reference counted per event type and symbol by the streamer itself,
+//! across the group and every other subscription: when two members both want `Quote`
+//! events for `SPY`, DXLink is only asked once, pausing or closing the group only drops
+//! the feeds no other subscription still wants, and each member only receives the
+//! events of its own symbols.
+//!
+//! ```rust,ignore
+//! let mut group = SubscriptionGroup::new();
+//! let mut spx = group.create_sub(&mut streamer, DXF_ET_QUOTE | DXF_ET_GREEKS);
+//! let mut spy = group.create_sub(&mut streamer, DXF_ET_QUOTE);
+//! group.add_symbols(spx.id, &[".SPXW250117P5800"]);
+//! group.add_symbols(spy.id, &["SPY"]);
+//!
+//! group.pause(); // no data billed while both strategies are flat
+//! group.resume();
+//! group.close(&mut streamer);
+//! ```
+
+use crate::AsSymbol;
+use crate::streaming::quote_streamer::{QuoteStreamer, QuoteSubscription, SubscriptionId};
+use std::collections::HashMap;
+
+/// A set of quote subscriptions sharing one lifecycle.
+#[derive(Default)]
+pub struct SubscriptionGroup {
+    members: HashMap<SubscriptionId, QuoteSubscription>,
+    paused: bool,
+}
+
+impl SubscriptionGroup {
+    /// Creates an empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a subscription on `streamer` and adds it to the group.
+    pub fn create_sub(
+        &mut self,
+        streamer: &mut QuoteStreamer,
+        flags: i32,
+    ) -> Box<QuoteSubscription> {
+        let sub = streamer.create_sub(flags);
+        self.add(&sub);
+        sub
+    }
+
+    /// Adds an existing subscription to the group, pausing it if the group is paused.
+    pub fn add(&mut self, sub: &QuoteSubscription) {
+        if self.paused {
+            sub.pause();
+        }
+        self.members.insert(sub.id, sub.clone());
+    }
+
+    /// Adds symbols to member `id`. Unknown ids are ignored.
+    pub fn add_symbols<S: AsSymbol>(&self, id: SubscriptionId, symbols: &[S]) {
+        if let Some(member) = self.members.get(&id) {
+            member.add_symbols(symbols);
+        }
+    }
+
+    /// Removes symbols from member `id`. Unknown ids are ignored.
+    pub fn remove_symbols<S: AsSymbol>(&self, id: SubscriptionId, symbols: &[S]) {
+        if let Some(member) = self.members.get(&id) {
+            member.remove_symbols(symbols);
+        }
+    }
+
+    /// Pauses every member, keeping their symbol sets.
+    pub fn pause(&mut self) {
+        self.paused = true;
+        for member in self.members.values() {
+            member.pause();
+        }
+    }
+
+    /// Resumes every member paused by [`pause`](Self::pause).
+    pub fn resume(&mut self) {
+        self.paused = false;
+        for member in self.members.values() {
+            member.resume();
+        }
+    }
+
+    /// Returns `true` while the group is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Ids of the subscriptions in this group.
+    pub fn members(&self) -> impl Iterator<Item = SubscriptionId> + '_ {
+        self.members.keys().copied()
+    }
+
+    /// Closes every member subscription on `streamer`.
+    pub fn close(self, streamer: &mut QuoteStreamer) {
+        for id in self.members.keys() {
+            streamer.close_sub(*id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dxfeed::{DXF_ET_GREEKS, DXF_ET_QUOTE};
+    use crate::streaming::quote_streamer::DXLinkCommand;
+    use crate::streaming::streamer_core::{StreamerAction, StreamerCore};
+    use tokio::sync::mpsc;
+
+    /// Replays the commands queued so far, returning how many feeds were subscribed
+    /// and unsubscribed upstream.
+    fn replay(
+        core: &mut StreamerCore,
+        rx: &mut mpsc::UnboundedReceiver<DXLinkCommand>,
+    ) -> (usize, usize) {
+        let (mut subscribed, mut unsubscribed) = (0, 0);
+        while let Ok(command) = rx.try_recv() {
+            match core.handle_command(command) {
+                Some(StreamerAction::Subscribe(_, requests)) => subscribed += requests.len(),
+                Some(StreamerAction::Unsubscribe(_, requests)) => unsubscribed += requests.len(),
+                _ => {}
+            }
+        }
+        (subscribed, unsubscribed)
+    }
+
+    #[test]
+    fn test_group_shares_feeds_with_the_streamer() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut streamer = QuoteStreamer::with_commands(Some(tx));
+        let mut core = StreamerCore::new(3);
+        let mut group = SubscriptionGroup::new();
+        let spx = group.create_sub(&mut streamer, DXF_ET_QUOTE | DXF_ET_GREEKS);
+        let spy = group.create_sub(&mut streamer, DXF_ET_QUOTE);
+        let outside = streamer.create_sub(DXF_ET_QUOTE);
+        group.add_symbols(spx.id, &["SPY", "QQQ"]);
+        group.add_symbols(spy.id, &["SPY"]);
+        outside.add_symbols(&["QQQ"]);
+        assert_eq!(replay(&mut core, &mut rx), (4, 0));
+
+        // Quote/QQQ is still wanted outside the group
+        group.pause();
+        assert!(group.is_paused() && spx.is_paused() && spy.is_paused());
+        assert!(!outside.is_paused());
+        assert_eq!(replay(&mut core, &mut rx), (0, 3));
+
+        group.add_symbols(spy.id, &["IWM"]);
+        assert_eq!(replay(&mut core, &mut rx), (0, 0));
+        group.resume();
+        assert_eq!(replay(&mut core, &mut rx), (4, 0));
+        assert_eq!(spy.symbols().len(), 2);
+
+        group.close(&mut streamer);
+        assert_eq!(replay(&mut core, &mut rx), (0, 4));
+        assert!(streamer.get_sub(outside.id).is_some());
+    }
+}
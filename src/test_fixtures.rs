@@ -0,0 +1,29 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Records shared by the unit tests, built from the scrubbed responses in
+//! `tests/fixtures`.
+
+use crate::FullPosition;
+use serde_json::Value;
+
+/// The equity position of `tests/fixtures/positions.json` with the fields of
+/// `overrides` replaced, as JSON.
+pub(crate) fn position_json(overrides: Value) -> Value {
+    let body: Value =
+        serde_json::from_str(include_str!("../tests/fixtures/positions.json")).unwrap();
+    let mut position = body["data"]["items"][0].clone();
+    if let (Some(fields), Value::Object(overrides)) = (position.as_object_mut(), overrides) {
+        fields.extend(overrides);
+    }
+    position
+}
+
+/// The equity position of `tests/fixtures/positions.json` with the fields of
+/// `overrides` replaced.
+pub(crate) fn position(overrides: Value) -> FullPosition {
+    serde_json::from_str(&position_json(overrides).to_string()).unwrap()
+}
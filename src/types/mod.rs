@@ -4,11 +4,13 @@
    Date: 5/3/25
 ******************************************************************************/
 
-pub(crate) mod balance;
+//! The data model lives in the `tastytrade-types` crate so it can be used without
+//! the HTTP and streaming stack; it is re-exported here under the historical paths.
+
+pub(crate) use tastytrade_types::{
+    balance, customer, instrument, login, market_metrics, order, position, transaction,
+};
+
 pub(crate) mod event;
-pub(crate) mod instrument;
-pub(crate) mod login;
-pub(crate) mod order;
-pub(crate) mod position;
 
-pub mod dxfeed;
+pub use tastytrade_types::{calendar, dxfeed, sizing, strategy, validation};
@@ -0,0 +1,228 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! On-disk cache of historical candles.
+//!
+//! Strategies usually warm up on the same months of history every run. [`CandleCache`]
+//! keeps the candles of each (symbol, period) pair in a JSON file and, through
+//! [`CandleCache::fetch_candles`], only asks the data source for the tail that is not
+//! cached yet. The last cached candle is always fetched again since it may have been
+//! still forming when it was stored.
+//!
+//! The cache is agnostic of where candles come from: `fetch_candles` takes an async
+//! function receiving the start time to request from, typically a DXLink `Candle`
+//! subscription with `fromTime` set to it.
+//!
+//! ```rust,ignore
+//! let cache = CandleCache::new(".candles").max_age(Duration::from_secs(7 * 24 * 3600));
+//! let candles = cache
+//!     .fetch_candles("SPY", "1d", one_year_ago_ms, |from| load_from_dxlink("SPY{=1d}", from))
+//!     .await?;
+//! ```
+
+use crate::api::base::TastyResult;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::debug;
+
+/// One OHLCV bar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    /// Bar open time, milliseconds since the Unix epoch.
+    pub time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+/// A directory of cached candle series, one JSON file per (symbol, period).
+#[derive(Debug, Clone)]
+pub struct CandleCache {
+    dir: PathBuf,
+    max_age: Option<Duration>,
+}
+
+impl CandleCache {
+    /// Creates a cache stored in `dir`, which is created on the first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_age: None,
+        }
+    }
+
+    /// Discards cached series whose file was last written longer than `max_age` ago.
+    ///
+    /// Useful when the provider may revise history, e.g. after corporate actions.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Path of the file holding `symbol` at `period`.
+    pub fn path(&self, symbol: &str, period: &str) -> PathBuf {
+        let sanitize = |s: &str| -> String {
+            s.chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect()
+        };
+        self.dir
+            .join(format!("{}_{}.json", sanitize(symbol), sanitize(period)))
+    }
+
+    /// Cached candles, oldest first. Empty when nothing is cached or the entry expired.
+    pub fn load(&self, symbol: &str, period: &str) -> TastyResult<Vec<Candle>> {
+        let path = self.path(symbol, period);
+        if self.is_expired(&path)? {
+            debug!("Candle cache entry {} expired", path.display());
+            return Ok(Vec::new());
+        }
+        match fs::read_to_string(&path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Replaces the cached series.
+    pub fn store(&self, symbol: &str, period: &str, candles: &[Candle]) -> TastyResult<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(symbol, period), serde_json::to_string(candles)?)?;
+        Ok(())
+    }
+
+    /// Removes the cached series of one (symbol, period).
+    pub fn invalidate(&self, symbol: &str, period: &str) -> TastyResult<()> {
+        match fs::remove_file(self.path(symbol, period)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes every cached series.
+    pub fn clear(&self) -> TastyResult<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns candles from `from_time` (epoch ms) onwards, fetching only what is not cached.
+    ///
+    /// `fetch` receives the time to request from. When the cache does not reach back to
+    /// `from_time` the whole range is fetched again.
+    pub async fn fetch_candles<F, Fut>(
+        &self,
+        symbol: &str,
+        period: &str,
+        from_time: i64,
+        fetch: F,
+    ) -> TastyResult<Vec<Candle>>
+    where
+        F: FnOnce(i64) -> Fut,
+        Fut: Future<Output = TastyResult<Vec<Candle>>>,
+    {
+        let cached = self.load(symbol, period)?;
+        let start = match (cached.first(), cached.last()) {
+            (Some(first), Some(last)) if first.time <= from_time => last.time,
+            _ => from_time,
+        };
+        debug!(
+            "Fetching {} {} candles from {} ({} cached)",
+            symbol,
+            period,
+            start,
+            cached.len()
+        );
+
+        let fetched = fetch(start).await?;
+        let merged = merge(cached, fetched);
+        self.store(symbol, period, &merged)?;
+        Ok(merged.into_iter().filter(|c| c.time >= from_time).collect())
+    }
+
+    fn is_expired(&self, path: &Path) -> TastyResult<bool> {
+        let Some(max_age) = self.max_age else {
+            return Ok(false);
+        };
+        match fs::metadata(path) {
+            Ok(metadata) => {
+                let age = SystemTime::now()
+                    .duration_since(metadata.modified()?)
+                    .unwrap_or_default();
+                Ok(age > max_age)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Merges `fetched` into `cached`, newer values winning for equal times, sorted by time.
+fn merge(cached: Vec<Candle>, fetched: Vec<Candle>) -> Vec<Candle> {
+    let mut by_time: std::collections::BTreeMap<i64, Candle> =
+        cached.into_iter().map(|c| (c.time, c)).collect();
+    by_time.extend(fetched.into_iter().map(|c| (c.time, c)));
+    by_time.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn candle(time: i64, close: f64) -> Candle {
+        Candle {
+            time,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_fetch_candles_only_requests_missing_tail() {
+        let dir = std::env::temp_dir().join(format!("tastytrade-candles-{}", std::process::id()));
+        let cache = CandleCache::new(&dir);
+        cache.clear().unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let requested = Mutex::new(Vec::new());
+
+        let first = runtime
+            .block_on(cache.fetch_candles("/ES:XCME", "1d", 100, |from| {
+                requested.lock().unwrap().push(from);
+                async { Ok(vec![candle(100, 1.0), candle(200, 2.0)]) }
+            }))
+            .unwrap();
+        assert_eq!(first.len(), 2);
+
+        // The still-forming 200 bar is refreshed and 300 appended.
+        let second = runtime
+            .block_on(cache.fetch_candles("/ES:XCME", "1d", 100, |from| {
+                requested.lock().unwrap().push(from);
+                async { Ok(vec![candle(200, 2.5), candle(300, 3.0)]) }
+            }))
+            .unwrap();
+        assert_eq!(*requested.lock().unwrap(), vec![100, 200]);
+        assert_eq!(
+            second,
+            vec![candle(100, 1.0), candle(200, 2.5), candle(300, 3.0)]
+        );
+        assert!(cache.path("/ES:XCME", "1d").ends_with("_ES_XCME_1d.json"));
+
+        cache.invalidate("/ES:XCME", "1d").unwrap();
+        assert!(cache.load("/ES:XCME", "1d").unwrap().is_empty());
+        cache.clear().unwrap();
+    }
+}
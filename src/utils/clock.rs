@@ -0,0 +1,133 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Time source abstraction.
+//!
+//! Components with time-dependent behaviour (order throttling, staleness of streamed
+//! state, order ages) read the time from a [`Clock`] instead of calling `Utc::now()`
+//! or `Instant::now()` directly. Production code uses [`SystemClock`]; tests inject a
+//! [`MockClock`] and move it forward explicitly.
+//!
+//! ```rust,ignore
+//! let clock = MockClock::new("2024-06-03T14:30:00Z".parse()?);
+//! let mut state = PortfolioState::new().with_clock(Arc::new(clock.clone()));
+//! state.reset(&positions, None);
+//! clock.advance(Duration::from_secs(120));
+//! assert!(state.is_stale(chrono::Duration::seconds(60)));
+//! ```
+
+use chrono::{DateTime, Utc};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of wall-clock and monotonic time.
+pub trait Clock: Debug + Send + Sync {
+    /// Current wall-clock time.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current monotonic time, for measuring intervals.
+    fn instant(&self) -> Instant;
+}
+
+/// A clock shared between the components of a session.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The real time of the operating system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A shared handle to the [`SystemClock`].
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+#[derive(Debug)]
+struct MockState {
+    now: DateTime<Utc>,
+    base: Instant,
+    elapsed: Duration,
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep one handle and give the others to
+/// the components under test.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockClock {
+    /// Creates a clock stopped at `now`.
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockState {
+                now,
+                base: Instant::now(),
+                elapsed: Duration::ZERO,
+            })),
+        }
+    }
+
+    /// Moves both the wall clock and the monotonic clock forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.now += chrono::Duration::from_std(by).expect("duration out of range");
+        state.elapsed += by;
+    }
+
+    /// Sets the wall clock to `now`, e.g. to cross a session boundary.
+    ///
+    /// The monotonic clock is not affected.
+    pub fn set(&self, now: DateTime<Utc>) {
+        self.state.lock().unwrap().now = now;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.state.lock().unwrap().now
+    }
+
+    fn instant(&self) -> Instant {
+        let state = self.state.lock().unwrap();
+        state.base + state.elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_moves_only_when_told() {
+        let start: DateTime<Utc> = "2024-06-03T14:30:00Z".parse().unwrap();
+        let clock = MockClock::new(start);
+        let shared: SharedClock = Arc::new(clock.clone());
+        let before = shared.instant();
+        assert_eq!(shared.now(), start);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(shared.now(), start + chrono::Duration::seconds(90));
+        assert_eq!(shared.instant() - before, Duration::from_secs(90));
+
+        let next_day: DateTime<Utc> = "2024-06-04T13:30:00Z".parse().unwrap();
+        clock.set(next_day);
+        assert_eq!(shared.now(), next_day);
+        assert_eq!(shared.instant() - before, Duration::from_secs(90));
+    }
+}
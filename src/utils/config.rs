@@ -1,10 +1,18 @@
+use crate::api::guard::EnvironmentGuardConfig;
+use crate::api::throttle::OrderThrottleConfig;
+use crate::streaming::streamer_config::StreamerConfig;
 use crate::utils::logger::setup_logger_with_level;
 use crate::{TastyTrade, TastyTradeError};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
+use reqwest::header::{HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt;
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
+use tracing::warn;
 
 const BASE_DEMO_URL: &str = "https://api.cert.tastyworks.com";
 const BASE_URL: &str = "https://api.tastyworks.com";
@@ -13,9 +21,197 @@ const WEBSOCKET_DEMO_URL: &str = "wss://streamer.cert.tastyworks.com";
 
 const WEBSOCKET_URL: &str = "wss://streamer.tastyworks.com";
 
+const DEFAULT_ENV_PREFIX: &str = "TASTYTRADE_";
+
+const VALID_LOG_LEVELS: [&str; 5] = ["TRACE", "DEBUG", "INFO", "WARN", "ERROR"];
+
+/// The tastytrade environment a configuration targets.
+///
+/// `Demo` and `Certification` are both served from the `cert` hosts; the distinction
+/// lets API partners label certification runs and accepts `TASTYTRADE_ENVIRONMENT=cert`
+/// as written in tastytrade's partner documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    /// Live trading.
+    #[default]
+    Production,
+    /// The sandbox selected by `use_demo`.
+    Demo,
+    /// The certification environment for API partners.
+    #[serde(alias = "cert")]
+    Certification,
+}
+
+impl Environment {
+    /// Whether this environment is anything other than production.
+    pub fn is_sandbox(self) -> bool {
+        self != Environment::Production
+    }
+
+    /// Default REST base URL.
+    pub fn base_url(self) -> &'static str {
+        match self {
+            Environment::Production => BASE_URL,
+            Environment::Demo | Environment::Certification => BASE_DEMO_URL,
+        }
+    }
+
+    /// Default account streaming URL.
+    pub fn websocket_url(self) -> &'static str {
+        match self {
+            Environment::Production => WEBSOCKET_URL,
+            Environment::Demo | Environment::Certification => WEBSOCKET_DEMO_URL,
+        }
+    }
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Environment::Production => write!(f, "production"),
+            Environment::Demo => write!(f, "demo"),
+            Environment::Certification => write!(f, "certification"),
+        }
+    }
+}
+
+impl FromStr for Environment {
+    type Err = TastyTradeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "prod" | "production" | "live" => Ok(Environment::Production),
+            "demo" | "sandbox" => Ok(Environment::Demo),
+            "cert" | "certification" => Ok(Environment::Certification),
+            other => Err(TastyTradeError::ConfigError(format!(
+                "unknown environment '{}' (expected production, demo or cert)",
+                other
+            ))),
+        }
+    }
+}
+
+/// A single problem detected by [`TastyTradeConfig::validate`].
+///
+/// Each variant carries enough context to tell the user what to change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum ConfigProblem {
+    /// `username` is empty.
+    MissingUsername {
+        /// Prefix of the environment variables the configuration was read from.
+        env_prefix: Option<String>,
+    },
+    /// `password` is empty.
+    MissingPassword {
+        /// Prefix of the environment variables the configuration was read from.
+        env_prefix: Option<String>,
+    },
+    /// `use_demo` does not agree with `environment`.
+    EnvironmentConflict {
+        /// Value of the `use_demo` flag.
+        use_demo: bool,
+        /// The configured environment.
+        environment: Environment,
+    },
+    /// `use_demo` does not agree with the environment `base_url` points at.
+    DemoFlagConflict {
+        /// Value of the `use_demo` flag.
+        use_demo: bool,
+        /// The conflicting URL.
+        url: String,
+        /// Prefix of the environment variables the configuration was read from.
+        env_prefix: Option<String>,
+    },
+    /// `log_level` is not one of the supported levels.
+    InvalidLogLevel(String),
+    /// A URL is empty or uses the wrong scheme.
+    InvalidUrl {
+        /// Name of the configuration field.
+        field: &'static str,
+        /// The offending value.
+        url: String,
+    },
+    /// A streaming URL points at the other streaming server: `websocket_url` must be the
+    /// account streamer and `dxlink_url` the DXLink quote server.
+    StreamingUrlMixup {
+        /// Name of the configuration field.
+        field: &'static str,
+        /// The offending value.
+        url: String,
+    },
+    /// `user_agent` or an entry of `extra_headers` is not a valid HTTP header.
+    InvalidHeader {
+        /// Name of the header.
+        name: String,
+    },
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigProblem::MissingUsername { env_prefix } => match env_prefix {
+                Some(prefix) => write!(f, "missing username (set {}USERNAME)", prefix),
+                None => write!(f, "missing username"),
+            },
+            ConfigProblem::MissingPassword { env_prefix } => match env_prefix {
+                Some(prefix) => write!(f, "missing password (set {}PASSWORD)", prefix),
+                None => write!(f, "missing password"),
+            },
+            ConfigProblem::DemoFlagConflict {
+                use_demo,
+                url,
+                env_prefix,
+            } => {
+                write!(
+                    f,
+                    "use_demo is {} but {} points at the {} environment",
+                    use_demo,
+                    url,
+                    if *use_demo { "production" } else { "demo" }
+                )?;
+                match env_prefix {
+                    Some(prefix) => write!(f, " (check {}USE_DEMO)", prefix),
+                    None => Ok(()),
+                }
+            }
+            ConfigProblem::EnvironmentConflict {
+                use_demo,
+                environment,
+            } => write!(
+                f,
+                "use_demo is {} but environment is {}",
+                use_demo, environment
+            ),
+            ConfigProblem::InvalidLogLevel(level) => write!(
+                f,
+                "invalid log level '{}' (expected one of {})",
+                level,
+                VALID_LOG_LEVELS.join(", ")
+            ),
+            ConfigProblem::InvalidUrl { field, url } => {
+                write!(f, "invalid {} '{}'", field, url)
+            }
+            ConfigProblem::StreamingUrlMixup { field, url } => write!(
+                f,
+                "{} '{}' points at the wrong streaming server (websocket_url is the account streamer, dxlink_url the DXLink quote server)",
+                field, url
+            ),
+            ConfigProblem::InvalidHeader { name } => {
+                write!(
+                    f,
+                    "invalid HTTP header '{}' (check its name and value)",
+                    name
+                )
+            }
+        }
+    }
+}
+
 /// Configuration structure for the application
 /// Handles environment variables and logger setup
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+#[serde(from = "ConfigFile")]
 pub struct TastyTradeConfig {
     /// TastyTrade API username/email
     pub username: String,
@@ -24,14 +220,122 @@ pub struct TastyTradeConfig {
     pub password: String,
     /// Whether to use demo/cert environment
     pub use_demo: bool,
+    /// Target environment; `use_demo` is true for every non-production environment.
+    ///
+    /// Configurations saved before this field existed are read with the environment
+    /// selected by `use_demo`.
+    pub environment: Environment,
     /// Log level: "INFO", "DEBUG", "WARN", "ERROR", "TRACE"
     pub log_level: String,
     /// Whether to remember login session
     pub remember_me: bool,
+    /// Remember token of an earlier session. When set, [`TastyTrade::login`] uses it
+    /// instead of the password, which avoids a new device verification on every restart.
+    /// Store [`TastyTrade::remember_token`] back here after each login: tokens are single use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remember_token: Option<String>,
     /// Base URL for API requests
     pub base_url: String,
-    /// Websocket URL.
+    /// Account streaming websocket URL. Only used for account updates, never for DXLink.
+    ///
+    /// Left at the default of `environment`, the URL reported with the session is used
+    /// when the API reports one; any other value overrides it. See
+    /// [`websocket_url_override`](Self::websocket_url_override).
     pub websocket_url: String,
+    /// DXLink streaming URL, used for quotes and the DXLink side of account streaming.
+    /// When `None`, the URL returned by `/api-quote-tokens` is used.
+    #[serde(default)]
+    pub dxlink_url: Option<String>,
+    /// Limits on order placement and cancellation for the session, none by default.
+    #[serde(default)]
+    pub order_throttle: OrderThrottleConfig,
+    /// Checks on order size and environment before placement; see
+    /// [`guard`](crate::api::guard). `None` places orders unchecked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment_guard: Option<EnvironmentGuardConfig>,
+    /// Socket options of the streaming connections.
+    #[serde(default)]
+    pub streamer: StreamerConfig,
+    /// Whether a request rejected with `401 Unauthorized` logs in again and is replayed
+    /// once. When `false`, the rejection is returned to the caller.
+    #[serde(default = "default_auto_relogin")]
+    pub auto_relogin: bool,
+    /// `User-Agent` sent with API requests, `tastytrade` when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Headers added to every API request, e.g. for attribution behind a proxy. They
+    /// replace the client's own headers of the same name, except `user_agent`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra_headers: BTreeMap<String, String>,
+    /// Prefix of the environment variables read by
+    /// [`from_env_with_prefix`](Self::from_env_with_prefix), used to name them in the
+    /// problems reported by [`validate`](Self::validate). `None` for other configurations.
+    #[serde(skip)]
+    pub env_prefix: Option<String>,
+}
+
+fn default_auto_relogin() -> bool {
+    true
+}
+
+/// The serialized form of [`TastyTradeConfig`], where `environment` may be missing.
+#[derive(Deserialize)]
+struct ConfigFile {
+    username: String,
+    #[serde(default)]
+    password: String,
+    use_demo: bool,
+    #[serde(default)]
+    environment: Option<Environment>,
+    log_level: String,
+    remember_me: bool,
+    #[serde(default)]
+    remember_token: Option<String>,
+    base_url: String,
+    websocket_url: String,
+    #[serde(default)]
+    dxlink_url: Option<String>,
+    #[serde(default)]
+    order_throttle: OrderThrottleConfig,
+    #[serde(default)]
+    environment_guard: Option<EnvironmentGuardConfig>,
+    #[serde(default)]
+    streamer: StreamerConfig,
+    #[serde(default = "default_auto_relogin")]
+    auto_relogin: bool,
+    #[serde(default)]
+    user_agent: Option<String>,
+    #[serde(default)]
+    extra_headers: BTreeMap<String, String>,
+}
+
+impl From<ConfigFile> for TastyTradeConfig {
+    fn from(file: ConfigFile) -> Self {
+        let environment = file.environment.unwrap_or(if file.use_demo {
+            Environment::Demo
+        } else {
+            Environment::Production
+        });
+        Self {
+            username: file.username,
+            password: file.password,
+            use_demo: file.use_demo,
+            environment,
+            log_level: file.log_level,
+            remember_me: file.remember_me,
+            remember_token: file.remember_token,
+            base_url: file.base_url,
+            websocket_url: file.websocket_url,
+            dxlink_url: file.dxlink_url,
+            order_throttle: file.order_throttle,
+            environment_guard: file.environment_guard,
+            streamer: file.streamer,
+            auto_relogin: file.auto_relogin,
+            user_agent: file.user_agent,
+            extra_headers: file.extra_headers,
+            env_prefix: None,
+        }
+    }
 }
 
 impl Default for TastyTradeConfig {
@@ -40,10 +344,20 @@ impl Default for TastyTradeConfig {
             username: String::new(),
             password: String::new(),
             use_demo: false,
+            environment: Environment::Production,
             log_level: "INFO".to_string(),
             remember_me: false,
+            remember_token: None,
             base_url: BASE_URL.to_string(),
             websocket_url: WEBSOCKET_URL.to_string(),
+            dxlink_url: None,
+            order_throttle: OrderThrottleConfig::default(),
+            environment_guard: None,
+            streamer: StreamerConfig::default(),
+            auto_relogin: true,
+            user_agent: None,
+            extra_headers: BTreeMap::new(),
+            env_prefix: None,
         }
     }
 }
@@ -65,38 +379,81 @@ impl TastyTradeConfig {
 
     /// Initialize a new configuration from environment variables
     pub fn from_env() -> Self {
+        Self::from_env_with_prefix(DEFAULT_ENV_PREFIX)
+    }
+
+    /// Initialize a new configuration from environment variables using a custom prefix.
+    ///
+    /// Every recognized variable is looked up as `{prefix}NAME`, so
+    /// `from_env_with_prefix("BOT1_")` reads `BOT1_USERNAME`, `BOT1_PASSWORD`,
+    /// `BOT1_USE_DEMO`, `BOT1_REMEMBER_ME`, `BOT1_REMEMBER_TOKEN`, `BOT1_AUTO_RELOGIN` and `BOT1_LOGLEVEL`. The log level falls
+    /// back to the unprefixed `LOGLEVEL` variable shared with [`setup_logger`](crate::utils::logger::setup_logger).
+    ///
+    /// `{prefix}BASE_URL`, `{prefix}WEBSOCKET_URL` and `{prefix}DXLINK_URL` override the
+    /// REST, account streaming and quote streaming endpoints independently, e.g. to go
+    /// through a gateway or a capture proxy. Unset ones follow the environment.
+    /// `{prefix}USER_AGENT` replaces the `User-Agent` sent to the API.
+    ///
+    /// `{prefix}ENVIRONMENT` (`production`, `demo` or `cert`) takes precedence over
+    /// `{prefix}USE_DEMO`; an unrecognized value is logged and ignored.
+    pub fn from_env_with_prefix(prefix: &str) -> Self {
         dotenv::dotenv().ok();
-        let username = env::var("TASTYTRADE_USERNAME").unwrap_or_default();
-        let password = env::var("TASTYTRADE_PASSWORD").unwrap_or_default();
-        let use_demo = env::var("TASTYTRADE_USE_DEMO")
+        let var = |name: &str| env::var(format!("{prefix}{name}"));
+
+        let username = var("USERNAME").unwrap_or_default();
+        let password = var("PASSWORD").unwrap_or_default();
+        let use_demo = var("USE_DEMO")
             .unwrap_or_else(|_| "false".to_string())
             .parse()
             .unwrap_or(false);
-        let log_level = env::var("LOGLEVEL").unwrap_or_else(|_| "INFO".to_string());
-        let remember_me = env::var("TASTYTRADE_REMEMBER_ME")
+        let log_level = var("LOGLEVEL")
+            .or_else(|_| env::var("LOGLEVEL"))
+            .unwrap_or_else(|_| "INFO".to_string());
+        let remember_me = var("REMEMBER_ME")
             .unwrap_or_else(|_| "false".to_string())
             .parse()
             .unwrap_or(false);
+        let auto_relogin = var("AUTO_RELOGIN")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .unwrap_or(true);
 
         // Initialize logger with the specified log level
         setup_logger_with_level(&log_level);
 
+        let fallback = if use_demo {
+            Environment::Demo
+        } else {
+            Environment::Production
+        };
+        let environment = match var("ENVIRONMENT").map(|value| value.parse::<Environment>()) {
+            Ok(Ok(environment)) => environment,
+            Ok(Err(e)) => {
+                warn!("Ignoring {prefix}ENVIRONMENT: {e}");
+                fallback
+            }
+            Err(_) => fallback,
+        };
+
         Self {
             username,
             password,
-            use_demo,
+            use_demo: environment.is_sandbox(),
+            environment,
             log_level,
             remember_me,
-            base_url: if use_demo {
-                BASE_DEMO_URL.to_string()
-            } else {
-                BASE_URL.to_string()
-            },
-            websocket_url: if use_demo {
-                WEBSOCKET_DEMO_URL.to_string()
-            } else {
-                WEBSOCKET_URL.to_string()
-            },
+            remember_token: var("REMEMBER_TOKEN").ok().filter(|token| !token.is_empty()),
+            base_url: var("BASE_URL").unwrap_or_else(|_| environment.base_url().to_string()),
+            websocket_url: var("WEBSOCKET_URL")
+                .unwrap_or_else(|_| environment.websocket_url().to_string()),
+            dxlink_url: var("DXLINK_URL").ok(),
+            order_throttle: OrderThrottleConfig::default(),
+            environment_guard: None,
+            streamer: StreamerConfig::default(),
+            auto_relogin,
+            user_agent: var("USER_AGENT").ok().filter(|agent| !agent.is_empty()),
+            extra_headers: BTreeMap::new(),
+            env_prefix: Some(prefix.to_string()),
         }
     }
 
@@ -118,18 +475,174 @@ impl TastyTradeConfig {
         Ok(())
     }
 
-    /// Check if the configuration has valid credentials
+    /// Sets [`user_agent`](Self::user_agent).
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Adds `name: value` to [`extra_headers`](Self::extra_headers).
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Check if the configuration has valid credentials: a username, and a password or
+    /// a remember token.
     pub fn has_valid_credentials(&self) -> bool {
-        !self.username.is_empty() && !self.password.is_empty()
+        !self.username.is_empty() && (!self.password.is_empty() || self.remember_token.is_some())
     }
 
-    /// Creates a TastyTrade client from the configuration
-    pub async fn create_client(&self) -> Result<TastyTrade, TastyTradeError> {
-        if !self.has_valid_credentials() {
-            "Missing TastyTrade credentials. Please set TASTYTRADE_USERNAME and TASTYTRADE_PASSWORD \
-            environment variables or load from config file.".to_string();
+    /// `websocket_url` when it differs from the default of `environment`, i.e. when it
+    /// was set on purpose and must win over the URL reported with the session.
+    pub fn websocket_url_override(&self) -> Option<&str> {
+        (self.websocket_url != self.environment.websocket_url())
+            .then_some(self.websocket_url.as_str())
+    }
+
+    /// Checks the whole configuration and returns every problem found.
+    ///
+    /// An empty vector means the configuration is usable. Unlike
+    /// [`has_valid_credentials`](Self::has_valid_credentials) this does not stop at the
+    /// first issue, so all misconfigurations can be reported at once.
+    pub fn validate(&self) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+
+        if self.username.is_empty() {
+            problems.push(ConfigProblem::MissingUsername {
+                env_prefix: self.env_prefix.clone(),
+            });
+        }
+        if self.password.is_empty() && self.remember_token.is_none() {
+            problems.push(ConfigProblem::MissingPassword {
+                env_prefix: self.env_prefix.clone(),
+            });
+        }
+
+        if !VALID_LOG_LEVELS.contains(&self.log_level.to_uppercase().as_str()) {
+            problems.push(ConfigProblem::InvalidLogLevel(self.log_level.clone()));
+        }
+
+        if !self.base_url.starts_with("https://") && !self.base_url.starts_with("http://") {
+            problems.push(ConfigProblem::InvalidUrl {
+                field: "base_url",
+                url: self.base_url.clone(),
+            });
+        }
+        if !self.websocket_url.starts_with("wss://") && !self.websocket_url.starts_with("ws://") {
+            problems.push(ConfigProblem::InvalidUrl {
+                field: "websocket_url",
+                url: self.websocket_url.clone(),
+            });
+        }
+
+        if let Some(url) = &self.dxlink_url
+            && !url.starts_with("wss://")
+            && !url.starts_with("ws://")
+        {
+            problems.push(ConfigProblem::InvalidUrl {
+                field: "dxlink_url",
+                url: url.clone(),
+            });
+        }
+
+        if self.websocket_url.contains("dxfeed") {
+            problems.push(ConfigProblem::StreamingUrlMixup {
+                field: "websocket_url",
+                url: self.websocket_url.clone(),
+            });
+        }
+        if let Some(url) = &self.dxlink_url
+            && [
+                self.websocket_url.as_str(),
+                WEBSOCKET_URL,
+                WEBSOCKET_DEMO_URL,
+            ]
+            .contains(&url.as_str())
+        {
+            problems.push(ConfigProblem::StreamingUrlMixup {
+                field: "dxlink_url",
+                url: url.clone(),
+            });
+        }
+
+        if let Some(agent) = &self.user_agent
+            && HeaderValue::from_str(agent).is_err()
+        {
+            problems.push(ConfigProblem::InvalidHeader {
+                name: "User-Agent".to_string(),
+            });
+        }
+        for (name, value) in &self.extra_headers {
+            if HeaderName::from_bytes(name.as_bytes()).is_err()
+                || HeaderValue::from_str(value).is_err()
+            {
+                problems.push(ConfigProblem::InvalidHeader { name: name.clone() });
+            }
+        }
+
+        if self.use_demo != self.environment.is_sandbox() {
+            problems.push(ConfigProblem::EnvironmentConflict {
+                use_demo: self.use_demo,
+                environment: self.environment,
+            });
+        }
+
+        let (wrong_base, wrong_websocket) = if self.use_demo {
+            (BASE_URL, WEBSOCKET_URL)
+        } else {
+            (BASE_DEMO_URL, WEBSOCKET_DEMO_URL)
+        };
+        for url in [&self.base_url, &self.websocket_url] {
+            if url == wrong_base || url == wrong_websocket {
+                problems.push(ConfigProblem::DemoFlagConflict {
+                    use_demo: self.use_demo,
+                    url: url.clone(),
+                    env_prefix: self.env_prefix.clone(),
+                });
+            }
         }
 
+        problems
+    }
+
+    /// Runs [`validate`](Self::validate) and folds all problems into a single
+    /// `TastyTradeError::ConfigError`.
+    pub fn ensure_valid(&self) -> Result<(), TastyTradeError> {
+        Self::fold_problems(self.validate())
+    }
+
+    /// Same as [`ensure_valid`](Self::ensure_valid), except that missing credentials are
+    /// accepted: an [`AuthProvider`](crate::api::auth::AuthProvider) supplies them.
+    pub(crate) fn ensure_valid_without_credentials(&self) -> Result<(), TastyTradeError> {
+        Self::fold_problems(
+            self.validate()
+                .into_iter()
+                .filter(|p| {
+                    !matches!(
+                        p,
+                        ConfigProblem::MissingUsername { .. }
+                            | ConfigProblem::MissingPassword { .. }
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn fold_problems(problems: Vec<ConfigProblem>) -> Result<(), TastyTradeError> {
+        if problems.is_empty() {
+            return Ok(());
+        }
+        let message = problems
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join("; ");
+        Err(TastyTradeError::ConfigError(message))
+    }
+
+    /// Creates a TastyTrade client from the configuration
+    pub async fn create_client(&self) -> Result<TastyTrade, TastyTradeError> {
         let client = TastyTrade::login(self).await?;
         Ok(client)
     }
@@ -149,6 +662,7 @@ mod tests {
         assert!(!config.use_demo);
         assert_eq!(config.log_level, "INFO");
         assert!(!config.remember_me);
+        assert!(config.auto_relogin);
     }
 
     #[test]
@@ -198,10 +712,20 @@ mod tests {
             username: "test_user".to_string(),
             password: "test_pass".to_string(),
             use_demo: true,
+            environment: Environment::Demo,
             log_level: "DEBUG".to_string(),
             remember_me: true,
+            remember_token: Some("remember-abc".to_string()),
             base_url: BASE_DEMO_URL.to_string(),
             websocket_url: WEBSOCKET_DEMO_URL.to_string(),
+            dxlink_url: None,
+            order_throttle: OrderThrottleConfig::default(),
+            environment_guard: None,
+            streamer: StreamerConfig::default(),
+            auto_relogin: false,
+            user_agent: Some("desk-7/1.2".to_string()),
+            extra_headers: BTreeMap::from([("X-Desk".to_string(), "7".to_string())]),
+            env_prefix: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -220,6 +744,216 @@ mod tests {
         assert_eq!(config.use_demo, deserialized.use_demo);
         assert_eq!(config.log_level, deserialized.log_level);
         assert_eq!(config.remember_me, deserialized.remember_me);
+        assert_eq!(config.remember_token, deserialized.remember_token);
+        assert_eq!(config.environment, deserialized.environment);
+        assert_eq!(config.user_agent, deserialized.user_agent);
+        assert_eq!(config.extra_headers, deserialized.extra_headers);
+    }
+
+    #[test]
+    fn test_config_without_environment_follows_use_demo() {
+        let json = serde_json::json!({
+            "username": "user",
+            "use_demo": true,
+            "log_level": "INFO",
+            "remember_me": false,
+            "base_url": BASE_DEMO_URL,
+            "websocket_url": WEBSOCKET_DEMO_URL,
+        });
+        let config: TastyTradeConfig = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(config.environment, Environment::Demo);
+        assert!(config.environment.is_sandbox());
+
+        let mut json = json;
+        json["environment"] = "production".into();
+        let config: TastyTradeConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.environment, Environment::Production);
+        assert!(
+            config
+                .validate()
+                .contains(&ConfigProblem::EnvironmentConflict {
+                    use_demo: true,
+                    environment: Environment::Production,
+                })
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_from_env_certification() {
+        assert_eq!(
+            "cert".parse::<Environment>().unwrap(),
+            Environment::Certification
+        );
+        assert!("staging".parse::<Environment>().is_err());
+
+        unsafe {
+            env::set_var("CERT_ENVIRONMENT", "cert");
+            env::set_var("CERT_USE_DEMO", "false");
+        }
+        let config = TastyTradeConfig::from_env_with_prefix("CERT_");
+        assert_eq!(config.environment, Environment::Certification);
+        assert!(config.use_demo);
+        assert_eq!(config.base_url, BASE_DEMO_URL.to_string());
+
+        unsafe {
+            env::remove_var("CERT_ENVIRONMENT");
+            env::remove_var("CERT_USE_DEMO");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_from_env_with_prefix() {
+        unsafe {
+            env::set_var("BOT1_USERNAME", "bot_user");
+            env::set_var("BOT1_PASSWORD", "bot_pass");
+            env::set_var("BOT1_USE_DEMO", "true");
+            env::set_var("TASTYTRADE_USERNAME", "other_user");
+        }
+        let config = TastyTradeConfig::from_env_with_prefix("BOT1_");
+        assert_eq!(config.username, "bot_user");
+        assert_eq!(config.password, "bot_pass");
+        assert!(config.use_demo);
+        assert_eq!(config.base_url, BASE_DEMO_URL.to_string());
+
+        unsafe {
+            env::remove_var("BOT1_USERNAME");
+            env::remove_var("BOT1_PASSWORD");
+            env::remove_var("BOT1_USE_DEMO");
+            env::remove_var("TASTYTRADE_USERNAME");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_problems_name_the_prefixed_variables() {
+        let config = TastyTradeConfig::from_env_with_prefix("BOT2_");
+        assert_eq!(config.env_prefix.as_deref(), Some("BOT2_"));
+
+        let message = config.ensure_valid().unwrap_err().to_string();
+        assert!(message.contains("set BOT2_USERNAME"));
+        assert!(message.contains("set BOT2_PASSWORD"));
+        assert!(!message.contains("TASTYTRADE_"));
+
+        let message = TastyTradeConfig::default()
+            .ensure_valid()
+            .unwrap_err()
+            .to_string();
+        assert!(!message.contains("TASTYTRADE_"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_config_from_env_url_overrides() {
+        unsafe {
+            env::set_var("PROXY_USE_DEMO", "true");
+            env::set_var("PROXY_BASE_URL", "http://localhost:8080");
+            env::set_var("PROXY_DXLINK_URL", "ws://localhost:9090");
+        }
+        let config = TastyTradeConfig::from_env_with_prefix("PROXY_");
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.websocket_url, WEBSOCKET_DEMO_URL.to_string());
+        assert_eq!(config.dxlink_url.as_deref(), Some("ws://localhost:9090"));
+
+        unsafe {
+            env::remove_var("PROXY_USE_DEMO");
+            env::remove_var("PROXY_BASE_URL");
+            env::remove_var("PROXY_DXLINK_URL");
+        }
+    }
+
+    #[test]
+    fn test_websocket_url_override() {
+        let mut config = TastyTradeConfig::default();
+        assert_eq!(config.websocket_url_override(), None);
+
+        config.websocket_url = "wss://proxy.internal/streamer".to_string();
+        assert_eq!(
+            config.websocket_url_override(),
+            Some("wss://proxy.internal/streamer")
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let config = TastyTradeConfig {
+            use_demo: true,
+            log_level: "VERBOSE".to_string(),
+            ..Default::default()
+        };
+
+        let problems = config.validate();
+        assert!(problems.contains(&ConfigProblem::MissingUsername { env_prefix: None }));
+        assert!(problems.contains(&ConfigProblem::MissingPassword { env_prefix: None }));
+        assert!(problems.contains(&ConfigProblem::InvalidLogLevel("VERBOSE".to_string())));
+        assert!(problems.contains(&ConfigProblem::DemoFlagConflict {
+            use_demo: true,
+            url: BASE_URL.to_string(),
+            env_prefix: None,
+        }));
+
+        let err = config.ensure_valid().unwrap_err();
+        assert!(err.to_string().contains("missing username"));
+        assert!(err.to_string().contains("VERBOSE"));
+    }
+
+    #[test]
+    fn test_validate_rejects_swapped_streaming_urls() {
+        let config = TastyTradeConfig {
+            websocket_url: "wss://tasty-openapi-ws.dxfeed.com/realtime".to_string(),
+            dxlink_url: Some(WEBSOCKET_URL.to_string()),
+            ..Default::default()
+        };
+
+        let problems = config.validate();
+        assert!(problems.contains(&ConfigProblem::StreamingUrlMixup {
+            field: "websocket_url",
+            url: "wss://tasty-openapi-ws.dxfeed.com/realtime".to_string(),
+        }));
+        assert!(problems.contains(&ConfigProblem::StreamingUrlMixup {
+            field: "dxlink_url",
+            url: WEBSOCKET_URL.to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_headers() {
+        let config = TastyTradeConfig::default()
+            .with_user_agent("desk\n7")
+            .with_header("X Desk", "7")
+            .with_header("X-Desk", "7");
+
+        let problems = config.validate();
+        assert!(problems.contains(&ConfigProblem::InvalidHeader {
+            name: "User-Agent".to_string()
+        }));
+        assert!(problems.contains(&ConfigProblem::InvalidHeader {
+            name: "X Desk".to_string()
+        }));
+        assert!(!problems.contains(&ConfigProblem::InvalidHeader {
+            name: "X-Desk".to_string()
+        }));
+    }
+
+    #[test]
+    fn test_validate_accepts_consistent_config() {
+        let config = TastyTradeConfig {
+            username: "user".to_string(),
+            password: "pass".to_string(),
+            log_level: "debug".to_string(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_empty());
+        assert!(config.ensure_valid().is_ok());
+
+        let remembered = TastyTradeConfig {
+            password: String::new(),
+            remember_token: Some("remember-abc".to_string()),
+            ..config
+        };
+        assert!(remembered.has_valid_credentials());
+        assert!(remembered.validate().is_empty());
     }
 
     #[test]
@@ -0,0 +1,219 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # DataFrame export
+//!
+//! Conversions from the crate's response types into [`polars::prelude::DataFrame`]s,
+//! available behind the `polars` feature: positions, option chains, external
+//! transactions and cached candle history. Monetary values are exported as `f64`
+//! columns, identifiers and dates as string columns.
+//!
+//! ```rust,ignore
+//! use tastytrade::utils::dataframe::ToDataFrame;
+//!
+//! let positions = account.positions().await?;
+//! let df = positions.to_dataframe()?;
+//! println!("{df}");
+//! ```
+
+use crate::FullPosition;
+use crate::types::instrument::NestedOptionChain;
+use crate::types::transaction::ExternalTransaction;
+use crate::utils::candle_cache::Candle;
+use polars::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Conversion of a collection of API records into a polars `DataFrame`.
+pub trait ToDataFrame {
+    /// Builds a `DataFrame` with one row per record.
+    fn to_dataframe(&self) -> PolarsResult<DataFrame>;
+}
+
+fn to_f64(value: &Decimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+impl ToDataFrame for [FullPosition] {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        df!(
+            "account_number" => self.iter().map(|p| p.account_number.0.clone()).collect::<Vec<_>>(),
+            "symbol" => self.iter().map(|p| p.symbol.0.clone()).collect::<Vec<_>>(),
+            "instrument_type" => self.iter().map(|p| p.instrument_type.to_string()).collect::<Vec<_>>(),
+            "underlying_symbol" => self.iter().map(|p| p.underlying_symbol.0.clone()).collect::<Vec<_>>(),
+            "quantity" => self.iter().map(|p| to_f64(&p.quantity)).collect::<Vec<_>>(),
+            "quantity_direction" => self.iter().map(|p| p.quantity_direction.to_string()).collect::<Vec<_>>(),
+            "close_price" => self.iter().map(|p| to_f64(&p.close_price)).collect::<Vec<_>>(),
+            "average_open_price" => self.iter().map(|p| to_f64(&p.average_open_price)).collect::<Vec<_>>(),
+            "multiplier" => self.iter().map(|p| to_f64(&p.multiplier)).collect::<Vec<_>>(),
+            "cost_effect" => self.iter().map(|p| p.cost_effect.to_string()).collect::<Vec<_>>(),
+            "realized_day_gain" => self.iter().map(|p| to_f64(&p.realized_day_gain)).collect::<Vec<_>>(),
+            "realized_today" => self.iter().map(|p| to_f64(&p.realized_today)).collect::<Vec<_>>(),
+            "created_at" => self.iter().map(|p| p.created_at.clone()).collect::<Vec<_>>(),
+            "updated_at" => self.iter().map(|p| p.updated_at.clone()).collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl ToDataFrame for Vec<FullPosition> {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        self.as_slice().to_dataframe()
+    }
+}
+
+/// Flattens the chain into one row per (expiration, strike).
+impl ToDataFrame for NestedOptionChain {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let rows: Vec<_> = self
+            .expirations
+            .iter()
+            .flat_map(|exp| exp.strikes.iter().map(move |strike| (exp, strike)))
+            .collect();
+
+        df!(
+            "underlying_symbol" => rows.iter().map(|_| self.underlying_symbol.0.clone()).collect::<Vec<_>>(),
+            "root_symbol" => rows.iter().map(|_| self.root_symbol.0.clone()).collect::<Vec<_>>(),
+            "expiration_date" => rows.iter().map(|(e, _)| e.expiration_date.clone()).collect::<Vec<_>>(),
+            "expiration_type" => rows.iter().map(|(e, _)| e.expiration_type.clone()).collect::<Vec<_>>(),
+            "days_to_expiration" => rows.iter().map(|(e, _)| e.days_to_expiration).collect::<Vec<_>>(),
+            "settlement_type" => rows.iter().map(|(e, _)| e.settlement_type.clone()).collect::<Vec<_>>(),
+            "strike_price" => rows.iter().map(|(_, s)| to_f64(&s.strike_price)).collect::<Vec<_>>(),
+            "call" => rows.iter().map(|(_, s)| s.call.0.clone()).collect::<Vec<_>>(),
+            "call_streamer_symbol" => rows.iter().map(|(_, s)| s.call_streamer_symbol.0.clone()).collect::<Vec<_>>(),
+            "put" => rows.iter().map(|(_, s)| s.put.0.clone()).collect::<Vec<_>>(),
+            "put_streamer_symbol" => rows.iter().map(|(_, s)| s.put_streamer_symbol.0.clone()).collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl ToDataFrame for [NestedOptionChain] {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let mut frames = self.iter().map(|chain| chain.to_dataframe());
+        let mut df = match frames.next() {
+            Some(first) => first?,
+            None => empty_chain_frame()?,
+        };
+        for frame in frames {
+            df.vstack_mut(&frame?)?;
+        }
+        Ok(df)
+    }
+}
+
+impl ToDataFrame for Vec<NestedOptionChain> {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        self.as_slice().to_dataframe()
+    }
+}
+
+impl ToDataFrame for [ExternalTransaction] {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        df!(
+            "id" => self.iter().map(|t| t.id).collect::<Vec<_>>(),
+            "account_number" => self.iter().map(|t| t.account_number.0.clone()).collect::<Vec<_>>(),
+            "amount" => self.iter().map(|t| to_f64(&t.amount)).collect::<Vec<_>>(),
+            "signed_amount" => self.iter().map(|t| to_f64(&t.signed_amount())).collect::<Vec<_>>(),
+            "direction" => self.iter().map(|t| t.direction.to_string()).collect::<Vec<_>>(),
+            "state" => self.iter().map(|t| t.state.to_string()).collect::<Vec<_>>(),
+            "disbursement_type" => self.iter().map(|t| t.disbursement_type.clone()).collect::<Vec<_>>(),
+            "bank_name" => self.iter().map(|t| t.bank_name.clone()).collect::<Vec<_>>(),
+            "funds_available_date" => self.iter().map(|t| t.funds_available_date.clone()).collect::<Vec<_>>(),
+            "created_at" => self.iter().map(|t| t.created_at.map(|d| d.to_rfc3339())).collect::<Vec<_>>(),
+            "updated_at" => self.iter().map(|t| t.updated_at.map(|d| d.to_rfc3339())).collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl ToDataFrame for Vec<ExternalTransaction> {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        self.as_slice().to_dataframe()
+    }
+}
+
+/// One row per bar, `time` in milliseconds since the Unix epoch.
+impl ToDataFrame for [Candle] {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        df!(
+            "time" => self.iter().map(|c| c.time).collect::<Vec<_>>(),
+            "open" => self.iter().map(|c| c.open).collect::<Vec<_>>(),
+            "high" => self.iter().map(|c| c.high).collect::<Vec<_>>(),
+            "low" => self.iter().map(|c| c.low).collect::<Vec<_>>(),
+            "close" => self.iter().map(|c| c.close).collect::<Vec<_>>(),
+            "volume" => self.iter().map(|c| c.volume).collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl ToDataFrame for Vec<Candle> {
+    fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        self.as_slice().to_dataframe()
+    }
+}
+
+fn empty_chain_frame() -> PolarsResult<DataFrame> {
+    NestedOptionChain {
+        underlying_symbol: "".into(),
+        root_symbol: "".into(),
+        option_chain_type: String::new(),
+        shares_per_contract: 0,
+        deliverables: Vec::new(),
+        expirations: Vec::new(),
+    }
+    .to_dataframe()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions_to_dataframe() {
+        let positions = vec![crate::test_fixtures::position(serde_json::json!({}))];
+
+        let df = positions.to_dataframe().unwrap();
+        assert_eq!(df.height(), 1);
+        assert_eq!(df.width(), 14);
+    }
+
+    #[test]
+    fn test_transactions_and_candles_to_dataframe() {
+        let json = r#"[{
+            "id": 987654,
+            "account-number": "5WT00001",
+            "amount": "2500.00",
+            "direction": "Outgoing",
+            "state": "Processing",
+            "created-at": "2024-06-03T14:30:00Z"
+        }]"#;
+        let transactions: Vec<ExternalTransaction> = serde_json::from_str(json).unwrap();
+        let df = transactions.to_dataframe().unwrap();
+        assert_eq!(df.height(), 1);
+        assert_eq!(df.width(), 11);
+
+        let candles = vec![
+            Candle {
+                time: 1_717_372_800_000,
+                open: 527.0,
+                high: 530.5,
+                low: 526.2,
+                close: 529.9,
+                volume: 4.1e7,
+            };
+            3
+        ];
+        let df = candles.to_dataframe().unwrap();
+        assert_eq!(df.height(), 3);
+        assert_eq!(df.width(), 6);
+    }
+
+    #[test]
+    fn test_empty_chains_to_dataframe() {
+        let chains: Vec<NestedOptionChain> = Vec::new();
+        let df = chains.to_dataframe().unwrap();
+        assert_eq!(df.height(), 0);
+        assert_eq!(df.width(), 11);
+    }
+}
@@ -0,0 +1,119 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Credentials kept in the operating system keychain.
+//!
+//! With the `keyring` feature the password and remember token no longer need to sit in
+//! a `.env` or JSON file. Each profile is a keychain service named
+//! `tastytrade:<profile>` holding the `username`, `password` and `remember-token`
+//! entries; everything else is read from the environment as in
+//! [`TastyTradeConfig::from_env`].
+//!
+//! ```rust,ignore
+//! // Once, e.g. from a setup command
+//! config.save_to_keyring("bot")?;
+//!
+//! // On every start
+//! let mut config = TastyTradeConfig::from_keyring("bot")?;
+//! let tasty = TastyTrade::login(&config).await?;
+//! config.remember_token = tasty.remember_token();
+//! config.save_to_keyring("bot")?;
+//! ```
+
+use crate::TastyTradeError;
+use crate::utils::config::TastyTradeConfig;
+use ::keyring::Entry;
+
+const USERNAME_ENTRY: &str = "username";
+const PASSWORD_ENTRY: &str = "password";
+const REMEMBER_TOKEN_ENTRY: &str = "remember-token";
+
+/// The keychain service holding the credentials of `profile`.
+pub fn keyring_service(profile: &str) -> String {
+    format!("tastytrade:{profile}")
+}
+
+fn entry(profile: &str, name: &str) -> Result<Entry, TastyTradeError> {
+    Entry::new(&keyring_service(profile), name).map_err(|e| keyring_error(profile, name, e))
+}
+
+fn keyring_error(profile: &str, name: &str, e: ::keyring::Error) -> TastyTradeError {
+    TastyTradeError::ConfigError(format!(
+        "keychain entry '{name}' of profile '{profile}': {e}"
+    ))
+}
+
+/// Reads an entry, `None` when it does not exist.
+fn read(profile: &str, name: &str) -> Result<Option<String>, TastyTradeError> {
+    match entry(profile, name)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(::keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(keyring_error(profile, name, e)),
+    }
+}
+
+/// Writes an entry, or deletes it when `value` is `None`.
+fn write(profile: &str, name: &str, value: Option<&str>) -> Result<(), TastyTradeError> {
+    let entry = entry(profile, name)?;
+    let result = match value {
+        Some(value) => entry.set_password(value),
+        None => match entry.delete_credential() {
+            Err(::keyring::Error::NoEntry) => Ok(()),
+            other => other,
+        },
+    };
+    result.map_err(|e| keyring_error(profile, name, e))
+}
+
+impl TastyTradeConfig {
+    /// Loads the configuration from the environment, taking the username, password and
+    /// remember token from the keychain entries of `profile`.
+    ///
+    /// Keychain entries take precedence over the environment; missing ones leave the
+    /// environment value in place.
+    pub fn from_keyring(profile: &str) -> Result<Self, TastyTradeError> {
+        let mut config = Self::from_env();
+        if let Some(username) = read(profile, USERNAME_ENTRY)? {
+            config.username = username;
+        }
+        if let Some(password) = read(profile, PASSWORD_ENTRY)? {
+            config.password = password;
+        }
+        if let Some(token) = read(profile, REMEMBER_TOKEN_ENTRY)? {
+            config.remember_token = Some(token);
+        }
+        Ok(config)
+    }
+
+    /// Stores the username, password and remember token in the keychain entries of
+    /// `profile`.
+    ///
+    /// An empty password is not stored, so saving a config loaded with only a remember
+    /// token keeps the password saved earlier. A missing remember token deletes the
+    /// saved one: it can no longer be used.
+    pub fn save_to_keyring(&self, profile: &str) -> Result<(), TastyTradeError> {
+        write(profile, USERNAME_ENTRY, Some(&self.username))?;
+        if !self.password.is_empty() {
+            write(profile, PASSWORD_ENTRY, Some(&self.password))?;
+        }
+        write(
+            profile,
+            REMEMBER_TOKEN_ENTRY,
+            self.remember_token.as_deref(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyring_service_per_profile() {
+        assert_eq!(keyring_service("bot"), "tastytrade:bot");
+        assert_ne!(keyring_service("bot"), keyring_service("paper"));
+    }
+}
@@ -71,6 +71,13 @@ pub mod config;
 /// and logger setup for the application.
 pub mod logger;
 
+pub mod candle_cache;
+pub mod clock;
+#[cfg(feature = "polars")]
+pub mod dataframe;
 pub mod download;
 pub mod file;
+#[cfg(feature = "keyring")]
+pub mod keyring;
 pub mod parse;
+pub mod universe;
@@ -0,0 +1,200 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Changes in the downloaded symbol universe between runs.
+//!
+//! A daily job calling [`download_options_symbols`](crate::utils::download::download_options_symbols)
+//! gets the whole universe every time. Keeping the previous run as a [`SymbolUniverse`]
+//! file lets it publish only a [`UniverseDiff`]: the symbols listed since, the ones
+//! gone, and which of the gone ones simply expired.
+//!
+//! ```rust,ignore
+//! let previous = SymbolUniverse::load("universe.json")?.unwrap_or_default();
+//! let current = previous.next(download_options_symbols().await?);
+//! let diff = previous.diff(&current);
+//! publish(&diff);
+//! current.save("universe.json")?;
+//! ```
+
+use crate::api::base::TastyResult;
+use crate::prelude::SymbolEntry;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Set operations on lists of [`SymbolEntry`], which are identified by symbol and epic.
+pub trait SymbolSetExt {
+    /// Entries of `self` missing from `previous`.
+    fn added<'a>(&'a self, previous: &[SymbolEntry]) -> Vec<&'a SymbolEntry>;
+
+    /// Entries of `previous` missing from `self`.
+    fn removed<'a>(&self, previous: &'a [SymbolEntry]) -> Vec<&'a SymbolEntry>;
+
+    /// Entries whose expiry falls after `last_run` and no later than now.
+    fn expired_since(&self, last_run: DateTime<Utc>) -> Vec<&SymbolEntry>;
+}
+
+impl SymbolSetExt for [SymbolEntry] {
+    fn added<'a>(&'a self, previous: &[SymbolEntry]) -> Vec<&'a SymbolEntry> {
+        let previous: HashSet<&SymbolEntry> = previous.iter().collect();
+        self.iter().filter(|e| !previous.contains(e)).collect()
+    }
+
+    fn removed<'a>(&self, previous: &'a [SymbolEntry]) -> Vec<&'a SymbolEntry> {
+        previous.added(self)
+    }
+
+    fn expired_since(&self, last_run: DateTime<Utc>) -> Vec<&SymbolEntry> {
+        let now = Utc::now();
+        self.iter()
+            .filter(|e| e.expiry > last_run && e.expiry <= now)
+            .collect()
+    }
+}
+
+/// One persisted run of the symbol download.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolUniverse {
+    /// Incremented on every run, starting at 1. `0` is the empty default.
+    pub version: u64,
+    /// When the symbols were downloaded.
+    pub created_at: DateTime<Utc>,
+    /// The downloaded entries.
+    pub symbols: Vec<SymbolEntry>,
+}
+
+/// What changed between two [`SymbolUniverse`] versions.
+#[derive(Debug, Clone, Serialize)]
+pub struct UniverseDiff {
+    /// Version the diff starts from.
+    pub from_version: u64,
+    /// Version the diff leads to.
+    pub to_version: u64,
+    /// Entries listed since the older version.
+    pub added: Vec<SymbolEntry>,
+    /// Entries no longer listed, expired ones included.
+    pub removed: Vec<SymbolEntry>,
+    /// The subset of `removed` whose expiry passed between the two versions.
+    pub expired: Vec<SymbolEntry>,
+}
+
+impl UniverseDiff {
+    /// Returns `true` when nothing was added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl SymbolUniverse {
+    /// The version following this one, holding `symbols` downloaded now.
+    pub fn next(&self, symbols: Vec<SymbolEntry>) -> Self {
+        Self {
+            version: self.version + 1,
+            created_at: Utc::now(),
+            symbols,
+        }
+    }
+
+    /// Changes from this version to `newer`.
+    pub fn diff(&self, newer: &SymbolUniverse) -> UniverseDiff {
+        let removed: Vec<SymbolEntry> = newer
+            .symbols
+            .removed(&self.symbols)
+            .into_iter()
+            .cloned()
+            .collect();
+        let expired = removed
+            .iter()
+            .filter(|e| e.expiry > self.created_at && e.expiry <= newer.created_at)
+            .cloned()
+            .collect();
+        UniverseDiff {
+            from_version: self.version,
+            to_version: newer.version,
+            added: newer
+                .symbols
+                .added(&self.symbols)
+                .into_iter()
+                .cloned()
+                .collect(),
+            removed,
+            expired,
+        }
+    }
+
+    /// Reads a version saved with [`save`](Self::save), `None` if the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> TastyResult<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Some(serde_json::from_str(&contents)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes this version as JSON, replacing the previous file.
+    pub fn save(&self, path: impl AsRef<Path>) -> TastyResult<()> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InstrumentType;
+    use chrono::Duration;
+
+    fn entry(symbol: &str, expiry: DateTime<Utc>) -> SymbolEntry {
+        SymbolEntry {
+            symbol: symbol.to_string(),
+            epic: symbol.to_string(),
+            name: symbol.to_string(),
+            instrument_type: InstrumentType::EquityOption,
+            exchange: "CBOE".to_string(),
+            expiry,
+            last_update: expiry,
+        }
+    }
+
+    #[test]
+    fn test_universe_diff_reports_added_removed_and_expired() {
+        let day_one = Utc::now() - Duration::days(2);
+        let previous = SymbolUniverse {
+            version: 1,
+            created_at: day_one,
+            symbols: vec![
+                entry("SPY 1", day_one + Duration::days(1)),
+                entry("SPY 2", day_one + Duration::days(30)),
+                entry("SPY 3", day_one + Duration::days(60)),
+            ],
+        };
+        let mut current = previous.next(vec![
+            entry("SPY 2", day_one + Duration::days(30)),
+            entry("SPY 4", day_one + Duration::days(90)),
+        ]);
+        assert_eq!(current.version, 2);
+
+        let diff = previous.diff(&current);
+        let symbols = |entries: &[SymbolEntry]| -> Vec<String> {
+            entries.iter().map(|e| e.symbol.clone()).collect()
+        };
+        assert_eq!(symbols(&diff.added), ["SPY 4"]);
+        assert_eq!(symbols(&diff.removed), ["SPY 1", "SPY 3"]);
+        assert_eq!(symbols(&diff.expired), ["SPY 1"]);
+        assert_eq!(previous.symbols.expired_since(day_one).len(), 1);
+
+        let path =
+            std::env::temp_dir().join(format!("tastytrade-universe-{}.json", std::process::id()));
+        current.save(&path).unwrap();
+        current = SymbolUniverse::load(&path).unwrap().unwrap();
+        assert_eq!(current.symbols.len(), 2);
+        assert!(current.diff(&current).is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+}
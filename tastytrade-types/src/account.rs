@@ -0,0 +1,91 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(
+    DebugPretty, DisplaySimple, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct AccountNumber(pub String);
+
+impl<T: AsRef<str>> From<T> for AccountNumber {
+    fn from(value: T) -> Self {
+        Self(value.as_ref().to_owned())
+    }
+}
+
+/// The customer's authority over an account, from `/customers/me/accounts`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum AuthorityLevel {
+    /// The customer owns the account.
+    Owner,
+    /// The customer may trade the account on the owner's behalf.
+    TradingAuthority,
+    /// The customer may only view the account.
+    ReadOnly,
+    /// An authority level this version does not know yet, holding the raw API value.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl AuthorityLevel {
+    /// Parses the raw API value; unrecognized values become [`AuthorityLevel::Unknown`].
+    pub fn parse(raw: &str) -> Self {
+        match raw {
+            "owner" => AuthorityLevel::Owner,
+            "trading-authority" => AuthorityLevel::TradingAuthority,
+            "read-only" => AuthorityLevel::ReadOnly,
+            other => AuthorityLevel::Unknown(other.to_string()),
+        }
+    }
+
+    /// Returns `true` when orders may be placed on the account. Unknown levels are
+    /// treated as not allowed.
+    pub fn can_trade(&self) -> bool {
+        matches!(
+            self,
+            AuthorityLevel::Owner | AuthorityLevel::TradingAuthority
+        )
+    }
+}
+
+impl fmt::Display for AuthorityLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuthorityLevel::Owner => write!(f, "owner"),
+            AuthorityLevel::TradingAuthority => write!(f, "trading-authority"),
+            AuthorityLevel::ReadOnly => write!(f, "read-only"),
+            AuthorityLevel::Unknown(raw) => write!(f, "{raw}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authority_level() {
+        for raw in ["owner", "trading-authority", "read-only", "custodian"] {
+            let parsed = AuthorityLevel::parse(raw);
+            assert_eq!(parsed.to_string(), raw);
+            let json = serde_json::to_string(raw).unwrap();
+            assert_eq!(
+                serde_json::from_str::<AuthorityLevel>(&json).unwrap(),
+                parsed
+            );
+        }
+        assert!(AuthorityLevel::parse("trading-authority").can_trade());
+        assert!(!AuthorityLevel::parse("read-only").can_trade());
+        assert!(!AuthorityLevel::parse("custodian").can_trade());
+    }
+}
@@ -3,8 +3,8 @@
    Email: jb@taunais.com
    Date: 9/3/25
 ******************************************************************************/
-use crate::PriceEffect;
-use crate::accounts::AccountNumber;
+use crate::account::AccountNumber;
+use crate::order::PriceEffect;
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -18,6 +18,7 @@ use std::fmt;
 /// from a data source using `serde` with kebab-case renaming.  All numeric values are represented as
 /// `Decimal` for precision.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct Balance {
     /// The account number associated with this balance information.
@@ -25,118 +26,146 @@ pub struct Balance {
 
     /// The cash balance available in the account.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub cash_balance: Decimal,
 
     /// The total value of long equity positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub long_equity_value: Decimal,
 
     /// The total value of short equity positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub short_equity_value: Decimal,
 
     /// The total value of long derivative positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub long_derivative_value: Decimal,
 
     /// The total value of short derivative positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub short_derivative_value: Decimal,
 
     /// The total value of long futures positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub long_futures_value: Decimal,
 
     /// The total value of short futures positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub short_futures_value: Decimal,
 
-    /// The total value of long futures derivative positions.
-    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
-    pub long_futures_derivative_value: Decimal,
+    /// The total value of long futures derivative positions. Omitted by the sandbox.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub long_futures_derivative_value: Option<Decimal>,
 
-    /// The total value of short futures derivative positions.
-    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
-    pub short_futures_derivative_value: Decimal,
+    /// The total value of short futures derivative positions. Omitted by the sandbox.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub short_futures_derivative_value: Option<Decimal>,
 
     /// The total value of long marginable positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub long_margineable_value: Decimal,
 
     /// The total value of short marginable positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub short_margineable_value: Decimal,
 
     /// The margin equity.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub margin_equity: Decimal,
 
     /// The equity buying power.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub equity_buying_power: Decimal,
 
     /// The derivative buying power.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub derivative_buying_power: Decimal,
 
     /// The day trading buying power.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub day_trading_buying_power: Decimal,
 
-    /// The futures margin requirement.
-    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
-    pub futures_margin_requirement: Decimal,
+    /// The futures margin requirement. Omitted by the sandbox.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub futures_margin_requirement: Option<Decimal>,
 
     /// The available trading funds.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub available_trading_funds: Decimal,
 
     /// The maintenance requirement.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub maintenance_requirement: Decimal,
 
     /// The maintenance call value.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub maintenance_call_value: Decimal,
 
     /// The Reg T call value.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub reg_t_call_value: Decimal,
 
     /// The day trading call value.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub day_trading_call_value: Decimal,
 
-    /// The day equity call value.
-    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
-    pub day_equity_call_value: Decimal,
+    /// The day equity call value. Omitted by the sandbox.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub day_equity_call_value: Option<Decimal>,
 
     /// The net liquidating value.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub net_liquidating_value: Decimal,
 
     /// The cash available to withdraw.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub cash_available_to_withdraw: Decimal,
 
     /// The day trade excess.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub day_trade_excess: Decimal,
 
     /// The pending cash.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub pending_cash: Decimal,
 
     /// The pending cash effect.
     pub pending_cash_effect: PriceEffect,
 
-    /// The pending margin interest.
-    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
-    pub pending_margin_interest: Decimal,
+    /// The pending margin interest. Omitted by the sandbox.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub pending_margin_interest: Option<Decimal>,
 
-    /// Effective cryptocurrency buying power
-    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
-    pub effective_cryptocurrency_buying_power: Decimal,
+    /// Effective cryptocurrency buying power. Omitted by the sandbox.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub effective_cryptocurrency_buying_power: Option<Decimal>,
 
     /// The timestamp of the last balance update.
     pub updated_at: String,
@@ -150,87 +179,114 @@ pub struct Balance {
 /// and margin-related values.  All monetary values are represented using `Decimal`
 /// for precision.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct BalanceSnapshot {
     /// The account number associated with this balance snapshot.
     pub account_number: AccountNumber,
     /// The cash balance in the account.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub cash_balance: Decimal,
     /// The value of long equity positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub long_equity_value: Decimal,
     /// The value of short equity positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub short_equity_value: Decimal,
     /// The value of long derivative positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub long_derivative_value: Decimal,
     /// The value of short derivative positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub short_derivative_value: Decimal,
     /// The value of long futures positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub long_futures_value: Decimal,
     /// The value of short futures positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub short_futures_value: Decimal,
     /// The value of long futures derivative positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub long_futures_derivative_value: Decimal,
     /// The value of short futures derivative positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub short_futures_derivative_value: Decimal,
     /// The value of long margineable positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub long_margineable_value: Decimal,
     /// The value of short margineable positions.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub short_margineable_value: Decimal,
     /// The margin equity.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub margin_equity: Decimal,
     /// The equity buying power.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub equity_buying_power: Decimal,
     /// The derivative buying power.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub derivative_buying_power: Decimal,
     /// The day trading buying power.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub day_trading_buying_power: Decimal,
     /// The futures margin requirement.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub futures_margin_requirement: Decimal,
     /// The available trading funds.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub available_trading_funds: Decimal,
     /// The maintenance requirement.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub maintenance_requirement: Decimal,
     /// The maintenance call value.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub maintenance_call_value: Decimal,
     /// The Reg T call value.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub reg_t_call_value: Decimal,
     /// The day trading call value.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub day_trading_call_value: Decimal,
     /// The day equity call value.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub day_equity_call_value: Decimal,
     /// The net liquidating value.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub net_liquidating_value: Decimal,
     /// The cash available to withdraw.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub cash_available_to_withdraw: Decimal,
     /// The day trade excess.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub day_trade_excess: Decimal,
     /// The pending cash.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub pending_cash: Decimal,
     /// The effect of pending cash on the account.
     pub pending_cash_effect: PriceEffect,
@@ -240,6 +296,7 @@ pub struct BalanceSnapshot {
 
 /// Represents the time of day for a snapshot.
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum SnapshotTimeOfDay {
     /// End of Day.
     #[serde(rename = "EOD")]
@@ -255,14 +312,92 @@ impl fmt::Display for SnapshotTimeOfDay {
     }
 }
 
+/// One bar of an account's net liquidating value history.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct NetLiqHistoryPoint {
+    /// Start of the bar, as formatted by the API (e.g. "2024-01-02 14:30:00+00").
+    pub time: String,
+    /// Net liquidating value at the start of the bar.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
+    pub open: Decimal,
+    /// Highest net liquidating value during the bar.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
+    pub high: Decimal,
+    /// Lowest net liquidating value during the bar.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
+    pub low: Decimal,
+    /// Net liquidating value at the end of the bar.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
+    pub close: Decimal,
+}
+
+/// How far back a net liquidating value history reaches.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TimeBack {
+    /// The current day.
+    #[serde(rename = "1d")]
+    Day,
+    /// One month.
+    #[serde(rename = "1m")]
+    Month,
+    /// Three months.
+    #[serde(rename = "3m")]
+    ThreeMonths,
+    /// Six months.
+    #[serde(rename = "6m")]
+    SixMonths,
+    /// One year.
+    #[serde(rename = "1y")]
+    Year,
+    /// Since the account was opened.
+    #[serde(rename = "all")]
+    All,
+}
+
+impl fmt::Display for TimeBack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match self {
+            TimeBack::Day => "1d",
+            TimeBack::Month => "1m",
+            TimeBack::ThreeMonths => "3m",
+            TimeBack::SixMonths => "6m",
+            TimeBack::Year => "1y",
+            TimeBack::All => "all",
+        };
+        write!(f, "{value}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::accounts::AccountNumber;
+    use crate::account::AccountNumber;
     use chrono::Datelike;
     use rust_decimal::Decimal;
     use std::str::FromStr;
 
+    #[test]
+    fn test_net_liq_history_point_deserialization() {
+        let json = r#"{
+            "open": "10000.5",
+            "high": "10100.0",
+            "low": "9950.25",
+            "close": "10050.0",
+            "time": "2024-01-02 14:30:00+00"
+        }"#;
+        let point: NetLiqHistoryPoint = serde_json::from_str(json).unwrap();
+        assert_eq!(point.close, Decimal::from_str("10050.0").unwrap());
+        assert_eq!(point.low, Decimal::from_str("9950.25").unwrap());
+        assert_eq!(TimeBack::ThreeMonths.to_string(), "3m");
+    }
+
     #[test]
     fn test_snapshot_time_of_day_display() {
         assert_eq!(format!("{}", SnapshotTimeOfDay::Eod), "Eod");
@@ -300,28 +435,28 @@ mod tests {
             short_derivative_value: Decimal::from_str("0.00").unwrap(),
             long_futures_value: Decimal::from_str("0.00").unwrap(),
             short_futures_value: Decimal::from_str("0.00").unwrap(),
-            long_futures_derivative_value: Decimal::from_str("0.00").unwrap(),
-            short_futures_derivative_value: Decimal::from_str("0.00").unwrap(),
+            long_futures_derivative_value: Some(Decimal::from_str("0.00").unwrap()),
+            short_futures_derivative_value: Some(Decimal::from_str("0.00").unwrap()),
             long_margineable_value: Decimal::from_str("5000.00").unwrap(),
             short_margineable_value: Decimal::from_str("0.00").unwrap(),
             margin_equity: Decimal::from_str("6500.50").unwrap(),
             equity_buying_power: Decimal::from_str("13000.00").unwrap(),
             derivative_buying_power: Decimal::from_str("6500.50").unwrap(),
             day_trading_buying_power: Decimal::from_str("26000.00").unwrap(),
-            futures_margin_requirement: Decimal::from_str("0.00").unwrap(),
+            futures_margin_requirement: Some(Decimal::from_str("0.00").unwrap()),
             available_trading_funds: Decimal::from_str("6500.50").unwrap(),
             maintenance_requirement: Decimal::from_str("0.00").unwrap(),
             maintenance_call_value: Decimal::from_str("0.00").unwrap(),
             reg_t_call_value: Decimal::from_str("0.00").unwrap(),
             day_trading_call_value: Decimal::from_str("0.00").unwrap(),
-            day_equity_call_value: Decimal::from_str("0.00").unwrap(),
+            day_equity_call_value: Some(Decimal::from_str("0.00").unwrap()),
             net_liquidating_value: Decimal::from_str("6500.50").unwrap(),
             cash_available_to_withdraw: Decimal::from_str("1000.50").unwrap(),
             day_trade_excess: Decimal::from_str("26000.00").unwrap(),
             pending_cash: Decimal::from_str("0.00").unwrap(),
             pending_cash_effect: PriceEffect::None,
-            pending_margin_interest: Decimal::from_str("0.00").unwrap(),
-            effective_cryptocurrency_buying_power: Decimal::from_str("0.00").unwrap(),
+            pending_margin_interest: Some(Decimal::from_str("0.00").unwrap()),
+            effective_cryptocurrency_buying_power: Some(Decimal::from_str("0.00").unwrap()),
             updated_at: "2024-01-01T12:00:00Z".to_string(),
         };
 
@@ -383,28 +518,28 @@ mod tests {
             short_derivative_value: Decimal::from_str("0.00").unwrap(),
             long_futures_value: Decimal::from_str("0.00").unwrap(),
             short_futures_value: Decimal::from_str("0.00").unwrap(),
-            long_futures_derivative_value: Decimal::from_str("0.00").unwrap(),
-            short_futures_derivative_value: Decimal::from_str("0.00").unwrap(),
+            long_futures_derivative_value: Some(Decimal::from_str("0.00").unwrap()),
+            short_futures_derivative_value: Some(Decimal::from_str("0.00").unwrap()),
             long_margineable_value: Decimal::from_str("500.00").unwrap(),
             short_margineable_value: Decimal::from_str("0.00").unwrap(),
             margin_equity: Decimal::from_str("600.00").unwrap(),
             equity_buying_power: Decimal::from_str("1200.00").unwrap(),
             derivative_buying_power: Decimal::from_str("600.00").unwrap(),
             day_trading_buying_power: Decimal::from_str("2400.00").unwrap(),
-            futures_margin_requirement: Decimal::from_str("0.00").unwrap(),
+            futures_margin_requirement: Some(Decimal::from_str("0.00").unwrap()),
             available_trading_funds: Decimal::from_str("600.00").unwrap(),
             maintenance_requirement: Decimal::from_str("0.00").unwrap(),
             maintenance_call_value: Decimal::from_str("0.00").unwrap(),
             reg_t_call_value: Decimal::from_str("0.00").unwrap(),
             day_trading_call_value: Decimal::from_str("0.00").unwrap(),
-            day_equity_call_value: Decimal::from_str("0.00").unwrap(),
+            day_equity_call_value: Some(Decimal::from_str("0.00").unwrap()),
             net_liquidating_value: Decimal::from_str("600.00").unwrap(),
             cash_available_to_withdraw: Decimal::from_str("100.00").unwrap(),
             day_trade_excess: Decimal::from_str("2400.00").unwrap(),
             pending_cash: Decimal::from_str("0.00").unwrap(),
             pending_cash_effect: PriceEffect::Debit,
-            pending_margin_interest: Decimal::from_str("0.00").unwrap(),
-            effective_cryptocurrency_buying_power: Decimal::from_str("0.00").unwrap(),
+            pending_margin_interest: Some(Decimal::from_str("0.00").unwrap()),
+            effective_cryptocurrency_buying_power: Some(Decimal::from_str("0.00").unwrap()),
             updated_at: "2024-01-01T12:00:00Z".to_string(),
         };
 
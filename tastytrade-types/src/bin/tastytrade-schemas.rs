@@ -0,0 +1,27 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Writes the JSON Schema of every tastytrade payload type to a directory
+//! (default `./schemas`), one `<Type>.schema.json` file per type.
+
+use std::fs;
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = PathBuf::from(
+        std::env::args()
+            .nth(1)
+            .unwrap_or_else(|| "schemas".to_string()),
+    );
+    fs::create_dir_all(&out_dir)?;
+
+    for (name, schema) in tastytrade_types::schema::schemas() {
+        let path = out_dir.join(format!("{name}.schema.json"));
+        fs::write(&path, serde_json::to_string_pretty(&schema)?)?;
+        println!("{}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! US equity market sessions.
+//!
+//! Times are evaluated in New York local time, with daylight saving applied from the
+//! second Sunday of March to the first Sunday of November. Exchange holidays and early
+//! closes are not modelled: on those days the session of a normal weekday is reported.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Trading session of the US equity markets at a given instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarketSession {
+    /// 04:00 to 09:30 New York time.
+    PreMarket,
+    /// 09:30 to 16:00 New York time.
+    Regular,
+    /// 16:00 to 20:00 New York time.
+    AfterHours,
+    /// Overnight and weekends.
+    Closed,
+}
+
+impl MarketSession {
+    /// Whether this is the pre-market or after-hours session.
+    pub fn is_extended(self) -> bool {
+        matches!(self, MarketSession::PreMarket | MarketSession::AfterHours)
+    }
+}
+
+/// Converts `now` to New York local time.
+pub fn new_york_time(now: DateTime<Utc>) -> NaiveDateTime {
+    let offset = if is_us_daylight_saving(now) { 4 } else { 5 };
+    now.naive_utc() - Duration::hours(offset)
+}
+
+/// The US equity session in progress at `now`.
+pub fn us_equity_session(now: DateTime<Utc>) -> MarketSession {
+    let local = new_york_time(now);
+    if matches!(local.weekday(), Weekday::Sat | Weekday::Sun) {
+        return MarketSession::Closed;
+    }
+
+    let time = local.time();
+    let at = |h, m| NaiveTime::from_hms_opt(h, m, 0).expect("valid time");
+    if time < at(4, 0) {
+        MarketSession::Closed
+    } else if time < at(9, 30) {
+        MarketSession::PreMarket
+    } else if time < at(16, 0) {
+        MarketSession::Regular
+    } else if time < at(20, 0) {
+        MarketSession::AfterHours
+    } else {
+        MarketSession::Closed
+    }
+}
+
+fn is_us_daylight_saving(now: DateTime<Utc>) -> bool {
+    let year = now.year();
+    let sunday = |month, n| {
+        NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, n)
+            .expect("every month has a first and second Sunday")
+    };
+    // 02:00 local: 07:00 UTC in March (still EST), 06:00 UTC in November (still EDT).
+    let start = sunday(3, 2)
+        .and_hms_opt(7, 0, 0)
+        .expect("valid time")
+        .and_utc();
+    let end = sunday(11, 1)
+        .and_hms_opt(6, 0, 0)
+        .expect("valid time")
+        .and_utc();
+    now >= start && now < end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_us_equity_session_across_daylight_saving() {
+        // Summer (EDT, UTC-4)
+        assert_eq!(
+            us_equity_session(utc("2024-06-03T12:00:00Z")),
+            MarketSession::PreMarket
+        );
+        assert_eq!(
+            us_equity_session(utc("2024-06-03T13:30:00Z")),
+            MarketSession::Regular
+        );
+        assert_eq!(
+            us_equity_session(utc("2024-06-03T20:30:00Z")),
+            MarketSession::AfterHours
+        );
+        // Winter (EST, UTC-5): 14:00 UTC is 09:00 local
+        assert_eq!(
+            us_equity_session(utc("2024-01-08T14:00:00Z")),
+            MarketSession::PreMarket
+        );
+        assert_eq!(
+            us_equity_session(utc("2024-01-08T02:00:00Z")),
+            MarketSession::Closed
+        );
+        // Saturday
+        assert_eq!(
+            us_equity_session(utc("2024-06-08T15:00:00Z")),
+            MarketSession::Closed
+        );
+    }
+}
@@ -0,0 +1,178 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! The account holder, as returned by `/customers/me`.
+//!
+//! Most fields are only filled for some customer types (individuals, entities, joint
+//! holders), so nearly everything is optional. Tax numbers are never modelled.
+
+use chrono::NaiveDate;
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+
+/// A postal address.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+pub struct Address {
+    /// First street line.
+    pub street_one: Option<String>,
+    /// Second street line.
+    pub street_two: Option<String>,
+    /// Third street line.
+    pub street_three: Option<String>,
+    /// City.
+    pub city: Option<String>,
+    /// State or region.
+    pub state_region: Option<String>,
+    /// Postal code.
+    pub postal_code: Option<String>,
+    /// Country.
+    pub country: Option<String>,
+    /// Whether the address is outside the US.
+    pub is_foreign: Option<bool>,
+    /// Whether the address is in the US.
+    pub is_domestic: Option<bool>,
+}
+
+/// Financial profile and trading experience declared by the customer.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+pub struct CustomerSuitability {
+    /// Marital status.
+    pub marital_status: Option<String>,
+    /// Number of dependents.
+    pub number_of_dependents: Option<u32>,
+    /// Employment status.
+    pub employment_status: Option<String>,
+    /// Occupation.
+    pub occupation: Option<String>,
+    /// Employer name.
+    pub employer_name: Option<String>,
+    /// Job title.
+    pub job_title: Option<String>,
+    /// Annual net income, in USD.
+    pub annual_net_income: Option<u64>,
+    /// Net worth, in USD.
+    pub net_worth: Option<u64>,
+    /// Liquid net worth, in USD.
+    pub liquid_net_worth: Option<u64>,
+    /// Stock trading experience, e.g. `Extensive`.
+    pub stock_trading_experience: Option<String>,
+    /// Covered options trading experience.
+    pub covered_options_trading_experience: Option<String>,
+    /// Uncovered options trading experience.
+    pub uncovered_options_trading_experience: Option<String>,
+    /// Futures trading experience.
+    pub futures_trading_experience: Option<String>,
+}
+
+/// The legal entity holding accounts that are not owned by a person.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+pub struct CustomerEntity {
+    /// Entity id.
+    pub id: Option<String>,
+    /// Legal name.
+    pub legal_name: Option<String>,
+    /// Kind of entity, e.g. `LLC` or `Trust`.
+    pub entity_type: Option<String>,
+    /// Whether the entity is US-based.
+    pub is_domestic: Option<bool>,
+    /// Registered address.
+    pub address: Option<Address>,
+    /// Contact email.
+    pub email: Option<String>,
+}
+
+/// The customer logged in, from `/customers/me`.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", default)]
+pub struct Customer {
+    /// Customer id.
+    pub id: String,
+    /// Name prefix, e.g. `Dr.`.
+    pub prefix_name: Option<String>,
+    /// First name.
+    pub first_name: Option<String>,
+    /// Middle name.
+    pub middle_name: Option<String>,
+    /// Last name.
+    pub last_name: Option<String>,
+    /// Name suffix, e.g. `Jr.`.
+    pub suffix_name: Option<String>,
+    /// Contact email.
+    pub email: Option<String>,
+    /// Mobile phone number.
+    pub mobile_phone_number: Option<String>,
+    /// Residential address.
+    pub address: Option<Address>,
+    /// Mailing address, when different from the residential one.
+    pub mailing_address: Option<Address>,
+    /// Date of birth.
+    pub birth_date: Option<NaiveDate>,
+    /// Country of citizenship.
+    pub citizenship_country: Option<String>,
+    /// US citizenship type, e.g. `Citizen`.
+    pub usa_citizenship_type: Option<String>,
+    /// Whether the customer lives outside the US.
+    pub is_foreign: Option<bool>,
+    /// Whether the customer is a market data professional.
+    pub is_professional: Option<bool>,
+    /// Whether the customer only receives delayed quotes.
+    pub has_delayed_quotes: Option<bool>,
+    /// Whether an account application is pending or approved.
+    pub has_pending_or_approved_application: Option<bool>,
+    /// Whether the customer agreed to margin trading.
+    pub agreed_to_margining: Option<bool>,
+    /// Whether the customer is affiliated with a broker-dealer or exchange.
+    pub has_industry_affiliation: Option<bool>,
+    /// Whether the customer holds a public office.
+    pub has_political_affiliation: Option<bool>,
+    /// Whether the customer is an officer of a listed company.
+    pub has_listed_affiliation: Option<bool>,
+    /// The entity holding the accounts, for non-individual customers.
+    pub entity: Option<CustomerEntity>,
+    /// Declared financial profile.
+    pub customer_suitability: Option<CustomerSuitability>,
+}
+
+impl Customer {
+    /// First and last name joined, or the entity's legal name when the customer has no
+    /// personal name.
+    pub fn display_name(&self) -> Option<String> {
+        let name = [self.first_name.as_deref(), self.last_name.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !name.is_empty() {
+            return Some(name);
+        }
+        self.entity.as_ref()?.legal_name.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_customer_display_name() {
+        let person: Customer =
+            serde_json::from_str(r#"{"id": "me", "first-name": "Jane", "last-name": "Doe"}"#)
+                .unwrap();
+        assert_eq!(person.display_name().as_deref(), Some("Jane Doe"));
+
+        let entity: Customer =
+            serde_json::from_str(r#"{"id": "me", "entity": {"legal-name": "Doe Capital LLC"}}"#)
+                .unwrap();
+        assert_eq!(entity.display_name().as_deref(), Some("Doe Capital LLC"));
+    }
+}
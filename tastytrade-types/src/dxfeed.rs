@@ -11,6 +11,7 @@ pub const DXF_ET_GREEKS: i32 = 0x08;
 
 /// Represents a quote event from the market data feed
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DxfQuoteT {
     pub time: i64,
     pub sequence: i32,
@@ -28,6 +29,7 @@ pub struct DxfQuoteT {
 
 /// Represents a trade event from the market data feed
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DxfTradeT {
     pub time: i64,
     pub sequence: i32,
@@ -48,6 +50,7 @@ pub struct DxfTradeT {
 
 /// Represents Greeks data for options
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DxfGreeksT {
     pub event_flags: i32,
     pub index: i64,
@@ -63,17 +66,43 @@ pub struct DxfGreeksT {
 
 /// Enum representing different types of market event data
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum EventData {
     Quote(DxfQuoteT),
     Trade(DxfTradeT),
     Greeks(DxfGreeksT),
 }
 
+/// Whether market data is live or held back by the exchange delay.
+///
+/// Customers without a real-time entitlement receive quotes 15 minutes late.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum DataQuality {
+    /// Live prices.
+    #[default]
+    Realtime,
+    /// Prices delayed by 15 minutes.
+    Delayed,
+}
+
+impl DataQuality {
+    /// Returns `true` for [`DataQuality::Delayed`].
+    pub fn is_delayed(self) -> bool {
+        self == Self::Delayed
+    }
+}
+
 /// Main event structure that contains symbol and event data
 #[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Event {
     pub sym: String,
     pub data: EventData,
+    /// Whether the event carries live or delayed data.
+    #[serde(default)]
+    pub quality: DataQuality,
 }
 
 impl Event {
@@ -82,6 +111,7 @@ impl Event {
         Self {
             sym: symbol,
             data: EventData::Quote(quote),
+            quality: DataQuality::Realtime,
         }
     }
 
@@ -90,6 +120,7 @@ impl Event {
         Self {
             sym: symbol,
             data: EventData::Trade(trade),
+            quality: DataQuality::Realtime,
         }
     }
 
@@ -98,6 +129,161 @@ impl Event {
         Self {
             sym: symbol,
             data: EventData::Greeks(greeks),
+            quality: DataQuality::Realtime,
+        }
+    }
+
+    /// Tags the event with `quality`.
+    pub fn with_quality(mut self, quality: DataQuality) -> Self {
+        self.quality = quality;
+        self
+    }
+}
+
+/// What to do with NaN and infinite values in events received from the feed.
+///
+/// DXLink reports a missing price, e.g. the bid of an illiquid strike, as `NaN`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum NonFinitePolicy {
+    /// Drop the whole event.
+    DropEvent,
+    /// Keep the event, replacing every non-finite value, infinities included, with
+    /// `NaN`. The fields stay `f64`; read them through the accessors returning
+    /// `Option`, such as [`DxfQuoteT::bid`], which report `NaN` as `None`.
+    #[default]
+    ReplaceWithNan,
+    /// Deliver the values as received.
+    PassThrough,
+}
+
+/// `value` when it is finite.
+fn finite(value: f64) -> Option<f64> {
+    value.is_finite().then_some(value)
+}
+
+/// `NaN` for any non-finite `value`.
+fn nan_if_non_finite(value: &mut f64) {
+    if !value.is_finite() {
+        *value = f64::NAN;
+    }
+}
+
+impl DxfQuoteT {
+    /// The bid price, `None` when missing.
+    pub fn bid(&self) -> Option<f64> {
+        finite(self.bid_price)
+    }
+
+    /// The ask price, `None` when missing.
+    pub fn ask(&self) -> Option<f64> {
+        finite(self.ask_price)
+    }
+
+    /// Midpoint of bid and ask, `None` when either is missing.
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.bid()? + self.ask()?) / 2.0)
+    }
+}
+
+impl DxfTradeT {
+    /// The last trade price, `None` when missing.
+    pub fn last_price(&self) -> Option<f64> {
+        finite(self.price)
+    }
+}
+
+impl DxfGreeksT {
+    /// The theoretical option price, `None` when missing.
+    pub fn theo_price(&self) -> Option<f64> {
+        finite(self.price)
+    }
+
+    /// The implied volatility, `None` when missing.
+    pub fn implied_volatility(&self) -> Option<f64> {
+        finite(self.volatility)
+    }
+
+    /// The delta, `None` when missing.
+    pub fn delta(&self) -> Option<f64> {
+        finite(self.delta)
+    }
+
+    /// The gamma, `None` when missing.
+    pub fn gamma(&self) -> Option<f64> {
+        finite(self.gamma)
+    }
+
+    /// The theta, `None` when missing.
+    pub fn theta(&self) -> Option<f64> {
+        finite(self.theta)
+    }
+
+    /// The vega, `None` when missing.
+    pub fn vega(&self) -> Option<f64> {
+        finite(self.vega)
+    }
+
+    /// The rho, `None` when missing.
+    pub fn rho(&self) -> Option<f64> {
+        finite(self.rho)
+    }
+}
+
+impl Event {
+    /// The floating-point values of the event.
+    fn values_mut(&mut self) -> Vec<&mut f64> {
+        match &mut self.data {
+            EventData::Quote(q) => vec![&mut q.bid_price, &mut q.ask_price],
+            EventData::Trade(t) => vec![
+                &mut t.price,
+                &mut t.change,
+                &mut t.day_volume,
+                &mut t.day_turnover,
+            ],
+            EventData::Greeks(g) => vec![
+                &mut g.price,
+                &mut g.volatility,
+                &mut g.delta,
+                &mut g.gamma,
+                &mut g.theta,
+                &mut g.rho,
+                &mut g.vega,
+            ],
+        }
+    }
+
+    /// Returns `true` when any value of the event is NaN or infinite.
+    pub fn has_non_finite(&self) -> bool {
+        match &self.data {
+            EventData::Quote(q) => [q.bid_price, q.ask_price].iter().any(|v| !v.is_finite()),
+            EventData::Trade(t) => [t.price, t.change, t.day_volume, t.day_turnover]
+                .iter()
+                .any(|v| !v.is_finite()),
+            EventData::Greeks(g) => [
+                g.price,
+                g.volatility,
+                g.delta,
+                g.gamma,
+                g.theta,
+                g.rho,
+                g.vega,
+            ]
+            .iter()
+            .any(|v| !v.is_finite()),
+        }
+    }
+
+    /// Applies `policy`, returning `None` when the event is dropped.
+    pub fn sanitize(mut self, policy: NonFinitePolicy) -> Option<Self> {
+        match policy {
+            NonFinitePolicy::DropEvent if self.has_non_finite() => None,
+            NonFinitePolicy::ReplaceWithNan => {
+                self.values_mut().into_iter().for_each(nan_if_non_finite);
+                Some(self)
+            }
+            _ => Some(self),
         }
     }
 }
@@ -216,6 +402,14 @@ mod tests {
 
         let event = Event::new_quote("AAPL".to_string(), quote);
         assert_eq!(event.sym, "AAPL");
+        assert_eq!(event.quality, DataQuality::Realtime);
+        assert!(
+            event
+                .clone()
+                .with_quality(DataQuality::Delayed)
+                .quality
+                .is_delayed()
+        );
 
         match event.data {
             EventData::Quote(q) => {
@@ -345,4 +539,38 @@ mod tests {
         assert_eq!(deserialized.sym, "TEST");
         matches!(deserialized.data, EventData::Quote(_));
     }
+
+    #[test]
+    fn test_non_finite_policy() {
+        let quote = Event::new_quote(
+            "SPY 250117C700".to_string(),
+            DxfQuoteT {
+                bid_price: f64::NAN,
+                ask_price: f64::INFINITY,
+                ..Default::default()
+            },
+        );
+        assert!(quote.has_non_finite());
+        assert!(quote.clone().sanitize(NonFinitePolicy::DropEvent).is_none());
+
+        let passed = quote
+            .clone()
+            .sanitize(NonFinitePolicy::PassThrough)
+            .unwrap();
+        let EventData::Quote(q) = passed.data else {
+            panic!("expected a quote")
+        };
+        assert_eq!(q.ask_price, f64::INFINITY);
+
+        let cleaned = quote.sanitize(NonFinitePolicy::ReplaceWithNan).unwrap();
+        let EventData::Quote(q) = cleaned.data else {
+            panic!("expected a quote")
+        };
+        assert!(q.ask_price.is_nan());
+        assert_eq!((q.bid(), q.ask(), q.mid()), (None, None, None));
+
+        let trade = Event::new_trade("SPY".to_string(), DxfTradeT::default());
+        assert!(!trade.has_non_finite());
+        assert!(trade.sanitize(NonFinitePolicy::DropEvent).is_some());
+    }
 }
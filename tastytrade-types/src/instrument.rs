@@ -1,18 +1,19 @@
-use super::order::Symbol;
-use crate::api::quote_streaming::DxFeedSymbol;
-use chrono::{DateTime, Utc};
+use super::order::{DxFeedSymbol, Symbol};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::Display;
 
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompactOptionChainResponse {
     pub data: CompactOptionChainData,
 }
 
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompactOptionChainData {
     pub items: Vec<CompactOptionChain>,
 }
@@ -23,6 +24,7 @@ pub struct CompactOptionChainData {
 /// compared to the full `NestedOptionChain`, focusing on essential information
 /// for quick access and reduced memory usage.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct CompactOptionChain {
     /// The symbol of the underlying asset (e.g., "AAPL").
@@ -50,8 +52,96 @@ pub struct CompactOptionChain {
     pub streamer_symbols: Option<Vec<String>>,
 }
 
+impl CompactOptionChain {
+    /// Pairs every option symbol with its DXLink streamer symbol.
+    ///
+    /// See [`expand_symbol_pairs`] for how incomplete lists are handled.
+    pub fn symbol_pairs(&self) -> Vec<(Symbol, DxFeedSymbol)> {
+        expand_symbol_pairs(
+            self.symbols.as_deref().unwrap_or_default(),
+            self.streamer_symbols.as_deref().unwrap_or_default(),
+        )
+    }
+
+    /// Streamer symbols of every option in the chain, ready for a bulk quote subscription.
+    pub fn dxfeed_symbols(&self) -> Vec<DxFeedSymbol> {
+        self.streamer_symbols
+            .iter()
+            .flatten()
+            .map(|s| DxFeedSymbol(s.clone()))
+            .collect()
+    }
+}
+
+/// Compact futures option chain, as returned by `/futures-option-chains/{product-code}/compact`.
+///
+/// Like [`CompactOptionChain`], strikes are not broken out: the chain carries the flat
+/// lists of option symbols and their streamer symbols, in the same order.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct FuturesCompactOptionChain {
+    /// The underlying futures contract (e.g., "/ESZ5"), when the chain is tied to one.
+    pub underlying_symbol: Option<Symbol>,
+
+    /// The root symbol of the options (e.g., "/ES").
+    pub root_symbol: Symbol,
+
+    /// The option root symbol used in OCC-style symbols (e.g., "E1A").
+    pub option_root_symbol: Option<String>,
+
+    /// The exercise style of the options (e.g., "American").
+    pub exercise_style: Option<String>,
+
+    /// The settlement type of the options.
+    pub settlement_type: Option<String>,
+
+    /// The expiration type of the chain (e.g., "Regular", "Weekly").
+    pub expiration_type: Option<String>,
+
+    /// Option symbols in the chain.
+    #[serde(default)]
+    pub symbols: Vec<String>,
+
+    /// DXLink streamer symbols, in the same order as `symbols`.
+    #[serde(default)]
+    pub streamer_symbols: Vec<String>,
+}
+
+impl FuturesCompactOptionChain {
+    /// Pairs every option symbol with its DXLink streamer symbol.
+    ///
+    /// See [`expand_symbol_pairs`] for how incomplete lists are handled.
+    pub fn symbol_pairs(&self) -> Vec<(Symbol, DxFeedSymbol)> {
+        expand_symbol_pairs(&self.symbols, &self.streamer_symbols)
+    }
+
+    /// Streamer symbols of every option in the chain, ready for a bulk quote subscription.
+    pub fn dxfeed_symbols(&self) -> Vec<DxFeedSymbol> {
+        self.streamer_symbols
+            .iter()
+            .map(|s| DxFeedSymbol(s.clone()))
+            .collect()
+    }
+}
+
+/// Zips the symbol and streamer symbol lists of a compact chain.
+///
+/// Both lists are positional; when one is shorter, the extra entries of the other are dropped.
+pub fn expand_symbol_pairs(
+    symbols: &[String],
+    streamer_symbols: &[String],
+) -> Vec<(Symbol, DxFeedSymbol)> {
+    symbols
+        .iter()
+        .zip(streamer_symbols)
+        .map(|(symbol, streamer)| (Symbol(symbol.clone()), DxFeedSymbol(streamer.clone())))
+        .collect()
+}
+
 /// Represents the different types of financial instruments.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum InstrumentType {
     /// Represents an equity instrument.
     Equity,
@@ -78,6 +168,9 @@ pub enum InstrumentType {
     LiquidityPool,
     /// Represents a warrant instrument.
     Warrant,
+    /// An instrument type this version does not know yet, holding the raw API value.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl Display for InstrumentType {
@@ -93,6 +186,7 @@ impl Display for InstrumentType {
             InstrumentType::FixedIncomeSecurity => write!(f, "Fixed Income Security"),
             InstrumentType::LiquidityPool => write!(f, "Liquidity Pool"),
             InstrumentType::Warrant => write!(f, "Warrant"),
+            InstrumentType::Unknown(raw) => write!(f, "{raw}"),
         }
     }
 }
@@ -102,6 +196,7 @@ impl Display for InstrumentType {
 /// This struct holds the symbol and the streamer symbol for an equity instrument.
 /// It uses kebab-case for serialization and deserialization.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct EquityInstrumentInfo {
     /// The symbol of the equity instrument.
@@ -115,6 +210,7 @@ pub struct EquityInstrumentInfo {
 /// This struct is deserialized from a JSON response using `serde`.
 /// The fields are renamed to kebab-case during deserialization.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct TickSize {
     /// The value of the tick size.
@@ -128,6 +224,7 @@ pub struct TickSize {
 /// This struct is deserialized from a JSON response using `serde`.
 /// The fields are renamed to kebab-case during deserialization.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct EquityInstrument {
     /// The unique identifier of the equity instrument.
@@ -142,32 +239,39 @@ pub struct EquityInstrument {
     pub short_description: String,
     /// Indicates whether the instrument is an index.
     pub is_index: bool,
-    /// The market where the equity instrument is listed.
-    pub listed_market: String,
+    /// The market where the equity instrument is listed. Not always sent by the sandbox.
+    #[serde(default)]
+    pub listed_market: Option<String>,
     /// A detailed description of the equity instrument.
     pub description: String,
     /// The lendability of the equity instrument.
     pub lendability: Option<String>,
     /// The borrow rate of the equity instrument.
     pub borrow_rate: Option<String>,
-    /// The market time instrument collection.
-    pub market_time_instrument_collection: String,
+    /// The market time instrument collection. Not always sent by the sandbox.
+    #[serde(default)]
+    pub market_time_instrument_collection: Option<String>,
     /// Indicates whether the instrument is closing only.
     pub is_closing_only: bool,
-    /// Indicates whether the instrument's options are closing only.
+    /// Indicates whether the instrument's options are closing only; `false` when absent.
+    #[serde(default)]
     pub is_options_closing_only: bool,
     /// Indicates whether the instrument is active.
     pub active: bool,
     /// Indicates whether the instrument is eligible for fractional quantity trading.
     #[serde(default)]
     pub is_fractional_quantity_eligible: bool,
-    /// Indicates whether the instrument is illiquid.
+    /// Indicates whether the instrument is illiquid; `false` when absent.
+    #[serde(default)]
     pub is_illiquid: bool,
-    /// Indicates whether the instrument is an ETF (Exchange Traded Fund).
+    /// Indicates whether the instrument is an ETF (Exchange Traded Fund); `false` when absent.
+    #[serde(default)]
     pub is_etf: bool,
-    /// Indicates whether the instrument bypasses manual review.
+    /// Indicates whether the instrument bypasses manual review; `false` when absent.
+    #[serde(default)]
     pub bypass_manual_review: bool,
-    /// Indicates whether the instrument is a fraud risk.
+    /// Indicates whether the instrument is a fraud risk; `false` when absent.
+    #[serde(default)]
     pub is_fraud_risk: bool,
     /// The symbol used by the DxFeed data stream.
     pub streamer_symbol: DxFeedSymbol,
@@ -183,10 +287,12 @@ pub struct EquityInstrument {
 /// and the associated call and put option symbols.  It uses symbols specifically designed for
 /// interaction with the DxFeed data stream.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct Strike {
     /// The strike price itself, represented as a Decimal for precision.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub strike_price: Decimal,
 
     /// The symbol for the call option at this strike price.
@@ -211,6 +317,7 @@ pub struct Strike {
 /// this expiration date.  The data structure uses kebab-case for its fields
 /// to match the format of incoming data.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct Expiration {
     /// The type of expiration (e.g., "weekly", "monthly").
@@ -230,6 +337,30 @@ pub struct Expiration {
     pub strikes: Vec<Strike>,
 }
 
+/// Expiration metadata of an option chain, without its strikes.
+///
+/// Returned by `TastyTrade::chain_expirations` for expiration pickers that load
+/// strikes only once an expiration is chosen.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ExpirationSummary {
+    /// The option root the expiration belongs to (e.g., "SPXW" next to "SPX").
+    pub root_symbol: Symbol,
+
+    /// The type of expiration (e.g., "Weekly", "Regular").
+    pub expiration_type: String,
+
+    /// The date of expiration (e.g., "2024-12-20").
+    pub expiration_date: String,
+
+    /// The number of days remaining until expiration.
+    pub days_to_expiration: u64,
+
+    /// The settlement type for the options (e.g., "AM", "PM").
+    pub settlement_type: String,
+}
+
 /// Represents a nested option chain for a specific underlying symbol.
 ///
 /// This structure encapsulates the details of an option chain,
@@ -239,6 +370,7 @@ pub struct Expiration {
 /// strike prices.  The data structure uses kebab-case for its fields
 /// to match the format of incoming data.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct NestedOptionChain {
     /// The symbol of the underlying asset (e.g., "AAPL").
@@ -253,16 +385,148 @@ pub struct NestedOptionChain {
     /// The number of shares represented by each option contract.
     pub shares_per_contract: u64,
 
+    /// What one contract of the chain delivers on exercise. Standard chains deliver
+    /// `shares_per_contract` shares of the underlying; chains adjusted after a corporate
+    /// action can mix other shares and cash in lieu.
+    #[serde(default)]
+    pub deliverables: Vec<Deliverable>,
+
     /// A vector of `Expiration` structs, each representing a different
     /// expiration date for the option chain.
     pub expirations: Vec<Expiration>,
 }
 
+/// Kind of [`Deliverable`].
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum DeliverableType {
+    /// Shares of an equity.
+    Shares,
+    /// Cash, e.g. in lieu of fractional shares.
+    Cash,
+    /// A deliverable type this version does not know yet, holding the raw API value.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+/// One component of what an option contract delivers.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct Deliverable {
+    /// Id of the deliverable.
+    #[serde(default)]
+    pub id: Option<u64>,
+    /// Root symbol of the chain it belongs to.
+    pub root_symbol: Symbol,
+    /// Shares or cash.
+    pub deliverable_type: DeliverableType,
+    /// Human-readable description, e.g. "100 shares of SPY".
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Number of shares, or cash amount in USD, per contract.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
+    pub amount: Decimal,
+    /// The delivered instrument; `None` for cash.
+    #[serde(default)]
+    pub symbol: Option<Symbol>,
+    /// Instrument type of `symbol`.
+    #[serde(default)]
+    pub instrument_type: Option<InstrumentType>,
+    /// Share of the contract's value this component represents, in percent.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub percent: Option<Decimal>,
+}
+
+impl NestedOptionChain {
+    /// Returns `true` when the chain delivers anything other than
+    /// `shares_per_contract` shares of its underlying, as after a split, merger or
+    /// special dividend.
+    pub fn is_non_standard(&self) -> bool {
+        match self.deliverables.as_slice() {
+            [] => false,
+            [only] => {
+                only.deliverable_type != DeliverableType::Shares
+                    || only.symbol.as_ref() != Some(&self.underlying_symbol)
+                    || only.amount != Decimal::from(self.shares_per_contract)
+            }
+            _ => true,
+        }
+    }
+
+    /// Counts and ranges of the chain, cheap to log.
+    ///
+    /// A full chain has thousands of strikes; prefer this over `{:?}` in log lines.
+    pub fn summary(&self) -> ChainSummary {
+        let strikes = self.expirations.iter().flat_map(|e| &e.strikes);
+        ChainSummary {
+            underlying_symbol: self.underlying_symbol.clone(),
+            root_symbol: self.root_symbol.clone(),
+            expirations: self.expirations.len(),
+            strikes: strikes.clone().count(),
+            first_expiration: self.expirations.first().map(|e| e.expiration_date.clone()),
+            last_expiration: self.expirations.last().map(|e| e.expiration_date.clone()),
+            min_strike: strikes.clone().map(|s| s.strike_price).min(),
+            max_strike: strikes.map(|s| s.strike_price).max(),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn debug_compact(&self) -> ChainSummary {
+        self.summary()
+    }
+}
+
+/// Overview of a [`NestedOptionChain`] returned by [`NestedOptionChain::summary`].
+///
+/// Displays as a single line, e.g.
+/// `AAPL (root AAPL): 2 expirations 2024-12-20..2025-01-17, 4 strikes 140..160`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainSummary {
+    /// The symbol of the underlying asset.
+    pub underlying_symbol: Symbol,
+    /// The root symbol of the chain.
+    pub root_symbol: Symbol,
+    /// Number of expirations.
+    pub expirations: usize,
+    /// Number of strikes summed over all expirations.
+    pub strikes: usize,
+    /// Nearest expiration date.
+    pub first_expiration: Option<String>,
+    /// Furthest expiration date.
+    pub last_expiration: Option<String>,
+    /// Lowest strike price.
+    pub min_strike: Option<Decimal>,
+    /// Highest strike price.
+    pub max_strike: Option<Decimal>,
+}
+
+impl Display for ChainSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (root {}): {} expirations",
+            self.underlying_symbol.0, self.root_symbol.0, self.expirations
+        )?;
+        if let (Some(first), Some(last)) = (&self.first_expiration, &self.last_expiration) {
+            write!(f, " {first}..{last}")?;
+        }
+        write!(f, ", {} strikes", self.strikes)?;
+        if let (Some(min), Some(max)) = (self.min_strike, self.max_strike) {
+            write!(f, " {}..{}", min.normalize(), max.normalize())?;
+        }
+        Ok(())
+    }
+}
+
 /// Represents a futures nested option chain response.
 ///
 /// This structure matches the FuturesNestedOptionChainSerializer from the API,
 /// containing both futures information and option chains data.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FuturesNestedOptionChain {
     /// Array of futures contracts information.
@@ -274,6 +538,7 @@ pub struct FuturesNestedOptionChain {
 
 /// Represents futures contract information.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FuturesInfo {
     /// The symbol of the futures contract.
@@ -303,6 +568,7 @@ pub struct FuturesInfo {
 
 /// Represents the option chains section of futures nested option chain.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FuturesOptionChains {
     /// The underlying symbol for the options.
@@ -320,6 +586,7 @@ pub struct FuturesOptionChains {
 
 /// Represents an expiration in a futures option chain.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FuturesExpiration {
     /// The underlying symbol.
@@ -351,14 +618,17 @@ pub struct FuturesExpiration {
 
     /// The notional value.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub notional_value: Decimal,
 
     /// The display factor.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub display_factor: Decimal,
 
     /// The strike factor.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub strike_factor: Decimal,
 
     /// When trading stops.
@@ -376,6 +646,7 @@ pub struct FuturesExpiration {
 
 /// Represents tick size information for futures options.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FuturesTickSize {
     /// The threshold value (optional).
@@ -388,10 +659,12 @@ pub struct FuturesTickSize {
 
 /// Represents a strike price and associated option symbols for futures.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FuturesStrike {
     /// The strike price.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub strike_price: Decimal,
 
     /// The call option symbol.
@@ -411,6 +684,7 @@ pub struct FuturesStrike {
 
 /// Represents an equity option.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct EquityOption {
     /// The symbol of the equity option.
@@ -421,6 +695,7 @@ pub struct EquityOption {
     pub active: bool,
     /// The strike price of the option.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub strike_price: Decimal,
     /// The root symbol of the option.
     pub root_symbol: Symbol,
@@ -459,6 +734,7 @@ pub struct EquityOption {
 /// This struct is deserialized from a JSON response using `serde`.
 /// The fields are renamed to kebab-case during deserialization.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct Future {
     /// The symbol of the future contract.
@@ -528,6 +804,7 @@ pub struct Future {
 /// other characteristics.  It utilizes the `kebab-case` naming convention for serialization
 /// and deserialization.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FutureProduct {
     /// The root symbol of the future product.
@@ -578,6 +855,102 @@ pub struct FutureProduct {
     pub roll: FutureRoll,
 }
 
+/// Futures month codes, January (`F`) to December (`Z`).
+pub const FUTURE_MONTH_CODES: [char; 12] =
+    ['F', 'G', 'H', 'J', 'K', 'M', 'N', 'Q', 'U', 'V', 'X', 'Z'];
+
+/// The calendar month (1 to 12) of a futures month code such as `"H"`.
+pub fn month_from_code(code: &str) -> Option<u32> {
+    let mut chars = code.trim().chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+    FUTURE_MONTH_CODES
+        .iter()
+        .position(|m| *m == c.to_ascii_uppercase())
+        .map(|i| i as u32 + 1)
+}
+
+/// The futures month code of calendar `month` (1 to 12).
+pub fn month_code(month: u32) -> Option<char> {
+    FUTURE_MONTH_CODES
+        .get(month.checked_sub(1)? as usize)
+        .copied()
+}
+
+/// The root of an OCC equity option symbol such as `SPY   240119C00450000` (a root
+/// padded to six characters, a `YYMMDD` expiration, `C` or `P` and an eight-digit
+/// strike), or `None` when `symbol` is not one.
+///
+/// Roots differing from the underlying, often with a trailing digit as in `GE1`, mark
+/// contracts adjusted after a corporate action.
+pub fn occ_root(symbol: &str) -> Option<&str> {
+    let bytes = symbol.as_bytes();
+    if bytes.len() != 21 || !symbol.is_ascii() {
+        return None;
+    }
+    let (root, rest) = bytes.split_at(6);
+    let valid = !root[0].is_ascii_whitespace()
+        && rest[..6].iter().all(u8::is_ascii_digit)
+        && matches!(rest[6], b'C' | b'P')
+        && rest[7..].iter().all(u8::is_ascii_digit);
+    valid.then(|| symbol[..6].trim_end())
+}
+
+impl FutureProduct {
+    /// Calendar months (1 to 12) of `listed_months`, in calendar order. Unknown codes
+    /// are skipped.
+    pub fn listed_calendar_months(&self) -> Vec<u32> {
+        calendar_months(&self.listed_months)
+    }
+
+    /// Calendar months (1 to 12) of `active_months`, in calendar order. Unknown codes
+    /// are skipped.
+    pub fn active_calendar_months(&self) -> Vec<u32> {
+        calendar_months(&self.active_months)
+    }
+
+    /// Symbols of the next `count` contracts of the listed cycle, starting with the
+    /// month of `from`, e.g. `/ESM4`, `/ESU4`, `/ESZ4` for `/ES` in April 2024.
+    ///
+    /// The symbols are built from the month codes, not looked up: a contract of the
+    /// current month may already have expired.
+    pub fn upcoming_contract_symbols(&self, from: NaiveDate, count: usize) -> Vec<Symbol> {
+        let months = self.listed_calendar_months();
+        if months.is_empty() {
+            return Vec::new();
+        }
+        let mut symbols = Vec::with_capacity(count);
+        let mut year = from.year();
+        let mut month = from.month();
+        while symbols.len() < count {
+            if months.contains(&month) {
+                let code = month_code(month).unwrap_or_default();
+                symbols.push(Symbol(format!(
+                    "{}{}{}",
+                    self.root_symbol.0,
+                    code,
+                    year.rem_euclid(10)
+                )));
+            }
+            if month == 12 {
+                month = 1;
+                year += 1;
+            } else {
+                month += 1;
+            }
+        }
+        symbols
+    }
+}
+
+fn calendar_months(codes: &[String]) -> Vec<u32> {
+    let mut months: Vec<u32> = codes.iter().filter_map(|c| month_from_code(c)).collect();
+    months.sort_unstable();
+    months.dedup();
+    months
+}
+
 /// Represents a future roll.
 ///
 /// This struct holds information about a future roll, including its name,
@@ -585,6 +958,7 @@ pub struct FutureProduct {
 /// if it's the first notice.
 ///
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FutureRoll {
     /// The name of the future roll.
@@ -607,6 +981,7 @@ pub struct FutureRoll {
 /// `serde` crate for serialization and deserialization, with a `kebab-case`
 /// naming convention.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FutureOption {
     /// The symbol of the future option.
@@ -624,6 +999,7 @@ pub struct FutureOption {
     /// The strike price of the future option.  Uses arbitrary precision
     /// deserialization via the `rust_decimal` crate.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub strike_price: Decimal,
     /// The exchange where the future option is traded.
     pub exchange: String,
@@ -687,6 +1063,7 @@ pub struct FutureOption {
 /// serialized and deserialized using the `serde` library, with field names
 /// converted to kebab-case.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FutureOptionProduct {
     /// The root symbol of the future option.
@@ -731,6 +1108,7 @@ pub struct FutureOptionProduct {
 /// instrument type, description, trading restrictions, activity status, tick size,
 /// streamer symbol, and destination venue symbols.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct Cryptocurrency {
     /// The unique identifier for the cryptocurrency.
@@ -755,6 +1133,39 @@ pub struct Cryptocurrency {
     pub destination_venue_symbols: Vec<DestinationVenueSymbol>,
 }
 
+impl Cryptocurrency {
+    /// The quantity precision accepted by every routable venue, i.e. the smallest one
+    /// reported. `None` when no routable venue reports a precision.
+    pub fn max_precision(&self) -> Option<u32> {
+        self.destination_venue_symbols
+            .iter()
+            .filter(|venue| venue.routable)
+            .filter_map(DestinationVenueSymbol::max_precision)
+            .min()
+    }
+
+    /// `quantity` truncated to [`max_precision`](Self::max_precision) decimal places.
+    pub fn truncate_quantity(&self, quantity: Decimal) -> Decimal {
+        truncate_to_precision(quantity, self.max_precision())
+    }
+
+    /// `quantity` truncated to [`max_precision`](Self::max_precision), without trailing zeros.
+    pub fn format_quantity(&self, quantity: Decimal) -> String {
+        self.truncate_quantity(quantity).to_string()
+    }
+}
+
+/// Truncates `quantity` towards zero to `precision` decimal places and drops trailing
+/// zeros. Rounding up could order more than the account holds, so digits are cut instead.
+fn truncate_to_precision(quantity: Decimal, precision: Option<u32>) -> Decimal {
+    match precision {
+        Some(dp) => quantity
+            .round_dp_with_strategy(dp, RoundingStrategy::ToZero)
+            .normalize(),
+        None => quantity.normalize(),
+    }
+}
+
 /// Represents a destination venue symbol.
 ///
 /// This struct holds information about a specific symbol traded on a particular
@@ -762,6 +1173,7 @@ pub struct Cryptocurrency {
 /// itself, the destination venue name, precision for quantity and price, and
 /// whether the symbol is routable.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct DestinationVenueSymbol {
     /// The unique identifier for the symbol.
@@ -778,11 +1190,29 @@ pub struct DestinationVenueSymbol {
     pub routable: bool,
 }
 
+impl DestinationVenueSymbol {
+    /// The maximum number of decimal places of a quantity on this venue, if reported.
+    pub fn max_precision(&self) -> Option<u32> {
+        self.max_quantity_precision
+    }
+
+    /// `quantity` truncated to [`max_precision`](Self::max_precision) decimal places.
+    pub fn truncate_quantity(&self, quantity: Decimal) -> Decimal {
+        truncate_to_precision(quantity, self.max_precision())
+    }
+
+    /// `quantity` truncated to [`max_precision`](Self::max_precision), without trailing zeros.
+    pub fn format_quantity(&self, quantity: Decimal) -> String {
+        self.truncate_quantity(quantity).to_string()
+    }
+}
+
 /// Represents a Warrant instrument.
 ///
 /// Warrants are derivative securities that give the holder the right, but not the obligation,
 /// to buy or sell an underlying asset at a certain price before expiration.  
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct Warrant {
     /// The symbol of the warrant.
@@ -807,7 +1237,8 @@ pub struct Warrant {
 /// required for the minimum increment.  For instance, a `value` of 2 and a `minimum_increment_precision`
 /// of 2 would allow quantities like 1.23, and the minimum increment would also need to be expressed
 /// with two decimal places (e.g., 0.01).
-#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct QuantityDecimalPrecision {
     /// The type of instrument.  Examples include `Equity`, `EquityOption`, `Future`, etc.
@@ -824,6 +1255,7 @@ pub struct QuantityDecimalPrecision {
 
 /// Structure to hold symbol information from TastyTrade
 #[derive(Clone, Serialize, Deserialize, DebugPretty, DisplaySimple)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SymbolEntry {
     /// The trading symbol identifier
     pub symbol: String,
@@ -893,6 +1325,27 @@ mod tests {
         assert_eq!(option.shares_per_contract, 100);
     }
 
+    #[test]
+    fn test_futures_compact_option_chain_symbol_pairs() {
+        let json = r#"{
+            "underlying-symbol": "/ESZ5",
+            "root-symbol": "/ES",
+            "option-root-symbol": "E1A",
+            "exercise-style": "American",
+            "settlement-type": "Future",
+            "expiration-type": "Weekly",
+            "symbols": ["./ESZ5 E1AX5 251103C6800", "./ESZ5 E1AX5 251103P6800"],
+            "streamer-symbols": ["./E1AX25C6800:XCME", "./E1AX25P6800:XCME"]
+        }"#;
+
+        let chain: FuturesCompactOptionChain = serde_json::from_str(json).unwrap();
+        let pairs = chain.symbol_pairs();
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[1].0.0, "./ESZ5 E1AX5 251103P6800");
+        assert_eq!(pairs[1].1.0, "./E1AX25P6800:XCME");
+        assert_eq!(chain.dxfeed_symbols().len(), 2);
+    }
+
     #[test]
     fn test_futures_nested_option_chain_deserialization() {
         // Test with a simplified version of the real JSON structure
@@ -1007,4 +1460,127 @@ mod tests {
         assert_eq!(expiration.strikes[1].call_streamer_symbol, None);
         assert_eq!(expiration.strikes[1].put_streamer_symbol, None);
     }
+
+    #[test]
+    fn test_nested_option_chain_summary() {
+        let strike = |price: &str| {
+            format!(
+                r#"{{"strike-price": "{price}", "call": "C{price}", "call-streamer-symbol": ".C{price}",
+                    "put": "P{price}", "put-streamer-symbol": ".P{price}"}}"#
+            )
+        };
+        let json = format!(
+            r#"{{
+                "underlying-symbol": "AAPL",
+                "root-symbol": "AAPL",
+                "option-chain-type": "Standard",
+                "shares-per-contract": 100,
+                "expirations": [
+                    {{"expiration-type": "Regular", "expiration-date": "2024-12-20", "days-to-expiration": 5,
+                      "settlement-type": "PM", "strikes": [{}, {}]}},
+                    {{"expiration-type": "Regular", "expiration-date": "2025-01-17", "days-to-expiration": 33,
+                      "settlement-type": "PM", "strikes": [{}, {}]}}
+                ]
+            }}"#,
+            strike("140.00"),
+            strike("150.00"),
+            strike("145.00"),
+            strike("160.00")
+        );
+        let chain: NestedOptionChain = serde_json::from_str(&json).unwrap();
+
+        let summary = chain.summary();
+        assert_eq!(summary.expirations, 2);
+        assert_eq!(summary.strikes, 4);
+        assert_eq!(
+            summary.to_string(),
+            "AAPL (root AAPL): 2 expirations 2024-12-20..2025-01-17, 4 strikes 140..160"
+        );
+    }
+
+    #[test]
+    fn test_nested_option_chain_non_standard_deliverables() {
+        let chain = |deliverables: &str| -> NestedOptionChain {
+            serde_json::from_str(&format!(
+                r#"{{"underlying-symbol": "GE", "root-symbol": "GE1", "option-chain-type": "Non-standard",
+                    "shares-per-contract": 100, "deliverables": [{deliverables}], "expirations": []}}"#
+            ))
+            .unwrap()
+        };
+        let shares = r#"{"root-symbol": "GE1", "deliverable-type": "Shares", "amount": "100.0",
+            "symbol": "GE", "instrument-type": "Equity", "percent": "100"}"#;
+        let cash = r#"{"root-symbol": "GE1", "deliverable-type": "Cash", "amount": "12.50"}"#;
+
+        assert!(!chain(shares).is_non_standard());
+        let adjusted = chain(&format!("{shares}, {cash}"));
+        assert!(adjusted.is_non_standard());
+        assert_eq!(
+            adjusted.deliverables[1].deliverable_type,
+            DeliverableType::Cash
+        );
+        assert_eq!(
+            adjusted.deliverables[1].amount,
+            Decimal::from_str("12.5").unwrap()
+        );
+        assert!(adjusted.deliverables[1].symbol.is_none());
+    }
+
+    #[test]
+    fn test_occ_root() {
+        assert_eq!(occ_root("SPY   240119C00450000"), Some("SPY"));
+        assert_eq!(occ_root("BRKB1 240119P00300000"), Some("BRKB1"));
+        assert_eq!(occ_root("SPY"), None);
+        assert_eq!(occ_root("./ESZ4 EW4U4 240927P5000"), None);
+        assert_eq!(occ_root("SPY   240119X00450000"), None);
+    }
+
+    #[test]
+    fn test_future_month_codes() {
+        assert_eq!(month_from_code("H"), Some(3));
+        assert_eq!(month_from_code("z"), Some(12));
+        assert_eq!(month_from_code("A"), None);
+        assert_eq!(month_from_code("HM"), None);
+        assert_eq!(month_code(1), Some('F'));
+        assert_eq!(month_code(0), None);
+        assert_eq!(month_code(13), None);
+
+        let product: FutureProduct = serde_json::from_value(serde_json::json!({
+            "root-symbol": "/ES",
+            "code": "ES",
+            "description": "E-Mini S&P 500",
+            "clearing-code": "ES",
+            "clearing-exchange-code": "16",
+            "exchange": "CME",
+            "product-type": "Financial",
+            "listed-months": ["Z", "H", "M", "U", "X"],
+            "active-months": ["U", "M"],
+            "notional-multiplier": "50.0",
+            "tick-size": "0.25",
+            "display-factor": "0.01",
+            "streamer-exchange-code": "XCME",
+            "small-notional": false,
+            "back-month-first-calendar-symbol": false,
+            "first-notice": false,
+            "cash-settled": true,
+            "market-sector": "Equity Index",
+            "roll": {
+                "name": "equity_index",
+                "active-count": 3,
+                "cash-settled": true,
+                "business-days-offset": 4,
+                "first-notice": false
+            }
+        }))
+        .unwrap();
+        assert_eq!(product.listed_calendar_months(), vec![3, 6, 9, 12]);
+        assert_eq!(product.active_calendar_months(), vec![6, 9]);
+
+        let from = NaiveDate::from_ymd_opt(2024, 10, 2).unwrap();
+        let symbols: Vec<String> = product
+            .upcoming_contract_symbols(from, 3)
+            .into_iter()
+            .map(|s| s.0)
+            .collect();
+        assert_eq!(symbols, vec!["/ESZ4", "/ESH5", "/ESM5"]);
+    }
 }
@@ -0,0 +1,53 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! # tastytrade-types
+//!
+//! The data model of the tastytrade API: orders, positions, balances, instruments
+//! and the DXFeed event structures used by quote streaming.
+//!
+//! This crate has no HTTP, websocket or async runtime dependency, so it can be shared
+//! by servers, storage layers and wasm frontends that only need to read or produce
+//! tastytrade payloads. The [`tastytrade`](https://crates.io/crates/tastytrade) client
+//! re-exports everything defined here.
+//!
+//! With the `schemars` feature every payload type also implements
+//! `schemars::JsonSchema`; see the `schema` module and the `tastytrade-schemas` binary.
+//!
+//! ```rust
+//! use tastytrade_types::order::{Action, OrderLegBuilder, Symbol};
+//! use tastytrade_types::instrument::InstrumentType;
+//! use rust_decimal::Decimal;
+//!
+//! let leg = OrderLegBuilder::default()
+//!     .instrument_type(InstrumentType::Equity)
+//!     .symbol(Symbol::from("AAPL"))
+//!     .quantity(Decimal::from(10))
+//!     .action(Action::Buy)
+//!     .build()
+//!     .unwrap();
+//! ```
+
+pub mod account;
+pub mod balance;
+pub mod calendar;
+pub mod customer;
+pub mod dxfeed;
+pub mod instrument;
+pub mod login;
+pub mod market_metrics;
+pub mod order;
+pub mod position;
+#[cfg(feature = "schemars")]
+pub mod schema;
+pub mod sizing;
+pub mod strategy;
+pub mod transaction;
+pub mod validation;
+
+pub use account::AccountNumber;
+pub use instrument::InstrumentType;
+pub use order::{AsSymbol, DxFeedSymbol, Symbol};
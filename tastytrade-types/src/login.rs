@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use serde::{Deserialize, Serialize};
+
+/// Login credentials for authentication.
+///
+/// This struct holds the login information required for authentication, including
+/// the username, password, and a "remember me" flag.  It's designed for
+/// serialization with kebab-case renaming for compatibility with external APIs.
+#[derive(DebugPretty, DisplaySimple, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct LoginCredentials {
+    /// The username for login.
+    pub login: String,
+    /// The password for login.
+    pub password: String,
+    /// A flag indicating whether to remember the login.
+    pub remember_me: bool,
+}
+
+#[allow(dead_code)]
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+/// Represents a user in a login response.  This struct is used for deserializing the JSON response
+/// received after a successful login.  The `#[serde(rename_all = "kebab-case")]` attribute ensures
+/// that the fields in the JSON response are matched to the struct fields correctly, even if the
+/// casing is different (e.g., "external-id" in JSON will map to `external_id` in the struct).
+pub struct LoginResponseUser {
+    /// The user's email address.
+    pub email: String,
+    /// The user's username.
+    pub username: String,
+    /// The user's external ID.
+    pub external_id: String,
+}
+
+/// Represents the response received after a successful login.
+///
+/// This struct is used for deserializing the JSON response.
+/// The `#[serde(rename_all = "kebab-case")]` attribute ensures that the
+/// fields in the JSON response are matched to the struct fields correctly,
+/// even if the casing is different (e.g., "session-token" in JSON will map to
+/// `session_token` in the struct).
+#[allow(dead_code)]
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct LoginResponse {
+    /// The user information associated with the login.
+    pub user: LoginResponseUser,
+    /// The session token.
+    pub session_token: String,
+    /// The remember token (optional).
+    pub remember_token: Option<String>,
+    /// When the session token expires, if reported.
+    #[serde(default)]
+    pub session_expiration: Option<DateTime<Utc>>,
+    /// Account streamer websocket URL of the environment the session belongs to, if
+    /// reported.
+    #[serde(default, alias = "websocket-url")]
+    pub streamer_url: Option<String>,
+}
+
+/// The session as reported by `POST /sessions/validate`.
+///
+/// The API answers with the user the token belongs to; a rejected token is an error
+/// response instead.
+#[derive(DebugPretty, DisplaySimple, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct SessionValidation {
+    /// The user's email address.
+    pub email: String,
+    /// The user's username.
+    pub username: String,
+    /// The user's external ID.
+    pub external_id: String,
+    /// The user's numeric ID, if reported.
+    #[serde(default)]
+    pub id: Option<u64>,
+    /// When the session token expires, if reported.
+    #[serde(default)]
+    pub session_expiration: Option<DateTime<Utc>>,
+}
+
+impl SessionValidation {
+    /// Time left before the session expires at `now`, zero once expired. `None` when the
+    /// API did not report an expiration.
+    pub fn remaining_lifetime(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.session_expiration
+            .map(|expires| (expires - now).max(chrono::Duration::zero()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_validation_remaining_lifetime() {
+        let json = r#"{
+            "email": "trader@example.com",
+            "username": "trader",
+            "external-id": "U0001",
+            "id": 42,
+            "session-expiration": "2026-10-16T12:00:00Z"
+        }"#;
+        let validation: SessionValidation = serde_json::from_str(json).unwrap();
+        assert_eq!(validation.id, Some(42));
+
+        let now: DateTime<Utc> = "2026-10-16T11:30:00Z".parse().unwrap();
+        assert_eq!(
+            validation.remaining_lifetime(now),
+            Some(chrono::Duration::minutes(30))
+        );
+        let later: DateTime<Utc> = "2026-10-16T13:00:00Z".parse().unwrap();
+        assert_eq!(
+            validation.remaining_lifetime(later),
+            Some(chrono::Duration::zero())
+        );
+    }
+}
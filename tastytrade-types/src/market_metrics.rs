@@ -0,0 +1,227 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Volatility and liquidity statistics from `/market-metrics`.
+//!
+//! Volatilities come in two units: the index and the per-expiration values are
+//! fractions, the 30/60/90-day implied and historical ones are percentages, as the API
+//! reports them. [`TermStructure`] lines the per-expiration implied volatilities up with
+//! an option chain, for calendar spreads and volatility trades.
+
+use crate::order::Symbol;
+use chrono::{DateTime, NaiveDate, Utc};
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Implied volatility of one expiration of the option chain.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ExpirationImpliedVolatility {
+    /// The expiration date.
+    pub expiration_date: NaiveDate,
+    /// The chain the expiration belongs to, e.g. `Standard` or `Non-standard`.
+    #[serde(default)]
+    pub option_chain_type: Option<String>,
+    /// `AM` or `PM` settlement.
+    #[serde(default)]
+    pub settlement_type: Option<String>,
+    /// Implied volatility of the expiration, as a fraction.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub implied_volatility: Option<Decimal>,
+}
+
+/// Market metrics of one symbol. Fields the API leaves out for a symbol are `None`.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct MarketMetrics {
+    /// The symbol the metrics describe.
+    pub symbol: Symbol,
+    /// Implied volatility index of the options, as a fraction (`0.25` is 25%).
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub implied_volatility_index: Option<Decimal>,
+    /// Change of [`implied_volatility_index`](Self::implied_volatility_index) over five days.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub implied_volatility_index_5_day_change: Option<Decimal>,
+    /// IV rank over the past year, from 0 to 1.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub implied_volatility_index_rank: Option<Decimal>,
+    /// IV percentile over the past year, from 0 to 1.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub implied_volatility_percentile: Option<Decimal>,
+    /// 30-day implied volatility, in percent.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub implied_volatility_30_day: Option<Decimal>,
+    /// 30-day historical volatility, in percent.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub historical_volatility_30_day: Option<Decimal>,
+    /// 60-day historical volatility, in percent.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub historical_volatility_60_day: Option<Decimal>,
+    /// 90-day historical volatility, in percent.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub historical_volatility_90_day: Option<Decimal>,
+    /// 30-day implied minus historical volatility, in percent.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub iv_hv_30_day_difference: Option<Decimal>,
+    /// Beta against SPY.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub beta: Option<Decimal>,
+    /// Implied volatility of each listed expiration.
+    #[serde(default)]
+    pub option_expiration_implied_volatilities: Vec<ExpirationImpliedVolatility>,
+    /// When the metrics were computed.
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl MarketMetrics {
+    /// The implied volatility of `expiration`, preferring the entry settled as
+    /// `settlement_type` when the date has several.
+    pub fn expiration_iv(
+        &self,
+        expiration: NaiveDate,
+        settlement_type: Option<&str>,
+    ) -> Option<&ExpirationImpliedVolatility> {
+        let mut same_date = self
+            .option_expiration_implied_volatilities
+            .iter()
+            .filter(|iv| iv.expiration_date == expiration);
+        let first = same_date.next()?;
+        let settled = |iv: &&ExpirationImpliedVolatility| {
+            settlement_type.is_some() && iv.settlement_type.as_deref() == settlement_type
+        };
+        Some(
+            std::iter::once(first)
+                .chain(same_date)
+                .find(settled)
+                .unwrap_or(first),
+        )
+    }
+}
+
+/// One expiration of a [`TermStructure`].
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct TermStructurePoint {
+    /// The expiration date.
+    pub expiration_date: NaiveDate,
+    /// Days left until the expiration.
+    pub days_to_expiration: u64,
+    /// The kind of expiration, e.g. `Regular` or `Weekly`.
+    pub expiration_type: String,
+    /// `AM` or `PM` settlement.
+    pub settlement_type: String,
+    /// Implied volatility of the expiration, as a fraction. `None` when the metrics do
+    /// not cover it.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub implied_volatility: Option<Decimal>,
+}
+
+/// Implied volatility by expiration for one underlying, nearest expiration first.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct TermStructure {
+    /// The underlying.
+    pub underlying: Symbol,
+    /// Implied volatility index of the underlying, as a fraction.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub implied_volatility_index: Option<Decimal>,
+    /// 30-day historical volatility of the underlying, in percent.
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision_option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<Decimal>"))]
+    pub historical_volatility_30_day: Option<Decimal>,
+    /// One point per expiration of the chain.
+    pub points: Vec<TermStructurePoint>,
+}
+
+impl TermStructure {
+    /// Points whose implied volatility is known.
+    pub fn known_points(&self) -> impl Iterator<Item = &TermStructurePoint> {
+        self.points
+            .iter()
+            .filter(|point| point.implied_volatility.is_some())
+    }
+
+    /// Implied volatility of the last known expiration minus the first: positive in
+    /// contango, negative when the curve is inverted. `None` with fewer than two known
+    /// points.
+    pub fn slope(&self) -> Option<Decimal> {
+        let mut known = self.known_points();
+        let front = known.next()?.implied_volatility?;
+        let back = known.last()?.implied_volatility?;
+        Some(back - front)
+    }
+
+    /// Returns `true` when near expirations are priced higher than far ones, as
+    /// around earnings or in a sell-off.
+    pub fn is_inverted(&self) -> bool {
+        self.slope().is_some_and(|slope| slope < Decimal::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_market_metrics_expiration_iv() {
+        let metrics: MarketMetrics = serde_json::from_str(
+            r#"{
+                "symbol": "SPX",
+                "implied-volatility-index": "0.1264",
+                "option-expiration-implied-volatilities": [
+                    {"expiration-date": "2024-06-21", "settlement-type": "AM", "implied-volatility": "0.13"},
+                    {"expiration-date": "2024-06-21", "settlement-type": "PM", "implied-volatility": "0.12"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+        let iv = |settlement| metrics.expiration_iv(date, settlement).unwrap();
+        assert_eq!(
+            iv(Some("PM")).implied_volatility,
+            Decimal::from_str("0.12").ok()
+        );
+        assert_eq!(iv(None).implied_volatility, Decimal::from_str("0.13").ok());
+        assert!(metrics.historical_volatility_30_day.is_none());
+
+        let point = |day, iv: &str| TermStructurePoint {
+            expiration_date: NaiveDate::from_ymd_opt(2024, 6, day).unwrap(),
+            days_to_expiration: u64::from(day),
+            expiration_type: "Weekly".to_string(),
+            settlement_type: "PM".to_string(),
+            implied_volatility: Decimal::from_str(iv).ok(),
+        };
+        let curve = TermStructure {
+            underlying: Symbol::from("SPX"),
+            implied_volatility_index: metrics.implied_volatility_index,
+            historical_volatility_30_day: None,
+            points: vec![point(7, "0.20"), point(14, "-"), point(21, "0.15")],
+        };
+        assert_eq!(curve.known_points().count(), 2);
+        assert_eq!(curve.slope(), Decimal::from_str("-0.05").ok());
+        assert!(curve.is_inverted());
+    }
+}
@@ -1,6 +1,6 @@
 use super::order::{PriceEffect, Symbol};
-use crate::accounts::AccountNumber;
-use crate::types::instrument::InstrumentType;
+use crate::account::AccountNumber;
+use crate::instrument::{Deliverable, DeliverableType, InstrumentType, occ_root};
 use pretty_simple_display::{DebugPretty, DisplaySimple};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -8,6 +8,7 @@ use std::fmt::Display;
 
 /// Represents the direction of a quantity, such as a trade or position.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum QuantityDirection {
     /// Represents a long position or buy trade.
     Long,
@@ -32,7 +33,8 @@ impl Display for QuantityDirection {
 /// This struct provides detailed information about a specific position held in an account, including
 /// the instrument, quantity, price details, and various flags.  It's designed for deserialization
 /// with kebab-case renaming for compatibility with external APIs.
-#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct FullPosition {
     /// The account number associated with the position.
@@ -45,23 +47,29 @@ pub struct FullPosition {
     pub underlying_symbol: Symbol,
     /// The quantity of the instrument held in the position.  Uses arbitrary precision for accuracy.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub quantity: Decimal,
     /// The direction of the quantity (Long, Short, or Zero).
     pub quantity_direction: QuantityDirection,
     /// The closing price of the instrument.  Uses arbitrary precision for accuracy.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub close_price: Decimal,
     /// The average opening price of the instrument. Uses arbitrary precision for accuracy.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub average_open_price: Decimal,
     /// The average yearly market close price of the instrument. Uses arbitrary precision for accuracy.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub average_yearly_market_close_price: Decimal,
     /// The average daily market close price of the instrument. Uses arbitrary precision for accuracy.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub average_daily_market_close_price: Decimal,
     /// The multiplier for the instrument. Uses floating-point deserialization for the Decimal type.
     #[serde(with = "rust_decimal::serde::float")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub multiplier: Decimal,
     /// The effect of the price on the account (Debit, Credit, or None).
     pub cost_effect: PriceEffect,
@@ -71,9 +79,11 @@ pub struct FullPosition {
     pub is_frozen: bool,
     /// The restricted quantity of the instrument. Uses arbitrary precision for accuracy.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub restricted_quantity: Decimal,
     /// The realized day gain for the position. Uses arbitrary precision for accuracy.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub realized_day_gain: Decimal,
     /// The effect of the realized day gain (e.g., "Debit", "Credit").
     pub realized_day_gain_effect: String,
@@ -81,6 +91,7 @@ pub struct FullPosition {
     pub realized_day_gain_date: String,
     /// The realized gain for today. Uses arbitrary precision for accuracy.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub realized_today: Decimal,
     /// The effect of the realized gain for today (e.g., "Debit", "Credit").
     pub realized_today_effect: String,
@@ -99,6 +110,7 @@ pub struct FullPosition {
 /// designed for deserialization with kebab-case renaming for compatibility with
 /// external APIs.
 #[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 pub struct BriefPosition {
     /// The account number associated with the position.
@@ -111,17 +123,21 @@ pub struct BriefPosition {
     pub underlying_symbol: Symbol,
     /// The quantity of the instrument held in the position.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub quantity: Decimal,
     /// The direction of the quantity (Long, Short, or Zero).
     pub quantity_direction: QuantityDirection,
     /// The closing price of the instrument.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub close_price: Decimal,
     /// The average opening price of the instrument.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub average_open_price: Decimal,
     /// The multiplier for the instrument.
     #[serde(with = "rust_decimal::serde::float")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub multiplier: Decimal,
     /// The effect of the price on the account (Debit, Credit, or None).
     pub cost_effect: PriceEffect,
@@ -131,12 +147,15 @@ pub struct BriefPosition {
     pub is_frozen: bool,
     /// The restricted quantity of the instrument.
     #[serde(with = "rust_decimal::serde::float")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub restricted_quantity: Decimal,
     /// The realized day gain for the position.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub realized_day_gain: Decimal,
     /// The realized amount for today.
     #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
     pub realized_today: Decimal,
     /// The timestamp of when the position was created.
     pub created_at: String,
@@ -144,6 +163,114 @@ pub struct BriefPosition {
     pub updated_at: String,
 }
 
+/// Fixed-width text rendering of a set of positions, for logs and terminals.
+pub trait PositionsSummary {
+    /// One header line plus one line per position: symbol, type, signed quantity,
+    /// average open price, close price and account.
+    fn summary_table(&self) -> String;
+}
+
+impl PositionsSummary for [FullPosition] {
+    fn summary_table(&self) -> String {
+        let mut table = format!(
+            "{:<24} {:<16} {:>10} {:>12} {:>12} {:<12}\n",
+            "SYMBOL", "TYPE", "QTY", "AVG OPEN", "CLOSE", "ACCOUNT"
+        );
+        for position in self {
+            let quantity = position.signed_quantity();
+            table.push_str(&format!(
+                "{:<24} {:<16} {:>10} {:>12} {:>12} {:<12}\n",
+                position.symbol.0,
+                position.instrument_type.to_string(),
+                quantity.normalize().to_string(),
+                position.average_open_price.round_dp(4).to_string(),
+                position.close_price.round_dp(4).to_string(),
+                position.account_number.0,
+            ));
+        }
+        table
+    }
+}
+
+impl FullPosition {
+    /// Quantity with shorts negative.
+    pub fn signed_quantity(&self) -> Decimal {
+        match self.quantity_direction {
+            QuantityDirection::Short => -self.quantity,
+            _ => self.quantity,
+        }
+    }
+
+    /// Open P&L if the position were valued at `mark`: (mark − average open) × quantity × multiplier.
+    pub fn unrealized_pnl(&self, mark: Decimal) -> Decimal {
+        (mark - self.average_open_price) * self.signed_quantity() * self.multiplier
+    }
+
+    /// P&L realized on this position today, negative when `realized_day_gain_effect` is "Debit".
+    pub fn realized_day_pnl(&self) -> Decimal {
+        if self.realized_day_gain_effect.eq_ignore_ascii_case("Debit") {
+            -self.realized_day_gain
+        } else {
+            self.realized_day_gain
+        }
+    }
+
+    /// Returns `true` for equity options adjusted after a corporate action: their OCC
+    /// root differs from the underlying (`GE1` on `GE`) or the multiplier is not 100.
+    ///
+    /// Such contracts may deliver other shares or cash in lieu, so P&L derived from the
+    /// underlying's price is wrong for them. [`AdjustedPosition`] pairs one with its
+    /// deliverables.
+    pub fn is_adjusted(&self) -> bool {
+        if !matches!(self.instrument_type, InstrumentType::EquityOption) {
+            return false;
+        }
+        let root_differs =
+            occ_root(&self.symbol.0).is_some_and(|root| root != self.underlying_symbol.0);
+        root_differs || self.multiplier != Decimal::ONE_HUNDRED
+    }
+
+    #[doc(hidden)]
+    pub fn debug_compact(&self) -> String {
+        format!(
+            "{} {} {} @ {}",
+            self.symbol.0, self.quantity_direction, self.quantity, self.average_open_price
+        )
+    }
+}
+
+/// An [adjusted](FullPosition::is_adjusted) option position with what each contract
+/// delivers on exercise.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AdjustedPosition {
+    /// The position.
+    pub position: FullPosition,
+    /// Deliverables of one contract.
+    pub deliverables: Vec<Deliverable>,
+}
+
+impl AdjustedPosition {
+    /// Shares of `symbol` delivered by one contract.
+    pub fn shares_of(&self, symbol: &Symbol) -> Decimal {
+        self.deliverables
+            .iter()
+            .filter(|d| d.deliverable_type == DeliverableType::Shares)
+            .filter(|d| d.symbol.as_ref() == Some(symbol))
+            .map(|d| d.amount)
+            .sum()
+    }
+
+    /// Cash delivered by one contract, in USD.
+    pub fn cash_in_lieu(&self) -> Decimal {
+        self.deliverables
+            .iter()
+            .filter(|d| d.deliverable_type == DeliverableType::Cash)
+            .map(|d| d.amount)
+            .sum()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -242,6 +369,19 @@ mod tests {
         assert_eq!(position.quantity, Decimal::from_str("100").unwrap());
         matches!(position.quantity_direction, QuantityDirection::Long);
         matches!(position.instrument_type, InstrumentType::Equity);
+
+        assert_eq!(position.debug_compact(), "AAPL Long 100 @ 145.00");
+        assert_eq!(
+            position.unrealized_pnl(Decimal::from(150)),
+            Decimal::from(500)
+        );
+        assert_eq!(position.realized_day_pnl(), Decimal::from(550));
+        let table = std::slice::from_ref(&position).summary_table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("SYMBOL"));
+        assert!(lines[1].starts_with("AAPL"));
+        assert!(lines[1].contains("145.00") && lines[1].ends_with("TEST123"));
     }
 
     #[test]
@@ -308,4 +448,34 @@ mod tests {
         assert_eq!(position.quantity, Decimal::ZERO);
         matches!(position.cost_effect, PriceEffect::None);
     }
+
+    #[test]
+    fn test_full_position_is_adjusted() {
+        let body: serde_json::Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/positions.json")).unwrap();
+        let option = |symbol: &str, multiplier: f64| -> FullPosition {
+            let mut position = body["data"]["items"][1].clone();
+            position["symbol"] = symbol.into();
+            position["multiplier"] = multiplier.into();
+            serde_json::from_str(&position.to_string()).unwrap()
+        };
+        assert!(!option("SPY   240621P00500000", 100.0).is_adjusted());
+        assert!(option("SPY1  240621P00500000", 100.0).is_adjusted());
+        assert!(option("SPY   240621P00500000", 125.0).is_adjusted());
+
+        let adjusted = AdjustedPosition {
+            position: option("SPY1  240621P00500000", 100.0),
+            deliverables: serde_json::from_str(
+                r#"[{"root-symbol": "SPY1", "deliverable-type": "Shares", "amount": "100", "symbol": "SPY"},
+                    {"root-symbol": "SPY1", "deliverable-type": "Shares", "amount": "25", "symbol": "GEHC"},
+                    {"root-symbol": "SPY1", "deliverable-type": "Cash", "amount": "3.10"}]"#,
+            )
+            .unwrap(),
+        };
+        assert_eq!(
+            adjusted.shares_of(&Symbol("GEHC".to_string())),
+            Decimal::from(25)
+        );
+        assert_eq!(adjusted.cash_in_lieu(), Decimal::from_str("3.10").unwrap());
+    }
 }
@@ -0,0 +1,103 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! JSON Schemas of the payload types, for services written in other languages.
+//!
+//! Only available with the `schemars` feature. The `tastytrade-schemas` binary writes
+//! every schema returned by [`schemas`] to a directory:
+//!
+//! ```text
+//! cargo run -p tastytrade-types --features schemars --bin tastytrade-schemas -- ./schemas
+//! ```
+
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+use crate::account::AccountNumber;
+use crate::balance::{Balance, BalanceSnapshot, NetLiqHistoryPoint};
+use crate::customer::Customer;
+use crate::dxfeed::Event;
+use crate::instrument::{
+    CompactOptionChainResponse, Cryptocurrency, EquityInstrument, EquityOption, ExpirationSummary,
+    Future, FutureOption, FutureOptionProduct, FutureProduct, FuturesCompactOptionChain,
+    FuturesNestedOptionChain, NestedOptionChain, QuantityDecimalPrecision, SymbolEntry, Warrant,
+};
+use crate::order::{
+    ComplexOrderPlacedResult, DryRunResult, LiveOrderRecord, Order, OrderPlacedResult,
+};
+use crate::position::{BriefPosition, FullPosition};
+use crate::transaction::ExternalTransaction;
+
+/// Schemas of the top-level request and response payloads, keyed by type name.
+///
+/// Nested types (legs, strikes, enums, ...) are included as definitions of the
+/// schema that references them.
+pub fn schemas() -> Vec<(&'static str, RootSchema)> {
+    vec![
+        ("AccountNumber", schema_for!(AccountNumber)),
+        ("Customer", schema_for!(Customer)),
+        ("Balance", schema_for!(Balance)),
+        ("BalanceSnapshot", schema_for!(BalanceSnapshot)),
+        ("NetLiqHistoryPoint", schema_for!(NetLiqHistoryPoint)),
+        ("FullPosition", schema_for!(FullPosition)),
+        ("BriefPosition", schema_for!(BriefPosition)),
+        ("Order", schema_for!(Order)),
+        ("DryRunResult", schema_for!(DryRunResult)),
+        ("OrderPlacedResult", schema_for!(OrderPlacedResult)),
+        ("LiveOrderRecord", schema_for!(LiveOrderRecord)),
+        ("ExternalTransaction", schema_for!(ExternalTransaction)),
+        (
+            "ComplexOrderPlacedResult",
+            schema_for!(ComplexOrderPlacedResult),
+        ),
+        ("EquityInstrument", schema_for!(EquityInstrument)),
+        ("EquityOption", schema_for!(EquityOption)),
+        ("Future", schema_for!(Future)),
+        ("FutureProduct", schema_for!(FutureProduct)),
+        ("FutureOption", schema_for!(FutureOption)),
+        ("FutureOptionProduct", schema_for!(FutureOptionProduct)),
+        ("Cryptocurrency", schema_for!(Cryptocurrency)),
+        ("Warrant", schema_for!(Warrant)),
+        (
+            "QuantityDecimalPrecision",
+            schema_for!(QuantityDecimalPrecision),
+        ),
+        ("NestedOptionChain", schema_for!(NestedOptionChain)),
+        (
+            "FuturesNestedOptionChain",
+            schema_for!(FuturesNestedOptionChain),
+        ),
+        (
+            "CompactOptionChainResponse",
+            schema_for!(CompactOptionChainResponse),
+        ),
+        (
+            "FuturesCompactOptionChain",
+            schema_for!(FuturesCompactOptionChain),
+        ),
+        ("ExpirationSummary", schema_for!(ExpirationSummary)),
+        ("SymbolEntry", schema_for!(SymbolEntry)),
+        ("Event", schema_for!(Event)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_schema_uses_wire_names() {
+        let (_, order) = schemas()
+            .into_iter()
+            .find(|(name, _)| *name == "Order")
+            .unwrap();
+        let json = serde_json::to_value(&order).unwrap();
+        let properties = &json["properties"];
+        assert!(properties.get("time-in-force").is_some());
+        assert!(properties.get("price-effect").is_some());
+        assert!(json["definitions"].get("OrderLeg").is_some());
+    }
+}
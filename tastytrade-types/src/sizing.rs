@@ -0,0 +1,108 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Order sizing against buying power.
+//!
+//! A defined-risk spread uses the same buying power per unit whatever the quantity, so
+//! one dry run is enough to size it: [`max_spreads_for_buying_power`] divides the room
+//! left under a utilization cap by the buying power of one spread.
+//!
+//! Utilization is the share of net liquidating value not available as derivative
+//! buying power, the figure shown as "BP usage" on the platform.
+//!
+//! ```rust,ignore
+//! let dry_run = account.dry_run(&one_spread).await?.data;
+//! let balance = account.balance().await?;
+//! let quantity = max_spreads_for_buying_power(&dry_run, &balance, dec!(0.5)).unwrap_or(10);
+//! ```
+
+use crate::balance::Balance;
+use crate::order::{DryRunResult, PriceEffect};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Buying power used by one unit of the dry-run order, `None` when the order does not
+/// use any (a credit or no change, e.g. when it closes positions).
+pub fn buying_power_per_unit(dry_run: &DryRunResult) -> Option<Decimal> {
+    let effect = &dry_run.buying_power_effect;
+    if !matches!(effect.change_in_buying_power_effect, PriceEffect::Debit)
+        || effect.change_in_buying_power <= Decimal::ZERO
+    {
+        return None;
+    }
+    let size = Decimal::from(dry_run.order.size.max(1));
+    Some(effect.change_in_buying_power.abs() / size)
+}
+
+/// Share of `balance`'s net liquidating value currently in use, between `0` and `1`.
+/// `None` when the net liquidating value is not positive.
+pub fn buying_power_utilization(balance: &Balance) -> Option<Decimal> {
+    if balance.net_liquidating_value <= Decimal::ZERO {
+        return None;
+    }
+    let used = (balance.net_liquidating_value - balance.derivative_buying_power).max(Decimal::ZERO);
+    Some((used / balance.net_liquidating_value).min(Decimal::ONE))
+}
+
+/// How many spreads like the dry-run order can be opened while keeping utilization
+/// at or below `utilization_cap` (`0.5` for 50%).
+///
+/// The dry run may be for any quantity; the buying power per spread is derived from
+/// its size. The result never exceeds the derivative buying power left, and is `0`
+/// when the account is already over the cap or has no net liquidating value. Returns
+/// `None` when the order does not use buying power, so it is not the limiting factor.
+pub fn max_spreads_for_buying_power(
+    dry_run: &DryRunResult,
+    balance: &Balance,
+    utilization_cap: Decimal,
+) -> Option<u64> {
+    let per_spread = buying_power_per_unit(dry_run)?;
+    Some(max_units(
+        per_spread,
+        balance.net_liquidating_value,
+        balance.derivative_buying_power,
+        utilization_cap,
+    ))
+}
+
+fn max_units(per_unit: Decimal, net_liq: Decimal, available: Decimal, cap: Decimal) -> u64 {
+    if net_liq <= Decimal::ZERO || cap <= Decimal::ZERO {
+        return 0;
+    }
+    let used = (net_liq - available).max(Decimal::ZERO);
+    let room = (net_liq * cap.min(Decimal::ONE) - used)
+        .min(available)
+        .max(Decimal::ZERO);
+    (room / per_unit).floor().to_u64().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_max_units_respects_cap_and_available() {
+        // 10k net liq, 8k buying power left: 20% used, 30% more allowed at a 50% cap.
+        assert_eq!(
+            max_units(dec("400"), dec("10000"), dec("8000"), dec("0.5")),
+            7
+        );
+        assert_eq!(
+            max_units(dec("400"), dec("10000"), dec("8000"), dec("2")),
+            20
+        );
+        assert_eq!(
+            max_units(dec("400"), dec("10000"), dec("4000"), dec("0.5")),
+            0
+        );
+        assert_eq!(max_units(dec("400"), dec("0"), dec("0"), dec("0.5")), 0);
+    }
+}
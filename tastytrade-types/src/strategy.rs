@@ -0,0 +1,496 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Multi-leg option orders built from a [`NestedOptionChain`].
+//!
+//! A [`StrategyBuilder`] looks strikes up in the chain by expiration date and strike
+//! price and returns a limit [`Order`] good for the day with every leg's action and
+//! quantity set. `price` is the net price of one unit of the strategy; whether it is
+//! paid or collected follows from the structure: buying the more expensive leg of a
+//! vertical, a long strangle, a calendar and a covered call are debits; selling them
+//! and iron condors are credits.
+//!
+//! ```rust,ignore
+//! let chain = tasty.nested_option_chain_for("SPY").await?;
+//! let strategies = StrategyBuilder::new(&chain, 2);
+//! let expiration = NaiveDate::from_ymd_opt(2026, 12, 18).unwrap();
+//! let condor = strategies.iron_condor(
+//!     expiration,
+//!     [dec!(540), dec!(550), dec!(610), dec!(620)],
+//!     dec!(2.10),
+//! )?;
+//! account.place_order(&condor).await?;
+//! ```
+
+use crate::instrument::{Expiration, InstrumentType, NestedOptionChain, Strike};
+use crate::order::{
+    Action, Order, OrderBuilder, OrderBuilderError, OrderLeg, OrderLegBuilder,
+    OrderLegBuilderError, OrderType, PriceEffect, Symbol, TimeInForce,
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::fmt;
+
+/// Call or put.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionKind {
+    /// A call option.
+    Call,
+    /// A put option.
+    Put,
+}
+
+/// Long (bought) or short (sold).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// The strategy is bought, for a debit.
+    Long,
+    /// The strategy is sold, for a credit.
+    Short,
+}
+
+/// Why a strategy could not be built.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StrategyError {
+    /// The chain has no expiration on this date.
+    NoExpiration(NaiveDate),
+    /// The expiration has no strike at this price.
+    NoStrike {
+        /// The expiration looked in.
+        expiration: NaiveDate,
+        /// The missing strike.
+        strike: Decimal,
+    },
+    /// The strikes or expirations given do not form the strategy, e.g. a condor whose
+    /// strikes are not increasing.
+    InvalidStructure(String),
+    /// The order could not be built, e.g. because the quantity is zero.
+    Order(String),
+}
+
+impl fmt::Display for StrategyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrategyError::NoExpiration(date) => write!(f, "no expiration on {date}"),
+            StrategyError::NoStrike { expiration, strike } => {
+                write!(f, "no {strike} strike on {expiration}")
+            }
+            StrategyError::InvalidStructure(msg) => write!(f, "invalid strategy: {msg}"),
+            StrategyError::Order(msg) => write!(f, "invalid order: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for StrategyError {}
+
+impl From<OrderLegBuilderError> for StrategyError {
+    fn from(err: OrderLegBuilderError) -> Self {
+        StrategyError::Order(err.to_string())
+    }
+}
+
+impl From<OrderBuilderError> for StrategyError {
+    fn from(err: OrderBuilderError) -> Self {
+        StrategyError::Order(err.to_string())
+    }
+}
+
+/// Builds strategy orders on one chain. See the module docs.
+#[derive(Debug, Clone, Copy)]
+pub struct StrategyBuilder<'a> {
+    chain: &'a NestedOptionChain,
+    quantity: u32,
+}
+
+impl<'a> StrategyBuilder<'a> {
+    /// Builds `quantity` units of each strategy on `chain`.
+    pub fn new(chain: &'a NestedOptionChain, quantity: u32) -> Self {
+        Self { chain, quantity }
+    }
+
+    /// A vertical spread buying `long_strike` and selling `short_strike`.
+    ///
+    /// A debit when the long leg is the more expensive one: the lower strike of a call
+    /// spread, the higher strike of a put spread.
+    pub fn vertical(
+        &self,
+        expiration: NaiveDate,
+        kind: OptionKind,
+        long_strike: Decimal,
+        short_strike: Decimal,
+        price: Decimal,
+    ) -> Result<Order, StrategyError> {
+        if long_strike == short_strike {
+            return Err(StrategyError::InvalidStructure(
+                "a vertical needs two different strikes".to_string(),
+            ));
+        }
+        let expiration = self.expiration(expiration)?;
+        let long = self.option(expiration, long_strike, kind)?;
+        let short = self.option(expiration, short_strike, kind)?;
+        let debit = match kind {
+            OptionKind::Call => long_strike < short_strike,
+            OptionKind::Put => long_strike > short_strike,
+        };
+        self.order(
+            vec![
+                self.leg(long, Action::BuyToOpen)?,
+                self.leg(short, Action::SellToOpen)?,
+            ],
+            price,
+            effect(debit),
+        )
+    }
+
+    /// A short iron condor on `strikes`, lowest first: long put, short put, short call,
+    /// long call. Always a credit.
+    pub fn iron_condor(
+        &self,
+        expiration: NaiveDate,
+        strikes: [Decimal; 4],
+        price: Decimal,
+    ) -> Result<Order, StrategyError> {
+        if !strikes.windows(2).all(|pair| pair[0] < pair[1]) {
+            return Err(StrategyError::InvalidStructure(format!(
+                "iron condor strikes must increase, got {strikes:?}"
+            )));
+        }
+        let [long_put, short_put, short_call, long_call] = strikes;
+        let expiration = self.expiration(expiration)?;
+        self.order(
+            vec![
+                self.leg(
+                    self.option(expiration, long_put, OptionKind::Put)?,
+                    Action::BuyToOpen,
+                )?,
+                self.leg(
+                    self.option(expiration, short_put, OptionKind::Put)?,
+                    Action::SellToOpen,
+                )?,
+                self.leg(
+                    self.option(expiration, short_call, OptionKind::Call)?,
+                    Action::SellToOpen,
+                )?,
+                self.leg(
+                    self.option(expiration, long_call, OptionKind::Call)?,
+                    Action::BuyToOpen,
+                )?,
+            ],
+            price,
+            PriceEffect::Credit,
+        )
+    }
+
+    /// A strangle of the put at `put_strike` and the call at `call_strike`, bought or
+    /// sold. Equal strikes make a straddle.
+    pub fn strangle(
+        &self,
+        expiration: NaiveDate,
+        direction: Direction,
+        put_strike: Decimal,
+        call_strike: Decimal,
+        price: Decimal,
+    ) -> Result<Order, StrategyError> {
+        if put_strike > call_strike {
+            return Err(StrategyError::InvalidStructure(format!(
+                "put strike {put_strike} is above call strike {call_strike}"
+            )));
+        }
+        let expiration = self.expiration(expiration)?;
+        let action = match direction {
+            Direction::Long => Action::BuyToOpen,
+            Direction::Short => Action::SellToOpen,
+        };
+        self.order(
+            vec![
+                self.leg(
+                    self.option(expiration, put_strike, OptionKind::Put)?,
+                    action.clone(),
+                )?,
+                self.leg(
+                    self.option(expiration, call_strike, OptionKind::Call)?,
+                    action,
+                )?,
+            ],
+            price,
+            effect(direction == Direction::Long),
+        )
+    }
+
+    /// A calendar spread selling `strike` on `near` and buying it on `far`. Always a
+    /// debit.
+    pub fn calendar(
+        &self,
+        kind: OptionKind,
+        strike: Decimal,
+        near: NaiveDate,
+        far: NaiveDate,
+        price: Decimal,
+    ) -> Result<Order, StrategyError> {
+        if near >= far {
+            return Err(StrategyError::InvalidStructure(format!(
+                "near expiration {near} is not before far expiration {far}"
+            )));
+        }
+        let short = self.option(self.expiration(near)?, strike, kind)?;
+        let long = self.option(self.expiration(far)?, strike, kind)?;
+        self.order(
+            vec![
+                self.leg(short, Action::SellToOpen)?,
+                self.leg(long, Action::BuyToOpen)?,
+            ],
+            price,
+            PriceEffect::Debit,
+        )
+    }
+
+    /// A covered call: buys the shares delivered by one contract per unit and sells the
+    /// call at `strike` against them. `price` is the stock price less the premium, a
+    /// debit.
+    pub fn covered_call(
+        &self,
+        expiration: NaiveDate,
+        strike: Decimal,
+        price: Decimal,
+    ) -> Result<Order, StrategyError> {
+        let call = self.option(self.expiration(expiration)?, strike, OptionKind::Call)?;
+        let shares = u64::from(self.quantity) * self.chain.shares_per_contract;
+        let stock = OrderLegBuilder::default()
+            .instrument_type(InstrumentType::Equity)
+            .symbol(self.chain.underlying_symbol.clone())
+            .quantity(shares)
+            .action(Action::BuyToOpen)
+            .build()?;
+        self.order(
+            vec![stock, self.leg(call, Action::SellToOpen)?],
+            price,
+            PriceEffect::Debit,
+        )
+    }
+
+    fn expiration(&self, date: NaiveDate) -> Result<&'a Expiration, StrategyError> {
+        let date_str = date.format("%Y-%m-%d").to_string();
+        self.chain
+            .expirations
+            .iter()
+            .find(|expiration| expiration.expiration_date == date_str)
+            .ok_or(StrategyError::NoExpiration(date))
+    }
+
+    fn option(
+        &self,
+        expiration: &'a Expiration,
+        strike: Decimal,
+        kind: OptionKind,
+    ) -> Result<&'a Symbol, StrategyError> {
+        let found: Option<&Strike> = expiration.strikes.iter().find(|s| s.strike_price == strike);
+        let found = found.ok_or_else(|| StrategyError::NoStrike {
+            expiration: NaiveDate::parse_from_str(&expiration.expiration_date, "%Y-%m-%d")
+                .unwrap_or_default(),
+            strike,
+        })?;
+        Ok(match kind {
+            OptionKind::Call => &found.call,
+            OptionKind::Put => &found.put,
+        })
+    }
+
+    fn leg(&self, symbol: &Symbol, action: Action) -> Result<OrderLeg, StrategyError> {
+        Ok(OrderLegBuilder::default()
+            .instrument_type(InstrumentType::EquityOption)
+            .symbol(symbol.clone())
+            .quantity(self.quantity)
+            .action(action)
+            .build()?)
+    }
+
+    fn order(
+        &self,
+        legs: Vec<OrderLeg>,
+        price: Decimal,
+        price_effect: PriceEffect,
+    ) -> Result<Order, StrategyError> {
+        Ok(OrderBuilder::default()
+            .time_in_force(TimeInForce::Day)
+            .order_type(OrderType::Limit)
+            .price(price.abs())
+            .price_effect(price_effect)
+            .legs(legs)
+            .build()?)
+    }
+}
+
+fn effect(debit: bool) -> PriceEffect {
+    if debit {
+        PriceEffect::Debit
+    } else {
+        PriceEffect::Credit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn chain() -> NestedOptionChain {
+        let strikes = |date: &str| -> Vec<Value> {
+            [500, 510, 520, 530]
+                .iter()
+                .map(|strike| {
+                    json!({
+                        "strike-price": strike.to_string(),
+                        "call": format!("SPY   {date}C00{strike}000"),
+                        "call-streamer-symbol": format!(".SPY{date}C{strike}"),
+                        "put": format!("SPY   {date}P00{strike}000"),
+                        "put-streamer-symbol": format!(".SPY{date}P{strike}"),
+                    })
+                })
+                .collect()
+        };
+        serde_json::from_value(json!({
+            "underlying-symbol": "SPY",
+            "root-symbol": "SPY",
+            "option-chain-type": "Standard",
+            "shares-per-contract": 100,
+            "tick-sizes": [],
+            "deliverables": [],
+            "expirations": [
+                {
+                    "expiration-type": "Regular",
+                    "expiration-date": "2026-11-20",
+                    "days-to-expiration": 35,
+                    "settlement-type": "PM",
+                    "strikes": strikes("261120"),
+                },
+                {
+                    "expiration-type": "Regular",
+                    "expiration-date": "2026-12-18",
+                    "days-to-expiration": 63,
+                    "settlement-type": "PM",
+                    "strikes": strikes("261218"),
+                },
+            ],
+        }))
+        .unwrap()
+    }
+
+    fn legs(order: &Order) -> Vec<(String, String, f64)> {
+        let value = serde_json::to_value(order).unwrap();
+        value["legs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|leg| {
+                (
+                    leg["symbol"].as_str().unwrap().to_string(),
+                    leg["action"].as_str().unwrap().to_string(),
+                    leg["quantity"].as_f64().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_strategies_from_chain() {
+        let chain = chain();
+        let builder = StrategyBuilder::new(&chain, 2);
+        let nov = NaiveDate::from_ymd_opt(2026, 11, 20).unwrap();
+        let dec = NaiveDate::from_ymd_opt(2026, 12, 18).unwrap();
+        let strike = |price: i64| Decimal::from(price);
+
+        let put_spread = builder
+            .vertical(nov, OptionKind::Put, strike(520), strike(510), Decimal::ONE)
+            .unwrap();
+        assert!(matches!(put_spread.price_effect(), PriceEffect::Debit));
+        assert_eq!(
+            legs(&put_spread),
+            [
+                (
+                    "SPY   261120P00520000".to_string(),
+                    "Buy to Open".to_string(),
+                    2.0
+                ),
+                (
+                    "SPY   261120P00510000".to_string(),
+                    "Sell to Open".to_string(),
+                    2.0
+                ),
+            ]
+        );
+        let call_spread = builder
+            .vertical(
+                nov,
+                OptionKind::Call,
+                strike(520),
+                strike(510),
+                Decimal::ONE,
+            )
+            .unwrap();
+        assert!(matches!(call_spread.price_effect(), PriceEffect::Credit));
+
+        let condor = builder
+            .iron_condor(
+                dec,
+                [strike(500), strike(510), strike(520), strike(530)],
+                Decimal::ONE,
+            )
+            .unwrap();
+        let actions: Vec<String> = legs(&condor).into_iter().map(|leg| leg.1).collect();
+        assert_eq!(
+            actions,
+            ["Buy to Open", "Sell to Open", "Sell to Open", "Buy to Open"]
+        );
+        assert!(matches!(condor.price_effect(), PriceEffect::Credit));
+
+        let strangle = builder
+            .strangle(
+                nov,
+                Direction::Short,
+                strike(500),
+                strike(530),
+                Decimal::ONE,
+            )
+            .unwrap();
+        assert!(matches!(strangle.price_effect(), PriceEffect::Credit));
+
+        let calendar = builder
+            .calendar(OptionKind::Call, strike(520), nov, dec, Decimal::ONE)
+            .unwrap();
+        assert_eq!(legs(&calendar)[1].0, "SPY   261218C00520000");
+        assert!(matches!(calendar.price_effect(), PriceEffect::Debit));
+
+        let covered = builder.covered_call(nov, strike(530), strike(515)).unwrap();
+        assert_eq!(
+            legs(&covered)[0],
+            ("SPY".to_string(), "Buy to Open".to_string(), 200.0)
+        );
+
+        assert_eq!(
+            builder
+                .vertical(nov, OptionKind::Put, strike(525), strike(510), Decimal::ONE)
+                .unwrap_err(),
+            StrategyError::NoStrike {
+                expiration: nov,
+                strike: strike(525)
+            }
+        );
+        assert!(matches!(
+            builder.calendar(OptionKind::Put, strike(500), dec, nov, Decimal::ONE),
+            Err(StrategyError::InvalidStructure(_))
+        ));
+        assert!(matches!(
+            StrategyBuilder::new(&chain, 0).strangle(
+                nov,
+                Direction::Long,
+                strike(500),
+                strike(530),
+                Decimal::ONE
+            ),
+            Err(StrategyError::Order(_))
+        ));
+    }
+}
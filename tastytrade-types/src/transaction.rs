@@ -0,0 +1,207 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Cash movements between an account and the outside world (ACH, wires, checks).
+//!
+//! These arrive on the account stream as `ExternalTransaction` messages each time a
+//! deposit or withdrawal is created or changes state. Bank account numbers are masked
+//! as soon as they are decoded, so they never reach logs in full.
+
+use crate::account::AccountNumber;
+use chrono::{DateTime, Utc};
+use pretty_simple_display::{DebugPretty, DisplaySimple};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+/// Whether money is coming into or leaving the account.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TransferDirection {
+    /// A deposit.
+    Incoming,
+    /// A withdrawal.
+    Outgoing,
+    /// A direction this version does not know yet, holding the raw API value.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl fmt::Display for TransferDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferDirection::Incoming => write!(f, "Incoming"),
+            TransferDirection::Outgoing => write!(f, "Outgoing"),
+            TransferDirection::Unknown(raw) => write!(f, "{raw}"),
+        }
+    }
+}
+
+/// Processing state of an external transaction.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TransferState {
+    /// Submitted, not yet sent to the bank.
+    Pending,
+    /// Sent to the bank and awaiting settlement.
+    Processing,
+    /// Settled.
+    Completed,
+    /// Cancelled before completion.
+    Cancelled,
+    /// Refused by tastytrade or the bank.
+    Rejected,
+    /// Returned by the bank after completing.
+    Returned,
+    /// A state this version does not know yet, holding the raw API value.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl TransferState {
+    /// Returns `true` once the transaction can no longer change state.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            TransferState::Completed
+                | TransferState::Cancelled
+                | TransferState::Rejected
+                | TransferState::Returned
+        )
+    }
+}
+
+impl fmt::Display for TransferState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferState::Pending => write!(f, "Pending"),
+            TransferState::Processing => write!(f, "Processing"),
+            TransferState::Completed => write!(f, "Completed"),
+            TransferState::Cancelled => write!(f, "Cancelled"),
+            TransferState::Rejected => write!(f, "Rejected"),
+            TransferState::Returned => write!(f, "Returned"),
+            TransferState::Unknown(raw) => write!(f, "{raw}"),
+        }
+    }
+}
+
+/// A bank account number reduced to its last four digits, e.g. `****6789`.
+///
+/// Masking happens during deserialization, so the full number is never held.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct MaskedAccountNumber(String);
+
+impl MaskedAccountNumber {
+    /// Masks `number`, keeping only its last four characters.
+    pub fn new(number: &str) -> Self {
+        let digits: Vec<char> = number.chars().filter(|c| c.is_alphanumeric()).collect();
+        let last_four: String = digits[digits.len().saturating_sub(4)..].iter().collect();
+        Self(format!("****{last_four}"))
+    }
+
+    /// The last four characters of the account number.
+    pub fn last_four(&self) -> &str {
+        self.0.trim_start_matches('*')
+    }
+}
+
+impl fmt::Display for MaskedAccountNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for MaskedAccountNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(Self::new(&raw))
+    }
+}
+
+/// A deposit or withdrawal, as sent by the account stream.
+#[derive(DebugPretty, DisplaySimple, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ExternalTransaction {
+    /// The unique identifier of the transaction.
+    pub id: u64,
+    /// The account the money moves into or out of.
+    pub account_number: AccountNumber,
+    /// The amount transferred, always positive; see `direction`.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Decimal"))]
+    pub amount: Decimal,
+    /// Whether this is a deposit or a withdrawal.
+    pub direction: TransferDirection,
+    /// Current processing state.
+    pub state: TransferState,
+    /// Transfer method, e.g. "ACH" or "Wire".
+    #[serde(default)]
+    pub disbursement_type: Option<String>,
+    /// Name of the external bank, when reported.
+    #[serde(default)]
+    pub bank_name: Option<String>,
+    /// Masked number of the external bank account, when reported.
+    #[serde(default)]
+    pub bank_account_number: Option<MaskedAccountNumber>,
+    /// Date the funds become available to trade, when known.
+    #[serde(default)]
+    pub funds_available_date: Option<String>,
+    /// When the transaction was created.
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the transaction last changed.
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl ExternalTransaction {
+    /// The amount signed by direction: positive for deposits, negative for withdrawals.
+    pub fn signed_amount(&self) -> Decimal {
+        match self.direction {
+            TransferDirection::Outgoing => -self.amount,
+            _ => self.amount,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_external_transaction_masks_bank_account() {
+        let json = r#"{
+            "id": 987654,
+            "account-number": "5WT00001",
+            "amount": "2500.00",
+            "direction": "Outgoing",
+            "state": "Processing",
+            "disbursement-type": "ACH",
+            "bank-name": "First Bank",
+            "bank-account-number": "000123456789",
+            "created-at": "2024-06-03T14:30:00Z"
+        }"#;
+        let transaction: ExternalTransaction = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            transaction.signed_amount(),
+            Decimal::from_str("-2500.00").unwrap()
+        );
+        assert!(!transaction.state.is_final());
+
+        let masked = transaction.bank_account_number.as_ref().unwrap();
+        assert_eq!(masked.to_string(), "****6789");
+        assert_eq!(masked.last_four(), "6789");
+        let serialized = serde_json::to_string(&transaction).unwrap();
+        assert!(!serialized.contains("000123456789"));
+
+        let state: TransferState = serde_json::from_str("\"On Hold\"").unwrap();
+        assert_eq!(state, TransferState::Unknown("On Hold".to_string()));
+    }
+}
@@ -0,0 +1,321 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Client-side checks of an order before it is sent.
+//!
+//! An [`OrderValidator`] holds the tick sizes and quantity precisions published by
+//! the instruments endpoints and checks an [`Order`] against them, and against the US
+//! equity session for market orders. Every problem found is reported as an
+//! [`OrderValidationError`], so a caller can fix the price or quantity instead of
+//! parsing an API rejection.
+//!
+//! Tick sizes are looked up by leg symbol, then by the option root of an equity option
+//! leg, then by instrument type. Holidays are not modelled: see
+//! [`calendar`](crate::calendar).
+//!
+//! ```rust,ignore
+//! let equity = tasty.get_equity("SPY").await?;
+//! let validator = OrderValidator::new()
+//!     .tick_sizes("SPY", equity.option_tick_sizes.unwrap_or_default())
+//!     .quantity_precisions(tasty.list_quantity_decimal_precisions().await?);
+//! validator.validate(&order)?;
+//! ```
+
+use crate::calendar::{MarketSession, us_equity_session};
+use crate::instrument::{InstrumentType, QuantityDecimalPrecision, TickSize, occ_root};
+use crate::order::{AsSymbol, Order, OrderType, Symbol};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A problem found by [`OrderValidator`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "kind")]
+pub enum OrderValidationError {
+    /// The price is not a multiple of the tick size that applies to it.
+    PriceOffTick {
+        /// The leg whose tick size applies.
+        symbol: Symbol,
+        /// The price or stop trigger checked.
+        price: Decimal,
+        /// The tick size at that price.
+        tick: Decimal,
+    },
+    /// The quantity of a leg has more decimals than its instrument allows.
+    QuantityPrecision {
+        /// The leg.
+        symbol: Symbol,
+        /// Its quantity.
+        quantity: Decimal,
+        /// Decimals allowed.
+        decimals: u32,
+    },
+    /// A market order on equities or equity options outside the regular session.
+    OutsideMarketHours {
+        /// The session at the time of the check.
+        session: MarketSession,
+    },
+}
+
+impl fmt::Display for OrderValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderValidationError::PriceOffTick {
+                symbol,
+                price,
+                tick,
+            } => write!(
+                f,
+                "{price} is not a multiple of the {tick} tick of {}",
+                symbol.0
+            ),
+            OrderValidationError::QuantityPrecision {
+                symbol,
+                quantity,
+                decimals,
+            } => write!(
+                f,
+                "quantity {quantity} of {} allows at most {decimals} decimals",
+                symbol.0
+            ),
+            OrderValidationError::OutsideMarketHours { session } => {
+                write!(f, "market orders need the regular session, not {session:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrderValidationError {}
+
+/// Tick sizes, quantity precisions and session rules to check orders against. See the
+/// module docs.
+#[derive(Debug, Clone, Default)]
+pub struct OrderValidator {
+    ticks_by_symbol: HashMap<String, Vec<TickSize>>,
+    ticks_by_type: Vec<(InstrumentType, Vec<TickSize>)>,
+    precisions: Vec<QuantityDecimalPrecision>,
+    ignore_market_hours: bool,
+}
+
+impl OrderValidator {
+    /// A validator checking market hours only, until tick sizes and precisions are
+    /// added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses `ticks` for legs on `symbol`, or on options whose root is `symbol`.
+    pub fn tick_sizes(mut self, symbol: impl AsSymbol, ticks: Vec<TickSize>) -> Self {
+        self.ticks_by_symbol.insert(symbol.as_symbol().0, ticks);
+        self
+    }
+
+    /// Uses `ticks` for legs of `instrument_type` without tick sizes of their own.
+    pub fn default_tick_sizes(
+        mut self,
+        instrument_type: InstrumentType,
+        ticks: Vec<TickSize>,
+    ) -> Self {
+        self.ticks_by_type
+            .retain(|(kind, _)| *kind != instrument_type);
+        self.ticks_by_type.push((instrument_type, ticks));
+        self
+    }
+
+    /// Checks quantities against `precisions`, as returned by
+    /// `list_quantity_decimal_precisions`.
+    pub fn quantity_precisions(mut self, precisions: Vec<QuantityDecimalPrecision>) -> Self {
+        self.precisions = precisions;
+        self
+    }
+
+    /// Skips the market hours check, e.g. when orders are queued for the next session
+    /// on purpose.
+    pub fn ignore_market_hours(mut self) -> Self {
+        self.ignore_market_hours = true;
+        self
+    }
+
+    /// Checks `order` now. Returns every problem found.
+    pub fn validate(&self, order: &Order) -> Result<(), Vec<OrderValidationError>> {
+        self.validate_at(order, Utc::now())
+    }
+
+    /// Checks `order` as of `now`.
+    pub fn validate_at(
+        &self,
+        order: &Order,
+        now: DateTime<Utc>,
+    ) -> Result<(), Vec<OrderValidationError>> {
+        let mut errors = Vec::new();
+
+        if let Some(leg) = order.legs().first() {
+            let prices = [Some(order.price()), order.stop_trigger()];
+            for price in prices.into_iter().flatten().filter(|p| !p.is_zero()) {
+                if let Some(tick) = self.tick_for(leg.instrument_type(), leg.symbol(), price)
+                    && !(price.abs() % tick).is_zero()
+                {
+                    errors.push(OrderValidationError::PriceOffTick {
+                        symbol: leg.symbol().clone(),
+                        price,
+                        tick,
+                    });
+                }
+            }
+        }
+
+        for leg in order.legs() {
+            let quantity = leg.quantity();
+            if let Some(decimals) = self.decimals_for(leg.instrument_type(), leg.symbol())
+                && quantity.normalize().scale() > decimals
+            {
+                errors.push(OrderValidationError::QuantityPrecision {
+                    symbol: leg.symbol().clone(),
+                    quantity,
+                    decimals,
+                });
+            }
+        }
+
+        let equities = order.legs().iter().any(|leg| {
+            matches!(
+                leg.instrument_type(),
+                InstrumentType::Equity | InstrumentType::EquityOption
+            )
+        });
+        if !self.ignore_market_hours && equities && matches!(order.order_type(), OrderType::Market)
+        {
+            let session = us_equity_session(now);
+            if session != MarketSession::Regular {
+                errors.push(OrderValidationError::OutsideMarketHours { session });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn tick_for(
+        &self,
+        instrument_type: &InstrumentType,
+        symbol: &Symbol,
+        price: Decimal,
+    ) -> Option<Decimal> {
+        let ticks = self
+            .ticks_by_symbol
+            .get(&symbol.0)
+            .or_else(|| {
+                let root = occ_root(&symbol.0)?;
+                self.ticks_by_symbol.get(root)
+            })
+            .or_else(|| {
+                self.ticks_by_type
+                    .iter()
+                    .find(|(kind, _)| kind == instrument_type)
+                    .map(|(_, ticks)| ticks)
+            })?;
+        tick_at(ticks, price)
+    }
+
+    fn decimals_for(&self, instrument_type: &InstrumentType, symbol: &Symbol) -> Option<u32> {
+        let same_type = |p: &&QuantityDecimalPrecision| &p.instrument_type == instrument_type;
+        self.precisions
+            .iter()
+            .filter(same_type)
+            .find(|p| p.symbol.as_ref() == Some(symbol))
+            .or_else(|| {
+                self.precisions
+                    .iter()
+                    .filter(same_type)
+                    .find(|p| p.symbol.is_none())
+            })
+            .map(|p| p.value)
+    }
+}
+
+/// The tick that applies at `price`: the first whose threshold is above it, or the
+/// one without a threshold.
+fn tick_at(ticks: &[TickSize], price: Decimal) -> Option<Decimal> {
+    ticks.iter().find_map(|tick| {
+        let value = Decimal::from_str(&tick.value).ok()?;
+        match tick.threshold.as_deref().map(Decimal::from_str) {
+            None => Some(value),
+            Some(Ok(threshold)) if price.abs() < threshold => Some(value),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(value: &str, threshold: Option<&str>) -> TickSize {
+        TickSize {
+            value: value.to_string(),
+            threshold: threshold.map(str::to_string),
+        }
+    }
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_order_validator() {
+        let validator = OrderValidator::new()
+            .tick_sizes("SPY", vec![tick("0.01", Some("3")), tick("0.05", None)])
+            .quantity_precisions(vec![QuantityDecimalPrecision {
+                instrument_type: InstrumentType::Equity,
+                symbol: None,
+                value: 0,
+                minimum_increment_precision: 0,
+            }]);
+        let regular = "2024-06-03T15:00:00Z".parse().unwrap();
+        let closed = "2024-06-08T15:00:00Z".parse().unwrap();
+
+        let option = "SPY   240621P00500000";
+        let order = Order::sell_to_open_option(option, 1, dec("2.37")).unwrap();
+        assert!(validator.validate_at(&order, regular).is_ok());
+        let order = Order::sell_to_open_option(option, 1, dec("3.12")).unwrap();
+        assert_eq!(
+            validator.validate_at(&order, regular).unwrap_err(),
+            [OrderValidationError::PriceOffTick {
+                symbol: Symbol::from(option),
+                price: dec("3.12"),
+                tick: dec("0.05"),
+            }]
+        );
+
+        let order = Order::market_buy("AAPL", dec("1.5")).unwrap();
+        let errors = validator.validate_at(&order, closed).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            OrderValidationError::QuantityPrecision { decimals: 0, .. }
+        ));
+        assert_eq!(
+            errors[1],
+            OrderValidationError::OutsideMarketHours {
+                session: MarketSession::Closed
+            }
+        );
+        assert_eq!(
+            validator
+                .ignore_market_hours()
+                .validate_at(&order, closed)
+                .unwrap_err()
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,128 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+use crate::scripted_server::{ScriptedServer, Step, connect_ack, order_update};
+use tastytrade::prelude::{AccountEvent, AccountMessage, AccountStreamer};
+use tastytrade::streaming::account_streaming::SubRequestAction;
+
+fn order_status(event: &AccountEvent) -> Option<(u64, String)> {
+    match event {
+        AccountEvent::AccountMessage(message) => match message.as_ref() {
+            AccountMessage::Order(order) => Some((order.id.0, order.status.to_string())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn test_account_streamer_parses_scripted_session() {
+    let server = ScriptedServer::start(vec![vec![
+        Step::Expect("connect"),
+        Step::Send(connect_ack()),
+        Step::Ping,
+        Step::Send(order_update(1, "Received")),
+        Step::Send("{not json".to_string()),
+        Step::Send(r#"{"type": "Order", "data": {"id": "wrong"}}"#.to_string()),
+        Step::Send(order_update(1, "Live")),
+        Step::Close,
+    ]])
+    .await;
+
+    let streamer = AccountStreamer::connect_websocket(&server.url(), "session-token")
+        .await
+        .unwrap();
+    streamer
+        .send(SubRequestAction::Connect, Some(vec!["5WT00001"]))
+        .await;
+
+    let ack = streamer.get_event().await.unwrap();
+    assert!(matches!(ack, AccountEvent::StatusMessage(ref status) if status.action == "connect"));
+    let first = streamer.get_event().await.unwrap();
+    assert_eq!(order_status(&first), Some((1, "Received".to_string())));
+    let second = streamer.get_event().await.unwrap();
+    assert_eq!(order_status(&second), Some((1, "Live".to_string())));
+    assert!(streamer.get_event().await.is_err());
+
+    let requests = server.received();
+    assert_eq!(requests[0]["auth-token"], "session-token");
+    assert_eq!(requests[0]["value"][0], "5WT00001");
+    server.finish().await;
+}
+
+#[tokio::test]
+async fn test_account_streamer_reports_dropped_connection() {
+    let server = ScriptedServer::start(vec![vec![
+        Step::Send(order_update(7, "Routed")),
+        Step::Drop,
+    ]])
+    .await;
+
+    let streamer = AccountStreamer::connect_websocket(&server.url(), "session-token")
+        .await
+        .unwrap();
+    let event = streamer.get_event().await.unwrap();
+    assert_eq!(order_status(&event), Some((7, "Routed".to_string())));
+    assert!(streamer.get_event().await.is_err());
+    server.finish().await;
+}
+
+#[tokio::test]
+async fn test_account_streamer_reconnects_after_dropped_connection() {
+    let server = ScriptedServer::start(vec![
+        vec![
+            Step::Expect("connect"),
+            Step::Send(connect_ack()),
+            Step::Send(order_update(3, "Routed")),
+            Step::Drop,
+        ],
+        vec![
+            Step::Expect("connect"),
+            Step::Send(connect_ack()),
+            Step::Send(order_update(3, "Filled")),
+            Step::Close,
+        ],
+    ])
+    .await;
+
+    let streamer = AccountStreamer::connect_websocket(&server.url(), "session-token")
+        .await
+        .unwrap();
+    streamer
+        .send(SubRequestAction::Connect, Some(vec!["5WT00001"]))
+        .await;
+    assert!(matches!(
+        streamer.get_event().await.unwrap(),
+        AccountEvent::StatusMessage(_)
+    ));
+    let before = streamer.get_event().await.unwrap();
+    assert_eq!(order_status(&before), Some((3, "Routed".to_string())));
+    assert!(streamer.get_event().await.is_err());
+
+    // A new connection gets the second script and has to subscribe again
+    let streamer = AccountStreamer::connect_websocket(&server.url(), "session-token")
+        .await
+        .unwrap();
+    streamer
+        .send(SubRequestAction::Connect, Some(vec!["5WT00001"]))
+        .await;
+    assert!(matches!(
+        streamer.get_event().await.unwrap(),
+        AccountEvent::StatusMessage(_)
+    ));
+    let after = streamer.get_event().await.unwrap();
+    assert_eq!(order_status(&after), Some((3, "Filled".to_string())));
+    assert!(streamer.get_event().await.is_err());
+
+    let connects: Vec<_> = server
+        .received()
+        .into_iter()
+        .filter(|request| request["action"] == "connect")
+        .collect();
+    assert_eq!(connects.len(), 2);
+    assert!(connects.iter().all(|r| r["auth-token"] == "session-token"));
+    server.finish().await;
+}
@@ -0,0 +1,94 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! Deserializes every recorded response in `tests/fixtures/` into its typed struct.
+//!
+//! The fixtures are API responses with identifiers and tokens scrubbed, one per typed
+//! endpoint. Watchlists and the account transaction history are not covered: the crate
+//! has no typed endpoint for them. A field changing type or going missing upstream
+//! shows up here as a parse error naming the fixture. Every file in the directory must be listed in [`FIXTURES`]. `sandbox_*`
+//! fixtures come from the sandbox environment, which omits some production fields.
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+use tastytrade::prelude::*;
+
+type Check = fn(Value) -> Result<usize, serde_json::Error>;
+
+/// Each fixture with the check of its `data` payload.
+const FIXTURES: &[(&str, Check)] = &[
+    ("accounts.json", items::<AccountInner>),
+    ("customers_me.json", one::<Customer>),
+    ("balances.json", one::<Balance>),
+    ("sandbox_balances.json", one::<Balance>),
+    ("positions.json", items::<FullPosition>),
+    ("live_orders.json", items::<LiveOrderRecord>),
+    ("order_dry_run.json", one::<DryRunResult>),
+    ("quote_tokens.json", one::<QuoteStreamerTokens>),
+    ("sessions.json", one::<LoginResponse>),
+    ("sessions_validate.json", one::<SessionValidation>),
+    ("equity.json", one::<EquityInstrument>),
+    ("sandbox_equity.json", one::<EquityInstrument>),
+    ("equity_options.json", items::<EquityOption>),
+    ("cryptocurrencies.json", items::<Cryptocurrency>),
+    ("futures.json", items::<Future>),
+    ("future_products.json", items::<FutureProduct>),
+    ("future_options.json", items::<FutureOption>),
+    ("warrants.json", items::<Warrant>),
+    (
+        "quantity_decimal_precisions.json",
+        items::<QuantityDecimalPrecision>,
+    ),
+    ("option_chain_nested.json", items::<NestedOptionChain>),
+    ("market_metrics.json", items::<MarketMetrics>),
+    ("net_liq_history.json", items::<NetLiqHistoryPoint>),
+    (
+        "stream_external_transaction.json",
+        one::<ExternalTransaction>,
+    ),
+];
+
+fn fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn one<T: DeserializeOwned>(data: Value) -> Result<usize, serde_json::Error> {
+    serde_json::from_value::<T>(data).map(|_| 1)
+}
+
+fn items<T: DeserializeOwned>(data: Value) -> Result<usize, serde_json::Error> {
+    let items = data.get("items").cloned().unwrap_or(Value::Null);
+    serde_json::from_value::<Vec<T>>(items).map(|items| items.len())
+}
+
+#[test]
+fn test_every_fixture_deserializes() {
+    let mut failures = Vec::new();
+    for (name, check) in FIXTURES {
+        let text = fs::read_to_string(fixture_dir().join(name)).unwrap();
+        let mut body: Value = serde_json::from_str(&text).unwrap();
+        match check(body["data"].take()) {
+            Ok(0) => failures.push(format!("{name}: no items")),
+            Ok(_) => {}
+            Err(e) => failures.push(format!("{name}: {e}")),
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_every_fixture_is_checked() {
+    let on_disk: BTreeSet<String> = fs::read_dir(fixture_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".json"))
+        .collect();
+    let listed: BTreeSet<String> = FIXTURES.iter().map(|(name, _)| name.to_string()).collect();
+    assert_eq!(on_disk, listed);
+}
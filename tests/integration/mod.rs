@@ -1 +1,3 @@
-
+mod account_streaming;
+mod fixtures;
+mod scripted_server;
@@ -0,0 +1,177 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 16/10/26
+******************************************************************************/
+
+//! A local websocket server that plays the account-streaming protocol from a script.
+//!
+//! Each accepted connection runs the next script in order, so a test can describe a
+//! first session that breaks and the one a reconnecting client opens afterwards. Every
+//! text frame sent by the client is recorded for assertions.
+//!
+//! ```rust,ignore
+//! let server = ScriptedServer::start(vec![vec![
+//!     Step::Expect("connect"),
+//!     Step::Send(connect_ack()),
+//!     Step::Send(order_update(1, "Live")),
+//!     Step::Close,
+//! ]])
+//! .await;
+//! let streamer = AccountStreamer::connect_websocket(&server.url(), "token").await?;
+//! ```
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::accept_async;
+use tokio_tungstenite::tungstenite::Message;
+
+/// How long `Expect` waits for the client before failing the script.
+const EXPECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One action of the server in a scripted session.
+#[derive(Debug, Clone)]
+pub enum Step {
+    /// Waits for the next request from the client, skipping heartbeats, and checks its
+    /// `action` field.
+    Expect(&'static str),
+    /// Sends a text frame as is, valid JSON or not.
+    Send(String),
+    /// Sends a ping frame.
+    Ping,
+    /// Pauses before the next step.
+    Wait(Duration),
+    /// Closes the session with a close frame.
+    Close,
+    /// Drops the TCP connection without a close frame.
+    Drop,
+}
+
+/// A running scripted server. Stops when dropped.
+pub struct ScriptedServer {
+    addr: SocketAddr,
+    received: Arc<Mutex<Vec<Value>>>,
+    task: JoinHandle<()>,
+}
+
+impl ScriptedServer {
+    /// Listens on a free local port, running `scripts[n]` for the n-th connection.
+    ///
+    /// A step that does not go as scripted panics the server task, which the client
+    /// sees as a dropped connection and `finish` reports as a failure.
+    pub async fn start(scripts: Vec<Vec<Step>>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let log = received.clone();
+        let task = tokio::spawn(async move {
+            for script in scripts {
+                let (stream, _) = listener.accept().await.unwrap();
+                let ws = accept_async(stream).await.unwrap();
+                run_script(ws, script, &log).await;
+            }
+        });
+
+        Self {
+            addr,
+            received,
+            task,
+        }
+    }
+
+    /// The `ws://` URL of the server.
+    pub fn url(&self) -> String {
+        format!("ws://{}", self.addr)
+    }
+
+    /// Every request received so far, heartbeats included.
+    pub fn received(&self) -> Vec<Value> {
+        self.received.lock().unwrap().clone()
+    }
+
+    /// Waits for every script to complete, panicking if one of them failed.
+    pub async fn finish(mut self) {
+        (&mut self.task).await.expect("scripted server failed");
+    }
+}
+
+impl Drop for ScriptedServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn run_script<S>(
+    mut ws: tokio_tungstenite::WebSocketStream<S>,
+    script: Vec<Step>,
+    log: &Mutex<Vec<Value>>,
+) where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    for step in script {
+        match step {
+            Step::Expect(action) => loop {
+                let frame = tokio::time::timeout(EXPECT_TIMEOUT, ws.next())
+                    .await
+                    .unwrap_or_else(|_| panic!("timed out expecting {action:?}"))
+                    .unwrap_or_else(|| panic!("client left while expecting {action:?}"))
+                    .unwrap();
+                let Message::Text(text) = frame else {
+                    continue;
+                };
+                let request: Value = serde_json::from_str(&text).unwrap();
+                log.lock().unwrap().push(request.clone());
+                if request["action"] == "heartbeat" && action != "heartbeat" {
+                    continue;
+                }
+                assert_eq!(request["action"], action, "unexpected request {request}");
+                break;
+            },
+            Step::Send(text) => ws.send(Message::Text(text.into())).await.unwrap(),
+            Step::Ping => ws.send(Message::Ping(Vec::new().into())).await.unwrap(),
+            Step::Wait(duration) => tokio::time::sleep(duration).await,
+            Step::Close => {
+                let _ = ws.close(None).await;
+                return;
+            }
+            Step::Drop => return,
+        }
+    }
+}
+
+/// The status message sent in reply to a `connect` request.
+pub fn connect_ack() -> String {
+    r#"{
+        "status": "ok",
+        "action": "connect",
+        "web-socket-session-id": "f3a1c2d4-0000-4000-8000-000000000001",
+        "request-id": 1
+    }"#
+    .to_string()
+}
+
+/// An `Order` account message for order `id` in `status`.
+pub fn order_update(id: u64, status: &str) -> String {
+    format!(
+        r#"{{"type": "Order", "data": {{
+            "id": {id},
+            "account-number": "5WT00001",
+            "time-in-force": "Day",
+            "order-type": "Limit",
+            "size": 1,
+            "underlying-symbol": "AAPL",
+            "price": "150.00",
+            "price-effect": "Debit",
+            "status": "{status}",
+            "cancellable": true,
+            "editable": true,
+            "edited": false
+        }}}}"#
+    )
+}